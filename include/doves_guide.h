@@ -0,0 +1,72 @@
+/*
+ * C bindings for the `doves_guide` crate, enabled by its `ffi` feature.  Build a shared library
+ * with:
+ *
+ *     cargo rustc --release --features ffi --crate-type cdylib
+ *
+ * A `Doves` must be freed with `doves_free`.  `DovesRing`s are borrowed from their `Doves`, so
+ * are only valid until it's freed.
+ */
+
+#ifndef DOVES_GUIDE_H
+#define DOVES_GUIDE_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A loaded copy of Dove's Guide */
+typedef struct Doves Doves;
+/* A single ring of bells, borrowed from a `Doves` */
+typedef struct DovesRing DovesRing;
+
+/* Constraints on the rings visited by `doves_for_each` and `doves_nearest`.  Zeroed fields (and
+ * null pointers) don't constrain anything. */
+typedef struct DovesFilter {
+    uint8_t min_bells;
+    uint8_t max_bells;
+    bool ringable_only;
+    bool ground_floor;
+    bool toilet;
+    bool simulator;
+    double min_weight_lbs;
+    const char *county;
+} DovesFilter;
+
+/* Called with each ring visited by `doves_for_each`.  Returning `false` stops the iteration.  If
+ * it's NULL, `doves_for_each` visits nothing and returns 0. */
+typedef bool (*DovesCallback)(const DovesRing *ring, void *user_data);
+
+/* Loading (these return NULL if the file can't be read or parsed) */
+Doves *doves_load_path(const char *path);
+Doves *doves_load_bytes(const uint8_t *data, size_t len);
+void doves_free(Doves *doves);
+
+/* Querying */
+size_t doves_len(const Doves *doves);
+const DovesRing *doves_ring_at(const Doves *doves, size_t idx);
+const DovesRing *doves_get(const Doves *doves, uint32_t id);
+size_t doves_nearest(const Doves *doves, double lat, double long_, size_t n,
+                     const DovesFilter *filter, const DovesRing **out);
+size_t doves_for_each(const Doves *doves, const DovesFilter *filter, DovesCallback callback,
+                      void *user_data);
+
+/* Rings */
+uint32_t doves_ring_id(const DovesRing *ring);
+uint8_t doves_ring_bells(const DovesRing *ring);
+double doves_ring_weight_lbs(const DovesRing *ring);
+bool doves_ring_coordinates(const DovesRing *ring, double *lat, double *long_);
+/* Copies a field (named by its header in Dove's CSV file, e.g. "Place") into `buf`, like
+ * `snprintf`.  Values which don't fit are truncated to a whole number of UTF-8 characters.
+ * Returns the full length of the value, or -1 if the column doesn't exist. */
+ptrdiff_t doves_ring_field(const DovesRing *ring, const char *column, char *buf, size_t buf_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* DOVES_GUIDE_H */
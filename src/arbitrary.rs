@@ -0,0 +1,254 @@
+//! [`Arbitrary`] implementations for the core types, so that they can be generated by
+//! [`proptest`].  Generated values look like the data in Dove's Guide (e.g. tenor weights are
+//! whole numbers of pounds, and coordinates have five decimal places and lie in the British
+//! Isles), so that they survive a round trip through Dove's CSV format.
+
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    collection::btree_set,
+    option, prop_oneof,
+    sample::select,
+    strategy::{BoxedStrategy, Just, Strategy},
+};
+
+use crate::{
+    AccessStatus, Accidental, Affiliation, Coordinates, Dedication, Note, NoteName, Ring,
+    RingBuilder, RingType, TowerId, Weight,
+};
+
+/// The range of tenor weights in Dove's Guide, in pounds
+const MIN_WEIGHT_LBS: u32 = 20;
+const MAX_WEIGHT_LBS: u32 = 10_000;
+/// The bounds of the British Isles, in hundred-thousandths of a degree
+const LAT_RANGE: std::ops::RangeInclusive<i32> = 4_990_000..=5_880_000;
+const LONG_RANGE: std::ops::RangeInclusive<i32> = -1_050_000..=180_000;
+
+/// Samples of the free-text fields of Dove's Guide
+const PLACES: [&str; 8] = [
+    "Oxford",
+    "Ab Kettleby",
+    "Leeds",
+    "St Peter Port",
+    "Bishop's Stortford",
+    "Bury St Edmunds",
+    "Dublin",
+    "Sydney",
+];
+const COUNTIES: [&str; 6] = [
+    "Oxfordshire",
+    "Leicestershire",
+    "West Yorkshire",
+    "Guernsey",
+    "Suffolk",
+    "New South Wales",
+];
+const DEDICATIONS: [&str; 6] = [
+    "S Mary V",
+    "All Saints",
+    "S Peter & S Paul",
+    "Cath Ch of Christ",
+    "S Michael & All Angels",
+    "Holy Trinity",
+];
+const PRACTICES: [&str; 4] = ["Mon 19:30", "Thu (1st, 3rd)", "Wed 19:30-21:00", "Sun"];
+
+impl Arbitrary for Weight {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (MIN_WEIGHT_LBS..=MAX_WEIGHT_LBS, any::<bool>())
+            .prop_map(|(lbs, approximate)| {
+                Weight::from_lbs(f64::from(lbs)).with_approximate(approximate)
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for NoteName {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        use NoteName::*;
+        select(vec![A, B, C, D, E, F, G]).boxed()
+    }
+}
+
+impl Arbitrary for Accidental {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        select(vec![
+            Accidental::Flat,
+            Accidental::Natural,
+            Accidental::Sharp,
+        ])
+        .boxed()
+    }
+}
+
+impl Arbitrary for Note {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<NoteName>(), any::<Accidental>())
+            .prop_map(|(name, accidental)| Note::new(name, accidental))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Affiliation {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![
+            Just(Affiliation::CambridgeUni),
+            Just(Affiliation::ManchesterUni),
+            Just(Affiliation::OxfordUni),
+            Just(Affiliation::OxfordDiocese),
+            Just(Affiliation::Surrey),
+            "[A-Z][A-Za-z]{1,5}".prop_map(|code| code.parse().unwrap()),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Coordinates {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (LAT_RANGE, LONG_RANGE)
+            .prop_map(|(lat, long)| {
+                Coordinates::new(f64::from(lat) / 1e5, f64::from(long) / 1e5).unwrap()
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Ring {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let identity = (
+            1..20_000u32,
+            select(&PLACES[..]),
+            // Most rings have between 3 and 12 bells, but carillons can have many more
+            prop_oneof![9 => 3..=12u8, 1 => 1..=72u8],
+            select(&COUNTIES[..]),
+            select(&DEDICATIONS[..]),
+        );
+        let details = (
+            prop_oneof![
+                8 => Just(RingType::FullCircle),
+                1 => Just(RingType::Carillon),
+                1 => Just(RingType::Chime),
+            ],
+            prop_oneof![
+                8 => Just(AccessStatus::Ringable),
+                1 => Just(AccessStatus::Restricted),
+                1 => Just(AccessStatus::Unringable),
+            ],
+            option::of(any::<Weight>()),
+            option::of(any::<Note>()),
+            option::of(any::<Coordinates>()),
+            btree_set(any::<Affiliation>(), 0..=2),
+            option::of(select(&PRACTICES[..])),
+            any::<[bool; 3]>(),
+        );
+        (identity, details)
+            .prop_map(|(identity, details)| {
+                let (id, place, bells, county, dedication) = identity;
+                let (ring_type, access, weight, note, coords, affiliations, practice, flags) =
+                    details;
+                let [ground_floor, toilet, simulator] = flags;
+                let mut builder = RingBuilder::new(TowerId::new(id), place, bells)
+                    .ring_type(ring_type)
+                    .access(access)
+                    .county(county)
+                    .dedication(dedication.parse::<Dedication>().unwrap())
+                    .ground_floor(ground_floor)
+                    .toilet(toilet)
+                    .simulator(simulator);
+                if let Some(weight) = weight {
+                    builder = builder.weight(weight);
+                }
+                if let Some(note) = note {
+                    builder = builder.note(note);
+                }
+                if let Some(coords) = coords {
+                    builder = builder.coordinates(coords);
+                }
+                if let Some(practice) = practice {
+                    builder = builder.practice(practice);
+                }
+                for affiliation in affiliations {
+                    builder = builder.affiliation(affiliation);
+                }
+                builder
+                    .build()
+                    .expect("Generated rings should always be valid")
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*};
+
+    use crate::{Column, Doves, Ring};
+
+    /// Writes `rings` in the format of Dove's CSV file
+    fn to_csv(rings: &[Ring]) -> Vec<u8> {
+        let mut csv = Vec::new();
+        let columns = Column::all().collect::<Vec<_>>();
+        Doves::from(rings.to_vec())
+            .write_csv(&mut csv, &columns)
+            .unwrap();
+        csv
+    }
+
+    /// Checks that `loaded` contains exactly the same `Ring`s as `rings`, comparing every field
+    fn assert_same(rings: &[Ring], loaded: &Doves) -> Result<(), TestCaseError> {
+        prop_assert_eq!(loaded.len(), rings.len());
+        for (ring, loaded) in rings.iter().zip(loaded) {
+            let values = |ring| {
+                Column::all()
+                    .map(|column| column.value(ring))
+                    .collect::<Vec<_>>()
+            };
+            prop_assert_eq!(values(ring), values(loaded), "{} changed", ring);
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[cfg(feature = "serde")]
+        #[test]
+        fn round_trip_serde(rings in vec(any::<Ring>(), 0..20)) {
+            let loaded = Doves::from_reader(to_csv(&rings).as_slice()).unwrap();
+            assert_same(&rings, &loaded)?;
+        }
+
+        #[test]
+        fn round_trip_fast(rings in vec(any::<Ring>(), 0..20)) {
+            let loaded = Doves::from_bytes_fast(&to_csv(&rings)).unwrap();
+            assert_same(&rings, &loaded)?;
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn loaders_agree(rings in vec(any::<Ring>(), 0..20)) {
+            let csv = to_csv(&rings);
+            let slow = Doves::from_reader(csv.as_slice()).unwrap();
+            let fast = Doves::from_bytes_fast(&csv).unwrap();
+            assert_same(slow.towers(), &fast)?;
+        }
+    }
+}
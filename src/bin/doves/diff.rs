@@ -0,0 +1,89 @@
+use std::{collections::HashMap, error::Error, path::PathBuf};
+
+use clap::Args;
+use doves_guide::{Doves, Ring};
+use serde_json::{json, Value};
+
+use crate::OutputFormat;
+
+/// The arguments of `doves diff`
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    /// The older version of `dove.csv`
+    old: PathBuf,
+    /// The newer version of `dove.csv`
+    new: PathBuf,
+    /// How to print the changes
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+impl DiffArgs {
+    pub fn run(&self) -> Result<(), Box<dyn Error>> {
+        let old = crate::load_path(&self.old)?;
+        let new = crate::load_path(&self.new)?;
+        match self.format.report_format() {
+            Some(format) => {
+                let table = old.diff_table(&new);
+                if table.rows.is_empty() {
+                    println!("no changes");
+                } else {
+                    print!("{}", table.render(format));
+                }
+            }
+            None => println!("{}", serde_json::to_string_pretty(&diff_json(&old, &new))?),
+        }
+        Ok(())
+    }
+}
+
+/// The added, removed and changed [`Ring`]s between two versions of Dove's Guide, matched by
+/// [`RingKey`](doves_guide::RingKey) (as in [`Doves::diff_table`])
+fn diff_json(old: &Doves, new: &Doves) -> Value {
+    let old_rings = old.iter().map(|r| (r.key(), r)).collect::<HashMap<_, _>>();
+    let new_rings = new.iter().map(|r| (r.key(), r)).collect::<HashMap<_, _>>();
+
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for ring in new {
+        let old_ring = match old_rings.get(&ring.key()) {
+            Some(old_ring) => old_ring,
+            None => {
+                added.push(ring_json(ring));
+                continue;
+            }
+        };
+        let changes = old_ring
+            .diff(ring)
+            .into_iter()
+            .map(|change| {
+                json!({
+                    "field": change.column.dove_header(),
+                    "old": change.old,
+                    "new": change.new,
+                })
+            })
+            .collect::<Vec<_>>();
+        if !changes.is_empty() {
+            let mut ring_json = ring_json(ring);
+            ring_json["changes"] = Value::Array(changes);
+            changed.push(ring_json);
+        }
+    }
+    let removed = old
+        .iter()
+        .filter(|r| !new_rings.contains_key(&r.key()))
+        .map(ring_json)
+        .collect::<Vec<_>>();
+
+    json!({ "added": added, "removed": removed, "changed": changed })
+}
+
+/// The fields identifying a [`Ring`] in the JSON output
+fn ring_json(ring: &Ring) -> Value {
+    json!({
+        "id": ring.id.get(),
+        "towerbase_id": ring.towerbase_id,
+        "ring": ring.to_string(),
+    })
+}
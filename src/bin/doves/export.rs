@@ -0,0 +1,60 @@
+use std::{error::Error, fs::File, io::Write, path::PathBuf};
+
+use clap::{Args, ValueEnum};
+use doves_guide::{Column, Doves};
+
+use crate::filter::FilterArgs;
+
+/// The arguments of `doves export`
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// The format to convert the guide into
+    #[arg(long, value_enum)]
+    format: ExportFormat,
+    /// The file to write to, rather than printing to the terminal
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    #[command(flatten)]
+    filter: FilterArgs,
+}
+
+/// The formats which `doves export` can write
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum ExportFormat {
+    /// A GeoJSON `FeatureCollection` of points, for GIS software and web maps
+    Geojson,
+    /// GPX waypoints, for sat-navs and GPS apps
+    Gpx,
+    /// SQL statements which create a `rings` table in SQLite (e.g. `sqlite3 dove.db < dove.sql`)
+    Sqlite,
+    /// A compact JSON bundle, for web apps
+    Json,
+    /// CSV in the same format as `dove.csv`
+    Csv,
+    /// An iCalendar feed of practice nights
+    Ical,
+}
+
+impl ExportArgs {
+    pub fn run(&self, doves: &Doves) -> Result<(), Box<dyn Error>> {
+        let query = self.filter.query(doves);
+        let mut output: Box<dyn Write> = match &self.output {
+            Some(path) => Box::new(File::create(path)?),
+            None => Box::new(std::io::stdout().lock()),
+        };
+        let text = match self.format {
+            ExportFormat::Geojson => doves.to_geojson(query),
+            ExportFormat::Gpx => doves.to_gpx(query),
+            ExportFormat::Sqlite => doves.to_sql(query),
+            ExportFormat::Json => doves.json_bundle().render(query),
+            ExportFormat::Ical => doves.practice_calendar(query),
+            ExportFormat::Csv => {
+                let columns = Column::all().collect::<Vec<_>>();
+                query.write_csv(&mut output, &columns)?;
+                return Ok(());
+            }
+        };
+        output.write_all(text.as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use clap::Args;
+use doves_guide::{Affiliation, Doves, Query, Weight};
+
+/// The arguments which filter the rings used by a subcommand
+#[derive(Debug, Args)]
+pub struct FilterArgs {
+    /// Only include rings with at least this many bells
+    #[arg(long)]
+    min_bells: Option<u8>,
+    /// Only include rings with at most this many bells
+    #[arg(long)]
+    max_bells: Option<u8>,
+    /// Only include rings in this county (ignoring case)
+    #[arg(long)]
+    county: Option<String>,
+    /// Only include rings which can be rung
+    #[arg(long)]
+    ringable: bool,
+    /// Only include rings which are rung from the ground floor
+    #[arg(long)]
+    ground_floor: bool,
+    /// Only include rings in churches with toilets
+    #[arg(long)]
+    toilet: bool,
+    /// Only include rings with a simulator
+    #[arg(long)]
+    simulator: bool,
+    /// Only include rings with this affiliation code (e.g. `ODG`)
+    #[arg(long)]
+    affiliation: Option<Affiliation>,
+    /// Only include rings whose tenor is at least this heavy (in `cwt-qr-lb`, e.g. `12-0-0`)
+    #[arg(long)]
+    min_weight: Option<Weight>,
+    /// Only include rings whose tenor is at most this heavy (in `cwt-qr-lb`, e.g. `12-0-0`)
+    #[arg(long)]
+    max_weight: Option<Weight>,
+}
+
+impl FilterArgs {
+    /// A [`Query`] matching the rings which pass these filters
+    pub fn query<'d>(&self, doves: &'d Doves) -> Query<'d> {
+        let mut query = doves.query();
+        if let Some(bells) = self.min_bells {
+            query = query.min_bells(bells);
+        }
+        if let Some(bells) = self.max_bells {
+            query = query.max_bells(bells);
+        }
+        if let Some(county) = &self.county {
+            query = query.county(county.as_str());
+        }
+        if self.ringable {
+            query = query.ringable_only();
+        }
+        if self.ground_floor {
+            query = query.ground_floor();
+        }
+        if self.toilet {
+            query = query.toilet();
+        }
+        if self.simulator {
+            query = query.simulator();
+        }
+        if let Some(affiliation) = &self.affiliation {
+            query = query.affiliation(affiliation.clone());
+        }
+        if let Some(weight) = self.min_weight {
+            query = query.min_weight(weight);
+        }
+        if let Some(weight) = self.max_weight {
+            query = query.max_weight(weight);
+        }
+        query
+    }
+}
@@ -0,0 +1,140 @@
+//! `doves`: a command-line interface to Dove's Guide for Church Bell Ringers.
+
+mod diff;
+mod export;
+mod filter;
+mod nearest;
+mod query;
+mod stats;
+
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use doves_guide::{Doves, ReportFormat, Source};
+
+/// Where the latest version of Dove's `dove.csv` file can be downloaded from
+const DOVE_CSV_URL: &str = "https://dove.cccbr.org.uk/downloads/dove.csv";
+
+/// Query Dove's Guide for Church Bell Ringers
+#[derive(Debug, Parser)]
+#[command(name = "doves", version)]
+struct Cli {
+    /// The path of Dove's `dove.csv` file
+    #[arg(long, global = true, default_value = "dove.csv")]
+    csv: PathBuf,
+    /// Download the latest `dove.csv` from Dove's Guide, rather than reading `--csv`
+    #[arg(long, global = true)]
+    download: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// List the rings which match some filters
+    Query(query::QueryArgs),
+    /// List the rings nearest to a location or postcode
+    Nearest(nearest::NearestArgs),
+    /// Show the rings which were added, removed or changed between two versions of `dove.csv`
+    Diff(diff::DiffArgs),
+    /// Convert the guide (or some of its rings) into another format
+    Export(export::ExportArgs),
+    /// Summarise the guide (or some of its rings) with statistics
+    Stats(stats::StatsArgs),
+}
+
+/// The formats in which lists of rings can be printed
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum OutputFormat {
+    /// A plain text table
+    Table,
+    /// A Markdown table
+    Markdown,
+    /// An HTML table
+    Html,
+    /// JSON
+    Json,
+}
+
+impl OutputFormat {
+    /// The [`ReportFormat`] for this `OutputFormat`, or `None` for JSON
+    fn report_format(self) -> Option<ReportFormat> {
+        match self {
+            OutputFormat::Table => Some(ReportFormat::PlainText),
+            OutputFormat::Markdown => Some(ReportFormat::Markdown),
+            OutputFormat::Html => Some(ReportFormat::Html),
+            OutputFormat::Json => None,
+        }
+    }
+}
+
+impl Cli {
+    /// Loads Dove's Guide from wherever the user asked
+    fn load(&self) -> Result<Doves, Box<dyn Error>> {
+        if self.download {
+            let response = ureq::get(DOVE_CSV_URL).call()?;
+            let exported = response.header("Last-Modified").and_then(parse_http_date);
+            let mut doves = Doves::from_reader(response.into_reader())?;
+            let metadata = doves.metadata_mut();
+            metadata.source = Some(Source::Url(DOVE_CSV_URL.to_owned()));
+            metadata.exported = exported;
+            Ok(doves)
+        } else {
+            load_path(&self.csv)
+        }
+    }
+}
+
+/// Loads Dove's Guide from a `dove.csv` file, naming the file in any error
+fn load_path(path: &Path) -> Result<Doves, Box<dyn Error>> {
+    Doves::from_path(path).map_err(|e| format!("can't read {}: {}", path.display(), e).into())
+}
+
+/// Parses an HTTP date (e.g. `Wed, 01 May 2024 09:30:00 GMT`), as used by the `Last-Modified`
+/// header
+fn parse_http_date(s: &str) -> Option<SystemTime> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let mut parts = s.split_whitespace().skip(1); // Skip the day of the week
+    let day = parts.next()?.parse::<u64>().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|&m| m == month)? as u64 + 1;
+    let year = parts.next()?.parse::<u64>().ok()?;
+    let mut time = parts.next()?.split(':').map(|t| t.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (time.next()??, time.next()??, time.next()??);
+
+    // Convert the date into days since the epoch, using the algorithm from
+    // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let month_index = (month + 9) % 12; // March is 0
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = (era * 146_097 + day_of_era).checked_sub(719_468)?;
+    let secs = days * 86_400 + hours * 3600 + minutes * 60 + seconds;
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(&cli) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: &Cli) -> Result<(), Box<dyn Error>> {
+    match &cli.command {
+        Command::Query(args) => args.run(&cli.load()?),
+        Command::Nearest(args) => args.run(&cli.load()?),
+        Command::Diff(args) => args.run(),
+        Command::Export(args) => args.run(&cli.load()?),
+        Command::Stats(args) => args.run(&cli.load()?),
+    }
+}
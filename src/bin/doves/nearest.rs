@@ -0,0 +1,81 @@
+use std::error::Error;
+
+use clap::Args;
+use doves_guide::{Coordinates, Doves, Postcode, Ring, Table};
+use serde_json::json;
+
+use crate::OutputFormat;
+
+/// The arguments of `doves nearest`
+#[derive(Debug, Args)]
+pub struct NearestArgs {
+    /// The latitude to search from, in decimal degrees
+    #[arg(long, requires = "long", allow_negative_numbers = true)]
+    lat: Option<f64>,
+    /// The longitude to search from, in decimal degrees
+    #[arg(long, requires = "lat", allow_negative_numbers = true)]
+    long: Option<f64>,
+    /// A postcode to search from instead of a latitude and longitude.  This is located using the
+    /// towers in Dove's Guide, so is only approximate.
+    #[arg(long, conflicts_with_all = ["lat", "long"], required_unless_present = "lat")]
+    postcode: Option<Postcode>,
+    /// The number of rings to list
+    #[arg(long, default_value_t = 10)]
+    count: usize,
+    /// Only list rings with at least this many bells
+    #[arg(long)]
+    min_bells: Option<u8>,
+    /// Only list rings which can be rung
+    #[arg(long)]
+    ringable: bool,
+    /// How to print the nearest rings
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+impl NearestArgs {
+    pub fn run(&self, doves: &Doves) -> Result<(), Box<dyn Error>> {
+        let coords = match (&self.postcode, self.lat.zip(self.long)) {
+            (Some(postcode), _) => doves
+                .locate_postcode(postcode)
+                .ok_or_else(|| format!("no towers in Dove's Guide are near {}", postcode))?,
+            (None, Some((lat, long))) => Coordinates::new(lat, long)?,
+            (None, None) => return Err("either --postcode or --lat and --long is needed".into()),
+        };
+
+        let min_bells = self.min_bells.unwrap_or(0);
+        let ringable = self.ringable;
+        let filter = |ring: &Ring| ring.bells >= min_bells && (!ringable || ring.is_ringable());
+        let nearest = doves.nearest(coords, self.count, filter);
+
+        match self.format.report_format() {
+            Some(format) => {
+                let title = format!("Nearest rings to {}", coords);
+                let mut table = Table::from_rings(title, nearest.iter().map(|n| n.ring));
+                table.headings.insert(0, "Distance".to_owned());
+                for (row, n) in table.rows.iter_mut().zip(&nearest) {
+                    row.insert(0, format!("{:.1} mi", n.distance.miles()));
+                }
+                print!("{}", table.render(format));
+            }
+            None => {
+                let rings = nearest
+                    .iter()
+                    .map(|n| {
+                        json!({
+                            "id": n.ring.id.get(),
+                            "place": n.ring.place.as_str(),
+                            "county": n.ring.county.as_deref(),
+                            "dedication": n.ring.dedication.as_str(),
+                            "bells": n.ring.bells,
+                            "distance_km": n.distance.km(),
+                            "distance_miles": n.distance.miles(),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&rings)?);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use std::error::Error;
+
+use clap::Args;
+use doves_guide::{Doves, Table};
+
+use crate::{filter::FilterArgs, OutputFormat};
+
+/// The arguments of `doves query`
+#[derive(Debug, Args)]
+pub struct QueryArgs {
+    #[command(flatten)]
+    filter: FilterArgs,
+    /// How to print the matching rings
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+impl QueryArgs {
+    pub fn run(&self, doves: &Doves) -> Result<(), Box<dyn Error>> {
+        let query = self.filter.query(doves);
+        match self.format.report_format() {
+            Some(format) => {
+                let title = format!("{} matching rings", query.count());
+                print!("{}", Table::from_rings(title, query.iter()).render(format));
+            }
+            None => println!("{}", doves.json_bundle().render(query)),
+        }
+        Ok(())
+    }
+}
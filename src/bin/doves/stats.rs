@@ -0,0 +1,79 @@
+use std::{convert::TryInto, error::Error};
+
+use clap::{Args, ValueEnum};
+use doves_guide::{Doves, Stats, Table};
+use serde_json::{Map, Value};
+
+use crate::{filter::FilterArgs, OutputFormat};
+
+/// The arguments of `doves stats`
+#[derive(Debug, Args)]
+pub struct StatsArgs {
+    /// Count the rings in each group, rather than printing every summary
+    #[arg(long, value_enum)]
+    by: Option<GroupBy>,
+    #[command(flatten)]
+    filter: FilterArgs,
+    /// How to print the statistics
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+/// The ways in which `doves stats` can group rings
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum GroupBy {
+    County,
+    Country,
+    Bells,
+}
+
+impl StatsArgs {
+    pub fn run(&self, doves: &Doves) -> Result<(), Box<dyn Error>> {
+        let stats = Stats::from_rings(self.filter.query(doves));
+        let [overview, by_bells, by_country, by_county]: [Table; 4] = stats
+            .tables()
+            .try_into()
+            .expect("Stats::tables should return four tables");
+        let tables = match self.by {
+            None => vec![overview, by_bells, by_country, by_county],
+            Some(GroupBy::Bells) => vec![by_bells],
+            Some(GroupBy::Country) => vec![by_country],
+            Some(GroupBy::County) => vec![by_county],
+        };
+
+        match self.format.report_format() {
+            Some(format) => {
+                let rendered = tables.iter().map(|t| t.render(format)).collect::<Vec<_>>();
+                print!("{}", rendered.join("\n"));
+            }
+            None => {
+                let json = tables
+                    .iter()
+                    .map(|t| (t.title.clone(), table_json(t)))
+                    .collect::<Map<_, _>>();
+                println!("{}", serde_json::to_string_pretty(&json)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A [`Table`] as a JSON array with one object per row, keyed by the table's headings.  Cells
+/// which are whole numbers (e.g. counts) become JSON numbers.
+fn table_json(table: &Table) -> Value {
+    let rows = table.rows.iter().map(|row| {
+        let object = table
+            .headings
+            .iter()
+            .zip(row)
+            .map(|(heading, cell)| {
+                let value = cell
+                    .parse::<u64>()
+                    .map_or_else(|_| Value::from(cell.as_str()), Value::from);
+                (heading.clone(), value)
+            })
+            .collect::<Map<_, _>>();
+        Value::Object(object)
+    });
+    Value::Array(rows.collect())
+}
@@ -0,0 +1,127 @@
+use crate::{
+    spatial::{from_vector, unit_vector},
+    Coordinates, Distance, Doves, Ring,
+};
+
+/// A rectangular region of latitude and longitude, such as the area visible in a map viewport.
+/// If the minimum longitude is greater than the maximum, the region crosses the antimeridian
+/// (e.g. a view of the Pacific from `170.0` to `-170.0`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    /// The south-west corner
+    pub min: Coordinates,
+    /// The north-east corner
+    pub max: Coordinates,
+}
+
+impl Bounds {
+    /// Creates `Bounds` from its south-west and north-east corners
+    pub fn new(min: Coordinates, max: Coordinates) -> Self {
+        Self { min, max }
+    }
+
+    /// `true` if `coords` are inside these `Bounds` (including on the edge)
+    pub fn contains(&self, coords: Coordinates) -> bool {
+        let lat_in_range = (self.min.lat()..=self.max.lat()).contains(&coords.lat());
+        let long_in_range = if self.min.long() <= self.max.long() {
+            (self.min.long()..=self.max.long()).contains(&coords.long())
+        } else {
+            // The bounds cross the antimeridian
+            coords.long() >= self.min.long() || coords.long() <= self.max.long()
+        };
+        lat_in_range && long_in_range
+    }
+}
+
+impl Doves {
+    /// Gets every [`Ring`] whose [best coordinates](Ring::best_coordinates) are inside the
+    /// [`Bounds`] with south-west corner `min` and north-east corner `max`, in the order they
+    /// appear in Dove's Guide.  This is cheap enough to run every time a map is panned.
+    pub fn in_bounds(&self, min: Coordinates, max: Coordinates) -> Vec<&Ring> {
+        let bounds = Bounds::new(min, max);
+        self.iter()
+            .filter(|ring| {
+                ring.best_coordinates()
+                    .is_some_and(|(coords, _)| bounds.contains(coords))
+            })
+            .collect()
+    }
+}
+
+/// The geographic extent of a set of [`Ring`]s, e.g. for zooming a map to show an association's
+/// territory or the towers on an outing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Extent {
+    /// The centre of the `Ring`s (i.e. the point on the Earth's surface closest to their average
+    /// position)
+    pub centroid: Coordinates,
+    /// The smallest [`Bounds`] containing every `Ring`.  These cross the antimeridian if that
+    /// makes them smaller (e.g. for `Ring`s in Fiji and Samoa).
+    pub bounds: Bounds,
+    /// The largest [`Distance`] between any two `Ring`s
+    pub max_spread: Distance,
+}
+
+impl Extent {
+    /// Computes the `Extent` of a set of [`Ring`]s, using their
+    /// [best coordinates](Ring::best_coordinates).  `Ring`s without coordinates are ignored, and
+    /// `None` is returned if none of the `Ring`s have coordinates.  Computing the `max_spread`
+    /// compares every pair of `Ring`s, so this takes time quadratic in the number of `Ring`s.
+    pub fn from_rings<'d>(rings: impl IntoIterator<Item = &'d Ring>) -> Option<Self> {
+        let points = rings
+            .into_iter()
+            .filter_map(|ring| Some(ring.best_coordinates()?.0))
+            .collect::<Vec<_>>();
+        if points.is_empty() {
+            return None;
+        }
+
+        let mut sum = [0.0; 3];
+        for &point in &points {
+            let vector = unit_vector(point);
+            for i in 0..3 {
+                sum[i] += vector[i];
+            }
+        }
+        // The average position can only be zero if the `Ring`s are spread evenly around the
+        // globe, in which case any of them is as good a centre as any other
+        let centroid = from_vector(sum).unwrap_or(points[0]);
+
+        let lats = points.iter().map(|c| c.lat());
+        let min_lat = lats.clone().fold(f64::INFINITY, f64::min);
+        let max_lat = lats.fold(f64::NEG_INFINITY, f64::max);
+        // The narrowest range of longitudes is everything except the largest gap between
+        // consecutive longitudes (including the gap which wraps around the antimeridian)
+        let mut longs = points.iter().map(|c| c.long()).collect::<Vec<_>>();
+        longs.sort_by(f64::total_cmp);
+        let (mut min_long, mut max_long) = (longs[0], longs[longs.len() - 1]);
+        let mut largest_gap = 360.0 - (max_long - min_long);
+        for pair in longs.windows(2) {
+            if pair[1] - pair[0] > largest_gap {
+                largest_gap = pair[1] - pair[0];
+                min_long = pair[1];
+                max_long = pair[0];
+            }
+        }
+        let bounds = Bounds::new(
+            Coordinates::new(min_lat, min_long).unwrap(),
+            Coordinates::new(max_lat, max_long).unwrap(),
+        );
+
+        let mut max_spread = Distance::default();
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                let distance = a.distance_to(*b);
+                if distance > max_spread {
+                    max_spread = distance;
+                }
+            }
+        }
+
+        Some(Self {
+            centroid,
+            bounds,
+            max_spread,
+        })
+    }
+}
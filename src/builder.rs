@@ -0,0 +1,413 @@
+use std::{
+    collections::BTreeSet,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    AccessStatus, Affiliation, ChurchCareId, Contractor, Coordinates, Country, CountryCode,
+    Dedication, Details, Diocese, Frequency, Note, Postcode, Ring, RingFeature, RingFlags,
+    RingType, SmolStr, Symbol, TowerId, Weight,
+};
+
+/// A builder for [`Ring`]s, useful for creating test fixtures or records for towers which aren't
+/// (yet) in Dove's Guide.  Every field other than the ID, place and number of bells is optional;
+/// missing fields are given sensible defaults (`None`, `false`, etc.).
+#[derive(Debug, Clone)]
+pub struct RingBuilder {
+    ring: Ring,
+}
+
+impl RingBuilder {
+    /// Creates a new `RingBuilder` for a `Ring` of `bells` bells in `place`
+    #[allow(deprecated)] // `dove_id` still needs to be initialised
+    pub fn new(id: TowerId, place: impl Into<SmolStr>, bells: u8) -> Self {
+        Self {
+            ring: Ring {
+                id,
+                ring_type: RingType::FullCircle,
+                bells,
+                access: AccessStatus::Ringable,
+                flags: RingFlags::empty(),
+                affiliations: BTreeSet::new(),
+                practice: None,
+                towerbase_id: 0,
+                felstead_id: None,
+                dove_id: None,
+                weight: None,
+                note: None,
+                freq: None,
+                details: Details::Partial,
+                extra_info: Vec::new(),
+                url: None,
+                semitones: None,
+                place: place.into(),
+                place2: None,
+                place_county_list: None,
+                county: None,
+                country: None,
+                iso_3166_code: None,
+                os_grid_ref: None,
+                postcode: None,
+                coordinates: None,
+                satnav_coordinates: None,
+                overhaul_year: None,
+                contractor: None,
+                tune_year: None,
+                building_id: None,
+                building_grade: None,
+                church_care: None,
+                dedication: Dedication::Unknown,
+                alt_name: None,
+                diocese: None,
+            },
+        }
+    }
+
+    /// Sets the [`RingType`] (defaults to [`RingType::FullCircle`])
+    pub fn ring_type(mut self, ring_type: RingType) -> Self {
+        self.ring.ring_type = ring_type;
+        self
+    }
+
+    /// Sets the [`AccessStatus`] (defaults to [`AccessStatus::Ringable`])
+    pub fn access(mut self, access: AccessStatus) -> Self {
+        self.ring.access = access;
+        self
+    }
+
+    /// Sets whether the `Ring` is rung from the ground floor (defaults to `false`)
+    pub fn ground_floor(mut self, ground_floor: bool) -> Self {
+        self.ring.flags.set(RingFlags::GROUND_FLOOR, ground_floor);
+        self
+    }
+
+    /// Sets whether the church has a toilet (defaults to `false`)
+    pub fn toilet(mut self, toilet: bool) -> Self {
+        self.ring.flags.set(RingFlags::TOILET, toilet);
+        self
+    }
+
+    /// Sets whether the `Ring` has a simulator (defaults to `false`)
+    pub fn simulator(mut self, simulator: bool) -> Self {
+        self.ring.flags.set(RingFlags::SIMULATOR, simulator);
+        self
+    }
+
+    /// Adds an [`Affiliation`]
+    pub fn affiliation(mut self, affiliation: Affiliation) -> Self {
+        self.ring.affiliations.insert(affiliation);
+        self
+    }
+
+    /// Sets the practice night description
+    pub fn practice(mut self, practice: impl Into<String>) -> Self {
+        self.ring.practice = Some(practice.into());
+        self
+    }
+
+    /// Sets the TowerBase ID (defaults to `0`)
+    pub fn towerbase_id(mut self, towerbase_id: u32) -> Self {
+        self.ring.towerbase_id = towerbase_id;
+        self
+    }
+
+    /// Sets the Felstead ID
+    pub fn felstead_id(mut self, felstead_id: u32) -> Self {
+        self.ring.felstead_id = Some(felstead_id);
+        self
+    }
+
+    /// Sets the [`Weight`] of the tenor
+    pub fn weight(mut self, weight: Weight) -> Self {
+        self.ring.weight = Some(weight);
+        self
+    }
+
+    /// Sets the [`Note`] of the tenor
+    pub fn note(mut self, note: Note) -> Self {
+        self.ring.note = Some(note);
+        self
+    }
+
+    /// Sets the [`Frequency`] of the tenor
+    pub fn freq(mut self, freq: Frequency) -> Self {
+        self.ring.freq = Some(freq);
+        self
+    }
+
+    /// Sets how complete the bell [`Details`] are (defaults to [`Details::Partial`])
+    pub fn details(mut self, details: Details) -> Self {
+        self.ring.details = details;
+        self
+    }
+
+    /// Adds a [`RingFeature`] to the `extra_info`
+    pub fn feature(mut self, feature: RingFeature) -> Self {
+        self.ring.extra_info.push(feature);
+        self
+    }
+
+    /// Sets the URL of the `Ring`'s web-page
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.ring.url = Some(url.into());
+        self
+    }
+
+    /// Sets the semitones of the `Ring`
+    pub fn semitones(mut self, semitones: impl Into<Box<str>>) -> Self {
+        self.ring.semitones = Some(semitones.into());
+        self
+    }
+
+    /// Sets the secondary place name
+    pub fn place2(mut self, place2: impl Into<Box<str>>) -> Self {
+        self.ring.place2 = Some(place2.into());
+        self
+    }
+
+    /// Sets the place name used in county lists
+    pub fn place_county_list(mut self, place_county_list: impl Into<Box<str>>) -> Self {
+        self.ring.place_county_list = Some(place_county_list.into());
+        self
+    }
+
+    /// Sets the county
+    pub fn county(mut self, county: impl Into<Symbol>) -> Self {
+        self.ring.county = Some(county.into());
+        self
+    }
+
+    /// Sets the [`Country`]
+    pub fn country(mut self, country: Country) -> Self {
+        self.ring.country = Some(country);
+        self
+    }
+
+    /// Sets the ISO 3166-1 [`CountryCode`]
+    pub fn iso_3166_code(mut self, iso_3166_code: CountryCode) -> Self {
+        self.ring.iso_3166_code = Some(iso_3166_code);
+        self
+    }
+
+    /// Sets the OS grid reference
+    pub fn os_grid_ref(mut self, os_grid_ref: impl Into<SmolStr>) -> Self {
+        self.ring.os_grid_ref = Some(os_grid_ref.into());
+        self
+    }
+
+    /// Sets the [`Postcode`]
+    pub fn postcode(mut self, postcode: Postcode) -> Self {
+        self.ring.postcode = Some(postcode);
+        self
+    }
+
+    /// Sets the [`Coordinates`] of the tower
+    pub fn coordinates(mut self, coordinates: Coordinates) -> Self {
+        self.ring.coordinates = Some(coordinates);
+        self
+    }
+
+    /// Sets the sat-nav [`Coordinates`]
+    pub fn satnav_coordinates(mut self, satnav_coordinates: Coordinates) -> Self {
+        self.ring.satnav_coordinates = Some(satnav_coordinates);
+        self
+    }
+
+    /// Sets the year of the last overhaul
+    pub fn overhaul_year(mut self, overhaul_year: u16) -> Self {
+        self.ring.overhaul_year = Some(overhaul_year);
+        self
+    }
+
+    /// Sets the contractor of the last overhaul
+    pub fn contractor(mut self, contractor: Contractor) -> Self {
+        self.ring.contractor = Some(contractor);
+        self
+    }
+
+    /// Sets the year the bells were last tuned
+    pub fn tune_year(mut self, tune_year: u16) -> Self {
+        self.ring.tune_year = Some(tune_year);
+        self
+    }
+
+    /// Sets the building ID
+    pub fn building_id(mut self, building_id: impl Into<SmolStr>) -> Self {
+        self.ring.building_id = Some(building_id.into());
+        self
+    }
+
+    /// Sets the listed building grade
+    pub fn building_grade(mut self, building_grade: impl Into<SmolStr>) -> Self {
+        self.ring.building_grade = Some(building_grade.into());
+        self
+    }
+
+    /// Sets the [`ChurchCareId`]
+    pub fn church_care(mut self, church_care: ChurchCareId) -> Self {
+        self.ring.church_care = Some(church_care);
+        self
+    }
+
+    /// Sets the [`Dedication`] of the church (defaults to [`Dedication::Unknown`])
+    pub fn dedication(mut self, dedication: Dedication) -> Self {
+        self.ring.dedication = dedication;
+        self
+    }
+
+    /// Sets the alternative name of the church
+    pub fn alt_name(mut self, alt_name: impl Into<Box<str>>) -> Self {
+        self.ring.alt_name = Some(alt_name.into());
+        self
+    }
+
+    /// Sets the [`Diocese`]
+    pub fn diocese(mut self, diocese: Diocese) -> Self {
+        self.ring.diocese = Some(diocese);
+        self
+    }
+
+    /// Checks that the `Ring` is valid and returns it
+    pub fn build(self) -> Result<Ring, BuildRingError> {
+        let ring = self.ring;
+        if ring.bells == 0 {
+            return Err(BuildRingError::NoBells);
+        }
+        if ring.place.trim().is_empty() {
+            return Err(BuildRingError::EmptyPlace);
+        }
+        if let Some(weight) = ring.weight {
+            if !(weight.lbs() > 0.0 && weight.lbs().is_finite()) {
+                return Err(BuildRingError::InvalidWeight(weight));
+            }
+        }
+        if let Some(freq) = ring.freq {
+            if !(freq.hz() > 0.0 && freq.hz().is_finite()) {
+                return Err(BuildRingError::InvalidFrequency(freq));
+            }
+        }
+        Ok(ring)
+    }
+}
+
+/// The error generated when [`RingBuilder::build`] is given an invalid [`Ring`].
+#[derive(Debug, Clone)]
+pub enum BuildRingError {
+    /// The `Ring` has no bells
+    NoBells,
+    /// The `Ring`'s place name is empty
+    EmptyPlace,
+    /// The `Ring`'s [`Weight`] isn't a positive number of pounds
+    InvalidWeight(Weight),
+    /// The `Ring`'s [`Frequency`] isn't a positive number of Hz
+    InvalidFrequency(Frequency),
+}
+
+impl Display for BuildRingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildRingError::NoBells => write!(f, "Ring has no bells"),
+            BuildRingError::EmptyPlace => write!(f, "Ring has an empty place name"),
+            BuildRingError::InvalidWeight(w) => write!(f, "{} lbs is not a valid weight", w.lbs()),
+            BuildRingError::InvalidFrequency(freq) => {
+                write!(f, "{} is not a valid frequency", freq)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildRingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{BuildRingError, RingBuilder};
+    use crate::{
+        AccessStatus, Dedication, Doves, Frequency, Note, Query, RingType, TowerId, Weight,
+    };
+
+    fn builder(id: u32, bells: u8) -> RingBuilder {
+        RingBuilder::new(TowerId::new(id), format!("Place {}", id), bells)
+    }
+
+    #[test]
+    fn defaults() {
+        let ring = builder(1, 6).build().unwrap();
+        assert_eq!(ring.id, TowerId::new(1));
+        assert_eq!(ring.place, "Place 1");
+        assert_eq!(ring.bells, 6);
+        assert_eq!(ring.ring_type, RingType::FullCircle);
+        assert_eq!(ring.access, AccessStatus::Ringable);
+        assert!(!ring.ground_floor() && !ring.toilet() && !ring.simulator());
+        assert_eq!(ring.dedication, Dedication::Unknown);
+        assert_eq!(ring.weight, None);
+        assert_eq!(ring.coordinates, None);
+        assert_eq!(ring.towerbase_id, 0);
+    }
+
+    #[test]
+    fn setters() {
+        let ring = builder(2, 8)
+            .ground_floor(true)
+            .toilet(true)
+            .weight(Weight::from_cwt_qr_lb(12, 3, 4))
+            .note("F#".parse().unwrap())
+            .practice("Thu 19:30")
+            .county("Devon")
+            .towerbase_id(1234)
+            .overhaul_year(1998)
+            .build()
+            .unwrap();
+        assert!(ring.ground_floor() && ring.toilet() && !ring.simulator());
+        assert_eq!(ring.weight.map(Weight::lbs), Some(1432.0));
+        assert_eq!(ring.note, Some("Gb".parse::<Note>().unwrap()));
+        assert_eq!(ring.practice.as_deref(), Some("Thu 19:30"));
+        assert_eq!(ring.county.as_deref(), Some("Devon"));
+        assert_eq!(ring.towerbase_id, 1234);
+        assert_eq!(ring.overhaul_year, Some(1998));
+        // Setting a flag to `false` clears it
+        assert!(!builder(3, 6)
+            .toilet(true)
+            .toilet(false)
+            .build()
+            .unwrap()
+            .toilet());
+    }
+
+    #[test]
+    fn invalid_rings() {
+        assert!(matches!(
+            builder(1, 0).build(),
+            Err(BuildRingError::NoBells)
+        ));
+        assert!(matches!(
+            RingBuilder::new(TowerId::new(1), "  ", 6).build(),
+            Err(BuildRingError::EmptyPlace)
+        ));
+        for lbs in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let result = builder(1, 6).weight(Weight::from_lbs(lbs)).build();
+            assert!(matches!(result, Err(BuildRingError::InvalidWeight(_))));
+        }
+        for hz in [0.0, -440.0, f64::NAN] {
+            let result = builder(1, 6).freq(Frequency::from_hz(hz)).build();
+            assert!(matches!(result, Err(BuildRingError::InvalidFrequency(_))));
+        }
+    }
+
+    #[test]
+    fn fixtures_can_be_queried() {
+        let rings = vec![
+            builder(1, 6).toilet(true).build().unwrap(),
+            builder(2, 8).ground_floor(true).build().unwrap(),
+            builder(3, 12).toilet(true).build().unwrap(),
+        ];
+        let doves = Doves::from(rings);
+        assert_eq!(doves.get(TowerId::new(2)).map(|r| r.bells), Some(8));
+        let ids = |query: Query| -> Vec<u32> { query.iter().map(|r| r.id.get()).collect() };
+        assert_eq!(ids(doves.query().toilet()), [1, 3]);
+        assert_eq!(ids(doves.query().min_bells(8)), [2, 3]);
+        assert_eq!(
+            ids(doves.query().ground_floor().toilet()),
+            Vec::<u32>::new()
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! A compact JSON encoding of Dove's Guide, for shipping the whole guide to web browsers.
+
+use std::fmt::Write;
+
+use crate::{Column, Doves, Ring, RingFilter};
+
+/// The version of the bundle format, which changes whenever the schema does
+const BUNDLE_VERSION: usize = 1;
+/// The number of decimal places of the coordinates in Dove's Guide (about 1m of precision)
+pub(crate) const DOVE_COORDINATE_DECIMALS: usize = 5;
+
+/// Options for rendering [`Ring`]s as a minified JSON bundle, created with
+/// [`Doves::json_bundle`].
+///
+/// The bundle is a single JSON object:
+///
+/// ```text
+/// {"v":1,"schema":{"id":"TowerID","p":"Place",...},"rings":[{"id":12574,"p":"Ab Kettleby",...},...]}
+/// ```
+///
+/// - `v` is the version of the bundle format.
+/// - `schema` maps the short key of every included field to its [`Column`] header in Dove's CSV
+///   file (see [`Column::short_key`]).
+/// - `rings` has one object per `Ring`, in the order they appear in Dove's Guide.  Empty fields
+///   are left out entirely.  Numeric columns (e.g. `Bells`, `Wt` or `Lat`) are JSON numbers, flag
+///   columns (e.g. `GF` or `UR`) are `1` if set, and everything else is a string formatted as in
+///   Dove's CSV file.
+#[derive(Debug, Clone)]
+pub struct JsonBundle<'d> {
+    doves: &'d Doves,
+    fields: Vec<Column>,
+    coordinate_decimals: usize,
+}
+
+/// How a [`Column`]'s values are encoded in a [`JsonBundle`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum Encoding {
+    Number,
+    Coordinate,
+    Flag,
+    Text,
+}
+
+impl Column {
+    /// The short key used for this `Column` in a [`JsonBundle`] (e.g. `p` for `Place`)
+    pub fn short_key(self) -> &'static str {
+        match self {
+            Column::TowerId => "id",
+            Column::RingType => "rt",
+            Column::Place => "p",
+            Column::Place2 => "p2",
+            Column::PlaceCountyList => "pcl",
+            Column::Dedication => "d",
+            Column::AltName => "an",
+            Column::County => "c",
+            Column::Country => "cy",
+            Column::Iso3166Code => "iso",
+            Column::Diocese => "dio",
+            Column::Lat => "la",
+            Column::Long => "lo",
+            Column::Bells => "b",
+            Column::Unringable => "ur",
+            Column::Semitones => "st",
+            Column::Weight => "w",
+            Column::Approximate => "ap",
+            Column::Note => "n",
+            Column::Frequency => "hz",
+            Column::Details => "dt",
+            Column::GroundFloor => "gf",
+            Column::Toilet => "t",
+            Column::Simulator => "s",
+            Column::ExtraInfo => "x",
+            Column::WebPage => "u",
+            Column::Affiliations => "af",
+            Column::OsGridRef => "ng",
+            Column::Postcode => "pc",
+            Column::Practice => "pr",
+            Column::OverhaulYear => "oy",
+            Column::Contractor => "co",
+            Column::TuneYear => "ty",
+            Column::BuildingGrade => "lg",
+            Column::BuildingId => "bid",
+            Column::ChurchCare => "cc",
+            Column::TowerBase => "tb",
+            Column::DoveId => "did",
+            Column::SatnavLat => "sla",
+            Column::SatnavLong => "slo",
+        }
+    }
+
+    pub(crate) fn encoding(self) -> Encoding {
+        match self {
+            Column::TowerId
+            | Column::Bells
+            | Column::Weight
+            | Column::Frequency
+            | Column::OverhaulYear
+            | Column::TuneYear
+            | Column::ChurchCare
+            | Column::TowerBase => Encoding::Number,
+            Column::Lat | Column::Long | Column::SatnavLat | Column::SatnavLong => {
+                Encoding::Coordinate
+            }
+            Column::Unringable
+            | Column::Approximate
+            | Column::GroundFloor
+            | Column::Toilet
+            | Column::Simulator => Encoding::Flag,
+            _ => Encoding::Text,
+        }
+    }
+}
+
+impl Doves {
+    /// Starts a [`JsonBundle`] containing every [`Column`], with coordinates to Dove's full
+    /// precision
+    pub fn json_bundle(&self) -> JsonBundle<'_> {
+        JsonBundle {
+            doves: self,
+            fields: Column::all().collect(),
+            coordinate_decimals: DOVE_COORDINATE_DECIMALS,
+        }
+    }
+}
+
+impl JsonBundle<'_> {
+    /// Only include the given [`Column`]s.  Leaving out the columns which an application doesn't
+    /// use (e.g. `DoveID` or `BldgID`) is by far the best way to shrink the bundle.
+    pub fn fields(mut self, fields: impl IntoIterator<Item = Column>) -> Self {
+        self.fields = fields.into_iter().collect();
+        self
+    }
+
+    /// Round coordinates to the given number of decimal places.  Dove's gives 5 decimal places
+    /// (about 1m), but 3 (about 100m) is plenty for plotting towers on a map.
+    pub fn coordinate_decimals(mut self, decimals: usize) -> Self {
+        self.coordinate_decimals = decimals.min(DOVE_COORDINATE_DECIMALS);
+        self
+    }
+
+    /// Renders every [`Ring`] which passes a [`RingFilter`] as a minified JSON bundle
+    pub fn render(&self, filter: impl RingFilter) -> String {
+        let schema = self
+            .fields
+            .iter()
+            .map(|c| {
+                format!(
+                    "{}:{}",
+                    json_string(c.short_key()),
+                    json_string(c.dove_header())
+                )
+            })
+            .collect::<Vec<_>>();
+        let rings = self
+            .doves
+            .filter(filter)
+            .map(|ring| self.ring_object(ring))
+            .collect::<Vec<_>>();
+        format!(
+            r#"{{"v":{},"schema":{{{}}},"rings":[{}]}}"#,
+            BUNDLE_VERSION,
+            schema.join(","),
+            rings.join(",")
+        )
+    }
+
+    /// The JSON object for one [`Ring`]
+    fn ring_object(&self, ring: &Ring) -> String {
+        let mut object = String::from("{");
+        for &column in &self.fields {
+            let encoded = match column.json_value(ring, self.coordinate_decimals) {
+                Some(encoded) => encoded,
+                None => continue,
+            };
+            if object.len() > 1 {
+                object.push(',');
+            }
+            write!(object, "{}:{}", json_string(column.short_key()), encoded).unwrap();
+        }
+        object.push('}');
+        object
+    }
+}
+
+impl Column {
+    /// The value of this `Column` for a [`Ring`], encoded as JSON as in a [`JsonBundle`], or
+    /// `None` if it's empty
+    pub(crate) fn json_value(self, ring: &Ring, coordinate_decimals: usize) -> Option<String> {
+        let value = self.value(ring);
+        if value.is_empty() {
+            return None;
+        }
+        Some(match self.encoding() {
+            // Re-print numbers so that e.g. `0017` becomes `17` and `783.0` becomes `783`
+            Encoding::Number => value.parse::<f64>().unwrap().to_string(),
+            Encoding::Coordinate => {
+                let rounded = format!(
+                    "{:.decimals$}",
+                    value.parse::<f64>().unwrap(),
+                    decimals = coordinate_decimals
+                );
+                rounded.parse::<f64>().unwrap().to_string()
+            }
+            Encoding::Flag => "1".to_owned(),
+            Encoding::Text => json_string(&value),
+        })
+    }
+}
+
+/// Encodes a string as a JSON string literal
+pub(crate) fn json_string(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len() + 2);
+    encoded.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => encoded.push_str("\\\""),
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            '\r' => encoded.push_str("\\r"),
+            '\t' => encoded.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(encoded, "\\u{:04x}", c as u32).unwrap(),
+            c => encoded.push(c),
+        }
+    }
+    encoded.push('"');
+    encoded
+}
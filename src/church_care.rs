@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{Country, Diocese, Ring};
+
+/// A reference to the church containing a [`Ring`] in the buildings database of its
+/// denomination.  Dove's stores these in a single column, but the IDs come from two different
+/// databases depending on the [`Diocese`] (or, for Welsh towers not in an Anglican diocese, the
+/// [`Country`]).
+///
+/// **CSV Header**: `ChurchCare`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum ChurchCareId {
+    /// A Church of England church code (e.g. `619058`), as used by ChurchCare and the Church
+    /// Heritage Record.  This also covers the Channel Islands, the Isle of Man and the few Welsh
+    /// parishes in English dioceses.
+    ChurchOfEngland(u32),
+    /// A building ID from the Church in Wales
+    ChurchInWales(u32),
+}
+
+impl ChurchCareId {
+    /// Interprets the `ChurchCare` value of a `Ring` in a given [`Diocese`] and [`Country`].  Towers
+    /// which aren't in a Church of England diocese (e.g. those owned by the Friends of Friendless
+    /// Churches) are classified by their country instead.
+    pub(crate) fn from_dove(id: u32, diocese: Option<&Diocese>, country: Option<&Country>) -> Self {
+        let is_church_in_wales = match diocese {
+            Some(d) if d.is_church_in_wales() => true,
+            Some(d) if d.is_church_of_england() => false,
+            _ => country == Some(&Country::Wales),
+        };
+        if is_church_in_wales {
+            ChurchCareId::ChurchInWales(id)
+        } else {
+            ChurchCareId::ChurchOfEngland(id)
+        }
+    }
+
+    /// The numeric ID, as written in Dove's Guide
+    pub fn get(self) -> u32 {
+        match self {
+            ChurchCareId::ChurchOfEngland(id) | ChurchCareId::ChurchInWales(id) => id,
+        }
+    }
+}
+
+impl Display for ChurchCareId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.get())
+    }
+}
+
+impl Ring {
+    /// A link to the Church of England's Church Heritage Record for the building containing this
+    /// `Ring`.  This is `None` if Dove's has no [`ChurchCareId`] for it, or if it's a Church in
+    /// Wales building (which aren't in the Church Heritage Record).
+    pub fn church_heritage_url(&self) -> Option<String> {
+        match self.church_care? {
+            ChurchCareId::ChurchOfEngland(code) => Some(format!(
+                "https://facultyonline.churchofengland.org/CHR/{}",
+                code
+            )),
+            ChurchCareId::ChurchInWales(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChurchCareId;
+    use crate::{Country, Diocese};
+
+    fn from_dove(id: u32, diocese: &str, country: Country) -> ChurchCareId {
+        let diocese = diocese.parse::<Diocese>().unwrap();
+        ChurchCareId::from_dove(id, Some(&diocese), Some(&country))
+    }
+
+    #[test]
+    fn classified_by_diocese() {
+        assert_eq!(
+            from_dove(619058, "Leicester", Country::England),
+            ChurchCareId::ChurchOfEngland(619058)
+        );
+        assert_eq!(
+            from_dove(1234, "Llandaff", Country::Wales),
+            ChurchCareId::ChurchInWales(1234)
+        );
+        // Welsh parishes in English dioceses use Church of England codes
+        assert_eq!(
+            from_dove(5678, "Hereford", Country::Wales),
+            ChurchCareId::ChurchOfEngland(5678)
+        );
+    }
+
+    #[test]
+    fn welsh_towers_outside_anglican_dioceses() {
+        // Towers 14439, 11890 and 13377 in Dove's Guide
+        assert_eq!(
+            "Monmouth;FriendsOfFC".parse::<Diocese>().unwrap(),
+            Diocese::Monmouth
+        );
+        assert_eq!(
+            from_dove(7188, "Monmouth;FriendsOfFC", Country::Wales),
+            ChurchCareId::ChurchInWales(7188)
+        );
+        assert_eq!(
+            from_dove(5047, "FriendsOfFC", Country::Wales),
+            ChurchCareId::ChurchInWales(5047)
+        );
+        assert_eq!(
+            from_dove(4948, "PrivOwnership", Country::Wales),
+            ChurchCareId::ChurchInWales(4948)
+        );
+        assert_eq!(
+            ChurchCareId::from_dove(4948, None, Some(&Country::Wales)),
+            ChurchCareId::ChurchInWales(4948)
+        );
+        assert_eq!(
+            from_dove(1000, "ChConsvnTrust", Country::England),
+            ChurchCareId::ChurchOfEngland(1000)
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{Distance, Doves, Ring};
+
+impl Doves {
+    /// Groups the [`Ring`]s into clusters of nearby towers.  Two `Ring`s are in the same cluster
+    /// if there is a chain of `Ring`s between them where each step is at most `max_distance` (i.e.
+    /// DBSCAN with a minimum cluster size of one), so a `Ring` with no neighbours forms its own
+    /// cluster.  Clusters are returned largest first, and the `Ring`s in each cluster are in the
+    /// order they appear in Dove's Guide.  `Ring`s without coordinates aren't in any cluster.
+    pub fn cluster(&self, max_distance: Distance) -> Vec<Vec<&Ring>> {
+        const UNASSIGNED: usize = usize::MAX;
+
+        let index = self.spatial_index();
+        let mut cluster_of = vec![UNASSIGNED; self.towers.len()];
+        let mut clusters = Vec::<Vec<usize>>::new();
+        for (seed, ring) in self.iter().enumerate() {
+            if cluster_of[seed] != UNASSIGNED || ring.best_coordinates().is_none() {
+                continue;
+            }
+            // Flood-fill outwards from `seed`, adding every `Ring` within reach
+            let cluster_idx = clusters.len();
+            cluster_of[seed] = cluster_idx;
+            let mut members = vec![seed];
+            let mut next = 0;
+            while let Some(&idx) = members.get(next) {
+                next += 1;
+                let coords = self.towers[idx].best_coordinates().unwrap().0;
+                for neighbour in index.by_distance(coords) {
+                    let neighbour_coords = self.towers[neighbour].best_coordinates().unwrap().0;
+                    if coords.distance_to(neighbour_coords) > max_distance {
+                        break;
+                    }
+                    if cluster_of[neighbour] == UNASSIGNED {
+                        cluster_of[neighbour] = cluster_idx;
+                        members.push(neighbour);
+                    }
+                }
+            }
+            members.sort_unstable();
+            clusters.push(members);
+        }
+
+        // `sort_by_key` is stable, so equally-sized clusters stay in Dove's order
+        clusters.sort_by_key(|members| std::cmp::Reverse(members.len()));
+        clusters
+            .into_iter()
+            .map(|members| members.into_iter().map(|idx| &self.towers[idx]).collect())
+            .collect()
+    }
+}
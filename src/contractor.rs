@@ -0,0 +1,138 @@
+use std::{
+    convert::Infallible,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::Symbol;
+
+/// The bell founder or bellhanger who carried out the last overhaul of a [`Ring`](crate::Ring).
+///
+/// Dove's doesn't always spell firms consistently (`John Taylor & Co`, `Taylors`, `Taylor's`,
+/// etc.), so known firms are normalized by ignoring case, punctuation and suffixes like `Ltd` or
+/// `& Sons`.  Anything else is kept verbatim in [`Contractor::Other`].
+///
+/// **CSV Header**: `Contractor`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Contractor {
+    /// **Dove's value:** `John Taylor & Co`
+    Taylor,
+    /// **Dove's value:** `Whitechapel Bell Foundry Ltd`
+    Whitechapel,
+    /// The name of the Whitechapel foundry until 1968.
+    ///
+    /// **Dove's value:** `Mears & Stainbank`
+    MearsAndStainbank,
+    /// **Dove's value:** `Whites of Appleton`
+    WhitesOfAppleton,
+    /// **Dove's value:** `Gillett & Johnston`
+    GillettAndJohnston,
+    /// **Dove's value:** `Andrew Nicholson`
+    Nicholson,
+    /// **Dove's value:** `Eayre & Smith`
+    EayreAndSmith,
+    /// **Dove's value:** `Hayward Mills Associates`
+    HaywardMills,
+    /// **Dove's value:** `Robert Parker`
+    RobertParker,
+    /// **Dove's value:** `Matthew Higby`
+    MatthewHigby,
+    /// Work done by the local band, guild or association rather than a firm.
+    ///
+    /// **Dove's value:** `Local labour/guild/assn`
+    LocalLabour,
+    /// Any other contractor, stored exactly as it appears in Dove's.
+    Other(Symbol),
+}
+
+/// Each known `Contractor`, along with the name used by Dove's Guide
+static NAMES: [(Contractor, &str); 11] = [
+    (Contractor::Taylor, "John Taylor & Co"),
+    (Contractor::Whitechapel, "Whitechapel Bell Foundry Ltd"),
+    (Contractor::MearsAndStainbank, "Mears & Stainbank"),
+    (Contractor::WhitesOfAppleton, "Whites of Appleton"),
+    (Contractor::GillettAndJohnston, "Gillett & Johnston"),
+    (Contractor::Nicholson, "Andrew Nicholson"),
+    (Contractor::EayreAndSmith, "Eayre & Smith"),
+    (Contractor::HaywardMills, "Hayward Mills Associates"),
+    (Contractor::RobertParker, "Robert Parker"),
+    (Contractor::MatthewHigby, "Matthew Higby"),
+    (Contractor::LocalLabour, "Local labour/guild/assn"),
+];
+
+/// Alternative names for known `Contractor`s, in the form produced by [`simplify`]
+static ALIASES: [(Contractor, &str); 11] = [
+    (Contractor::Taylor, "taylor"),
+    (Contractor::Taylor, "taylors"),
+    (Contractor::Taylor, "john taylor"),
+    (Contractor::Taylor, "taylors loughborough"),
+    (Contractor::Whitechapel, "whitechapel"),
+    (Contractor::WhitesOfAppleton, "whites"),
+    (Contractor::Nicholson, "nicholson"),
+    (Contractor::Nicholson, "nicholson engineering"),
+    (Contractor::Nicholson, "nicholson bellhangers"),
+    (Contractor::MatthewHigby, "higby"),
+    (Contractor::LocalLabour, "local labour"),
+];
+
+/// Words which don't help to tell firms apart
+const NOISE_WORDS: [&str; 7] = ["ltd", "co", "and", "sons", "of", "the", "associates"];
+
+/// Reduces a contractor's name to lower-case words, dropping punctuation and [`NOISE_WORDS`]
+fn simplify(name: &str) -> String {
+    name.to_lowercase()
+        .replace('\'', "")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty() && !NOISE_WORDS.contains(w))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl Contractor {
+    /// The name used for this `Contractor` in Dove's Guide
+    pub fn dove_name(&self) -> &str {
+        match self {
+            Contractor::Other(s) => s,
+            contractor => {
+                NAMES
+                    .iter()
+                    .find(|(c, _)| c == contractor)
+                    .expect("Every non-`Other` contractor should be in `NAMES`")
+                    .1
+            }
+        }
+    }
+
+    /// `true` if this `Contractor` is a bell foundry (rather than just a bellhanger)
+    pub fn is_foundry(&self) -> bool {
+        matches!(
+            self,
+            Contractor::Taylor
+                | Contractor::Whitechapel
+                | Contractor::MearsAndStainbank
+                | Contractor::GillettAndJohnston
+        )
+    }
+}
+
+impl Display for Contractor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.dove_name())
+    }
+}
+
+impl FromStr for Contractor {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let simple = simplify(s);
+        let known = NAMES
+            .iter()
+            .find(|(_, name)| simplify(name) == simple)
+            .or_else(|| ALIASES.iter().find(|(_, alias)| *alias == simple));
+        Ok(match known {
+            Some((contractor, _)) => contractor.clone(),
+            None => Contractor::Other(Symbol::new(s.trim())),
+        })
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt::{Display, Formatter};
+
+/// A point on the Earth's surface, as a latitude and longitude in decimal degrees.  Latitudes are
+/// always in `-90.0..=90.0` and longitudes in `-180.0..=180.0`.
+///
+/// `Coordinates` are [`Display`]ed in decimal degrees (e.g. `51.75283, -1.25490`); use
+/// [`Coordinates::dms`] to display them in degrees, minutes and seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinates {
+    lat: f64,
+    long: f64,
+}
+
+impl Coordinates {
+    /// Creates a new `Coordinates`, returning an error if either value is out of range (or not a
+    /// number).
+    pub fn new(lat: f64, long: f64) -> Result<Self, InvalidCoordinates> {
+        if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&long) {
+            Ok(Self { lat, long })
+        } else {
+            Err(InvalidCoordinates { lat, long })
+        }
+    }
+
+    /// The latitude, in decimal degrees north of the equator
+    pub fn lat(self) -> f64 {
+        self.lat
+    }
+
+    /// The longitude, in decimal degrees east of the prime meridian
+    pub fn long(self) -> f64 {
+        self.long
+    }
+
+    /// Returns a wrapper which [`Display`]s these `Coordinates` in degrees, minutes and seconds
+    /// (e.g. `51°45′10.2″N 1°15′17.6″W`).
+    pub fn dms(self) -> DmsCoordinates {
+        DmsCoordinates(self)
+    }
+}
+
+impl Display for Coordinates {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.5}, {:.5}", self.lat, self.long)
+    }
+}
+
+/// Which of a [`Ring`](crate::Ring)'s [`Coordinates`] were used by
+/// [`Ring::best_coordinates`](crate::Ring::best_coordinates).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CoordinateSource {
+    /// The sat-nav coordinates (i.e. [`Ring::satnav_coordinates`](crate::Ring::satnav_coordinates))
+    SatNav,
+    /// The coordinates of the tower (i.e. [`Ring::coordinates`](crate::Ring::coordinates))
+    Tower,
+}
+
+/// A wrapper which [`Display`]s [`Coordinates`] in degrees, minutes and seconds.  Created by
+/// [`Coordinates::dms`].
+#[derive(Debug, Clone, Copy)]
+pub struct DmsCoordinates(Coordinates);
+
+impl Display for DmsCoordinates {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Coordinates { lat, long } = self.0;
+        write_dms(f, lat, if lat < 0.0 { 'S' } else { 'N' })?;
+        write!(f, " ")?;
+        write_dms(f, long, if long < 0.0 { 'W' } else { 'E' })
+    }
+}
+
+fn write_dms(f: &mut Formatter<'_>, degrees: f64, hemisphere: char) -> std::fmt::Result {
+    // Round to the nearest tenth of a second before splitting, so that we never display `60.0″`
+    let tenths_of_secs = (degrees.abs() * 36_000.0).round() as u64;
+    let secs = (tenths_of_secs % 600) as f64 / 10.0;
+    let mins = (tenths_of_secs / 600) % 60;
+    let degs = tenths_of_secs / 36_000;
+    write!(f, "{}°{}′{:.1}″{}", degs, mins, secs, hemisphere)
+}
+
+/// The error generated when creating [`Coordinates`] which are out of range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidCoordinates {
+    pub lat: f64,
+    pub long: f64,
+}
+
+impl Display for InvalidCoordinates {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}) are not valid coordinates", self.lat, self.long)
+    }
+}
+
+impl std::error::Error for InvalidCoordinates {}
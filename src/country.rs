@@ -0,0 +1,285 @@
+use std::{
+    convert::Infallible,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::Symbol;
+
+/// A country (or group of countries) as named by Dove's Guide.  These don't always line up with
+/// sovereign states (e.g. [`Country::IslandOfIreland`] covers both the UK and the Republic of
+/// Ireland), so use the [`CountryCode`] if you need to know which state a [`Ring`](crate::Ring) is
+/// in.
+///
+/// **CSV Header**: `Country`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Country {
+    /* BRITISH ISLES */
+    England,
+    Wales,
+    Scotland,
+    IslandOfIreland,
+    ChannelIslands,
+    IsleOfMan,
+
+    /* OVERSEAS */
+    Australia,
+    Belgium,
+    Canada,
+    Caribbean,
+    France,
+    India,
+    Kenya,
+    Netherlands,
+    NewZealand,
+    Pakistan,
+    Singapore,
+    SouthAfrica,
+    Spain,
+    UnitedStates,
+    Zimbabwe,
+
+    /// Any value not covered by the other variants, stored exactly as it appears in Dove's.
+    Other(Symbol),
+}
+
+/// The `Country`s which have their own variants, along with their values in Dove's Guide.
+static NAMES: [(Country, &str); 21] = [
+    (Country::England, "England"),
+    (Country::Wales, "Wales"),
+    (Country::Scotland, "Scotland"),
+    (Country::IslandOfIreland, "Island of Ireland"),
+    (Country::ChannelIslands, "Channel Islands"),
+    (Country::IsleOfMan, "Isle of Man"),
+    (Country::Australia, "Australia"),
+    (Country::Belgium, "Belgium"),
+    (Country::Canada, "Canada"),
+    (Country::Caribbean, "Caribbean"),
+    (Country::France, "France"),
+    (Country::India, "India"),
+    (Country::Kenya, "Kenya"),
+    (Country::Netherlands, "Netherlands"),
+    (Country::NewZealand, "New Zealand"),
+    (Country::Pakistan, "Pakistan"),
+    (Country::Singapore, "Singapore"),
+    (Country::SouthAfrica, "South Africa"),
+    (Country::Spain, "Spain"),
+    (Country::UnitedStates, "United States of America"),
+    (Country::Zimbabwe, "Zimbabwe"),
+];
+
+impl Country {
+    /// The string used for this `Country` in Dove's Guide
+    pub fn dove_name(&self) -> &str {
+        match self {
+            Country::Other(s) => s,
+            c => {
+                NAMES
+                    .iter()
+                    .find(|(c2, _)| c2 == c)
+                    .expect("Every non-`Other` country should be in `NAMES`")
+                    .1
+            }
+        }
+    }
+
+    /// Finds the `Country` with a given name in Dove's Guide, ignoring case.  Returns `None` for
+    /// unknown names, rather than creating a [`Country::Other`].
+    pub(crate) fn from_dove_name_ignore_case(name: &str) -> Option<Self> {
+        NAMES
+            .iter()
+            .find(|(_, n)| n.eq_ignore_ascii_case(name))
+            .map(|(c, _)| c.clone())
+    }
+
+    /// The position of this `Country` in the printed guide, which lists the British Isles first,
+    /// followed by the overseas countries in alphabetical order (with any unknown countries last)
+    pub(crate) fn guide_order(&self) -> usize {
+        NAMES
+            .iter()
+            .position(|(c, _)| c == self)
+            .unwrap_or(NAMES.len())
+    }
+
+    /// `true` if this `Country` is entirely contained within the United Kingdom.  Note that this
+    /// is `false` for [`Country::IslandOfIreland`], since Dove's groups Northern Ireland with the
+    /// Republic of Ireland; use [`CountryCode::is_uk`] to check that.
+    pub fn is_uk(&self) -> bool {
+        matches!(self, Country::England | Country::Wales | Country::Scotland)
+    }
+
+    /// The ISO 3166-2 subdivision code for this `Country`, given the [`CountryCode`] of the state
+    /// containing it.  This is only `Some` for the four countries of the UK.
+    pub fn subdivision(&self, code: CountryCode) -> Option<SubdivisionCode> {
+        if code != CountryCode::GB {
+            return None;
+        }
+        let subdivision = match self {
+            Country::England => "ENG",
+            Country::Wales => "WLS",
+            Country::Scotland => "SCT",
+            Country::IslandOfIreland => "NIR",
+            _ => return None,
+        };
+        Some(SubdivisionCode::new(code, subdivision).unwrap())
+    }
+}
+
+impl Display for Country {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.dove_name())
+    }
+}
+
+impl FromStr for Country {
+    type Err = Infallible;
+
+    /// Parses a `Country` from its value in Dove's Guide.  Unrecognised strings become
+    /// [`Country::Other`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match NAMES.iter().find(|(_, name)| s == *name) {
+            Some((c, _)) => c.clone(),
+            None => Country::Other(Symbol::new(s)),
+        })
+    }
+}
+
+//////////////////
+// COUNTRY CODE //
+//////////////////
+
+/// An ISO 3166-1 alpha-2 country code (e.g. `GB` or `AU`).
+///
+/// **CSV Header**: `ISO3166code`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct CountryCode([u8; 2]);
+
+impl CountryCode {
+    pub const AU: Self = Self(*b"AU");
+    pub const BE: Self = Self(*b"BE");
+    pub const CA: Self = Self(*b"CA");
+    pub const ES: Self = Self(*b"ES");
+    pub const FR: Self = Self(*b"FR");
+    pub const GB: Self = Self(*b"GB");
+    pub const GD: Self = Self(*b"GD");
+    pub const GG: Self = Self(*b"GG");
+    pub const IE: Self = Self(*b"IE");
+    pub const IM: Self = Self(*b"IM");
+    pub const IN: Self = Self(*b"IN");
+    pub const JE: Self = Self(*b"JE");
+    pub const KE: Self = Self(*b"KE");
+    pub const NL: Self = Self(*b"NL");
+    pub const NZ: Self = Self(*b"NZ");
+    pub const PK: Self = Self(*b"PK");
+    pub const SG: Self = Self(*b"SG");
+    pub const US: Self = Self(*b"US");
+    pub const VC: Self = Self(*b"VC");
+    pub const ZA: Self = Self(*b"ZA");
+    pub const ZW: Self = Self(*b"ZW");
+
+    /// Creates a `CountryCode` from a string of two ASCII letters, or `None` if the string isn't
+    /// in that form.  The letters are converted to upper case.
+    pub fn new(s: &str) -> Option<Self> {
+        match s.as_bytes() {
+            &[a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => {
+                Some(Self([a.to_ascii_uppercase(), b.to_ascii_uppercase()]))
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets this `CountryCode` as a string
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("Country codes are always ASCII")
+    }
+
+    /// `true` if this code refers to the United Kingdom (i.e. it's `GB`).  Note that this is
+    /// `false` for the Crown Dependencies ([`CountryCode::GG`], [`CountryCode::JE`] and
+    /// [`CountryCode::IM`]).
+    pub fn is_uk(self) -> bool {
+        self == Self::GB
+    }
+}
+
+impl Display for CountryCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for CountryCode {
+    type Err = ParseCountryCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s).ok_or_else(|| ParseCountryCodeError(s.to_owned()))
+    }
+}
+
+/// An ISO 3166-2 subdivision code (e.g. `GB-ENG`), made of a [`CountryCode`] and up to three
+/// letters or digits identifying the subdivision.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct SubdivisionCode {
+    country: CountryCode,
+    subdivision: [u8; 3],
+    len: u8,
+}
+
+impl SubdivisionCode {
+    /// Creates a new `SubdivisionCode`, or `None` if `subdivision` isn't 1-3 ASCII letters or
+    /// digits.  Letters are converted to upper case.
+    pub fn new(country: CountryCode, subdivision: &str) -> Option<Self> {
+        let bytes = subdivision.as_bytes();
+        if bytes.is_empty() || bytes.len() > 3 || !bytes.iter().all(u8::is_ascii_alphanumeric) {
+            return None;
+        }
+        let mut buf = [0; 3];
+        for (b, s) in buf.iter_mut().zip(bytes) {
+            *b = s.to_ascii_uppercase();
+        }
+        Some(Self {
+            country,
+            subdivision: buf,
+            len: bytes.len() as u8,
+        })
+    }
+
+    /// The [`CountryCode`] of the state containing this subdivision
+    pub fn country(&self) -> CountryCode {
+        self.country
+    }
+
+    /// The part of this code after the `-` (e.g. `ENG` for `GB-ENG`)
+    pub fn subdivision(&self) -> &str {
+        std::str::from_utf8(&self.subdivision[..self.len as usize])
+            .expect("Subdivision codes are always ASCII")
+    }
+}
+
+impl Display for SubdivisionCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.country, self.subdivision())
+    }
+}
+
+impl FromStr for SubdivisionCode {
+    type Err = ParseCountryCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseCountryCodeError(s.to_owned());
+        let (country, subdivision) = s.split_once('-').ok_or_else(err)?;
+        let country = CountryCode::new(country).ok_or_else(err)?;
+        Self::new(country, subdivision).ok_or_else(err)
+    }
+}
+
+/// The error generated when parsing an invalid [`CountryCode`] or [`SubdivisionCode`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseCountryCodeError(String);
+
+impl Display for ParseCountryCodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a valid ISO 3166 code", self.0)
+    }
+}
+
+impl std::error::Error for ParseCountryCodeError {}
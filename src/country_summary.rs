@@ -0,0 +1,69 @@
+//! Summarising the [`Ring`]s in each [`Country`].
+
+use std::collections::BTreeMap;
+
+use crate::{Country, Doves, Ring, Weight};
+
+/// Totals for the [`Ring`]s in one [`Country`].  Created by [`Doves::by_country`].
+#[derive(Debug, Clone)]
+pub struct CountrySummary<'d> {
+    /// The `Country`, or `None` for `Ring`s with no country
+    pub country: Option<&'d Country>,
+    /// The number of `Ring`s
+    pub num_rings: usize,
+    /// The number of `Ring`s which can currently be rung (see [`Ring::is_ringable`])
+    pub num_ringable: usize,
+    /// The total number of bells in every `Ring`
+    pub total_bells: usize,
+    /// The total number of [ringable bells](Ring::ringable_bells) in every `Ring`
+    pub total_ringable_bells: usize,
+    /// The `Ring` with the heaviest tenor, if any `Ring`s have a known weight
+    pub heaviest: Option<&'d Ring>,
+}
+
+impl<'d> CountrySummary<'d> {
+    fn new(country: Option<&'d Country>) -> Self {
+        Self {
+            country,
+            num_rings: 0,
+            num_ringable: 0,
+            total_bells: 0,
+            total_ringable_bells: 0,
+            heaviest: None,
+        }
+    }
+
+    fn add(&mut self, ring: &'d Ring) {
+        self.num_rings += 1;
+        self.num_ringable += usize::from(ring.is_ringable());
+        self.total_bells += usize::from(ring.bells);
+        self.total_ringable_bells += usize::from(ring.ringable_bells());
+        if let Some(weight) = ring.weight {
+            let heaviest_lbs = self.heaviest.and_then(|r| r.weight).map(Weight::lbs);
+            if heaviest_lbs.is_none_or(|lbs| weight.lbs() > lbs) {
+                self.heaviest = Some(ring);
+            }
+        }
+    }
+}
+
+impl Doves {
+    /// Summarises the [`Ring`]s in each [`Country`], in the order used by the printed guide
+    /// (England, Wales, Scotland, Ireland, then the rest of the world).  `Ring`s with no country
+    /// are summarised last.
+    pub fn by_country(&self) -> Vec<CountrySummary<'_>> {
+        let mut summaries = BTreeMap::new();
+        for ring in self {
+            let country = ring.country.as_ref();
+            let key = (
+                country.map_or(usize::MAX, Country::guide_order),
+                country.map(Country::dove_name),
+            );
+            summaries
+                .entry(key)
+                .or_insert_with(|| CountrySummary::new(country))
+                .add(ring);
+        }
+        summaries.into_values().collect()
+    }
+}
@@ -0,0 +1,120 @@
+//! Grouping [`Ring`]s by county, in the same order as the county lists of the printed guide.
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
+
+use crate::{Country, Doves, NameStyle, Ring};
+
+/// Rings with the same place name in neighbouring counties are treated as parts of one place
+/// (e.g. Belfast, which spans Antrim and Down) if they're closer than this
+const SPANNING_PLACE_KM: f64 = 10.0;
+
+/// The [`Ring`]s in one county, as listed in the printed guide.  Created by
+/// [`Doves::by_county_list`].
+#[derive(Debug, Clone)]
+pub struct CountyGroup<'d> {
+    /// The [`Country`] containing this county, or `None` for `Ring`s with no country
+    pub country: Option<&'d Country>,
+    /// The name of the county, or `None` for `Ring`s which aren't listed under a county (e.g. those
+    /// in countries too small to have counties)
+    pub county: Option<&'d str>,
+    /// Every `Ring` in this county, ordered by their county list names (see
+    /// [`NameStyle::CountyList`])
+    pub rings: Vec<&'d Ring>,
+    /// `Ring`s in places which span this county and another, but which Dove's lists under the
+    /// other county.  The printed guide gives these as cross-references (e.g. `Belfast: see also
+    /// Down`).
+    pub see_also: Vec<&'d Ring>,
+}
+
+impl Doves {
+    /// Groups every [`Ring`] by county, in the order used by the county lists of the printed
+    /// guide: countries in Dove's order (England, Wales, Scotland, Ireland, then the rest of the
+    /// world), counties alphabetically within each country (with `Ring`s not in any county
+    /// last), and `Ring`s ordered by their county list names (see [`NameStyle::CountyList`]).
+    /// `Ring`s in places which span more than one county (e.g. Belfast) are listed under their
+    /// own county, and cross-referenced from the others (see [`CountyGroup::see_also`]).
+    pub fn by_county_list(&self) -> Vec<CountyGroup<'_>> {
+        // Keyed by the position of each group in the printed guide
+        let mut groups = BTreeMap::<_, (Option<&Country>, Vec<&Ring>)>::new();
+        for ring in self {
+            let country = ring.country.as_ref();
+            let key = (
+                country.map_or(usize::MAX, Country::guide_order),
+                country.map(Country::dove_name),
+                ring.county.is_none(),
+                ring.county.as_deref(),
+            );
+            groups
+                .entry(key)
+                .or_insert((country, Vec::new()))
+                .1
+                .push(ring);
+        }
+
+        let mut groups = groups
+            .into_iter()
+            .map(|((_, _, _, county), (country, mut rings))| {
+                rings.sort_by(|a, b| compare_county_list(a, b));
+                CountyGroup {
+                    country,
+                    county,
+                    rings,
+                    see_also: Vec::new(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Cross-reference places which span counties.  Only places whose names appear in more than
+        // one county of the same country need checking.
+        let mut by_place = HashMap::<(Option<&Country>, &str), Vec<(usize, &Ring)>>::new();
+        for (idx, group) in groups.iter().enumerate() {
+            if group.county.is_some() {
+                for ring in &group.rings {
+                    by_place
+                        .entry((group.country, &ring.place))
+                        .or_default()
+                        .push((idx, ring));
+                }
+            }
+        }
+        for rings in by_place.values() {
+            for &(idx, ring) in rings {
+                let is_spanned = rings
+                    .iter()
+                    .any(|&(other_idx, other)| other_idx != idx && is_same_place(ring, other));
+                if !is_spanned {
+                    continue;
+                }
+                for &(other_idx, _) in rings {
+                    let see_also = &mut groups[other_idx].see_also;
+                    if other_idx != idx && !see_also.iter().any(|r| std::ptr::eq(*r, ring)) {
+                        see_also.push(ring);
+                    }
+                }
+            }
+        }
+        for group in &mut groups {
+            group.see_also.sort_by(|a, b| compare_county_list(a, b));
+        }
+        groups
+    }
+}
+
+/// The order of [`Ring`]s within a county list
+fn compare_county_list(a: &Ring, b: &Ring) -> Ordering {
+    a.display_name(NameStyle::CountyList)
+        .cmp(&b.display_name(NameStyle::CountyList))
+        .then_with(|| a.place2.cmp(&b.place2))
+        .then_with(|| a.dedication.as_str().cmp(b.dedication.as_str()))
+        .then_with(|| a.key().cmp(&b.key()))
+}
+
+/// `true` if two [`Ring`]s with the same place name (but in different counties) are in the same
+/// place, rather than in two different places which happen to share a name
+fn is_same_place(a: &Ring, b: &Ring) -> bool {
+    a.distance_to_ring(b)
+        .is_some_and(|distance| distance.km() < SPANNING_PLACE_KM)
+}
@@ -0,0 +1,250 @@
+use std::{
+    convert::Infallible,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{Doves, Ring, SmolStr};
+
+/// The dedication of the church containing a [`Ring`](crate::Ring) (e.g. `S Mary V & S
+/// Nicholas`).
+///
+/// Dedications are parsed into a list of normalized saint or feast names (e.g. `["Mary",
+/// "Nicholas"]`), so that spelling and abbreviation differences (`S Mary V` vs `Blessed Virgin
+/// Mary`, `S Swithin` vs `S Swithun`, etc.) don't get in the way of searches.  The original string
+/// is always kept, and is what gets [`Display`]ed.
+///
+/// **CSV Header**: `Dedicn`
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub enum Dedication {
+    /// A dedication to one or more saints or feasts
+    Named { raw: SmolStr, names: Vec<SmolStr> },
+    /// Dove's doesn't know the dedication.
+    ///
+    /// **Dove's value:** `dedication unknown`
+    #[default]
+    Unknown,
+    /// A value which doesn't look like a dedication (e.g. `Town Hall` or `Mobile Ring`), stored
+    /// exactly as it appears in Dove's.
+    Raw(SmolStr),
+}
+
+/// The string used by Dove's Guide for unknown dedications
+const UNKNOWN: &str = "dedication unknown";
+
+/// Dedications (in lower case) which aren't to a saint, along with their normalized names
+static FEASTS: [(&str, &str); 34] = [
+    ("all saints", "All Saints"),
+    ("all hallows", "All Saints"),
+    ("all angels", "All Angels"),
+    ("all souls", "All Souls"),
+    ("holy trinity", "Holy Trinity"),
+    ("undivided trinity", "Holy Trinity"),
+    ("blessed trinity", "Holy Trinity"),
+    ("trinity", "Holy Trinity"),
+    ("holy cross", "Holy Cross"),
+    ("holy rood", "Holy Cross"),
+    ("blessed virgin mary", "Mary"),
+    ("bvm", "Mary"),
+    ("our lady", "Mary"),
+    ("our blessed lady", "Mary"),
+    ("christ", "Christ"),
+    ("christ church", "Christ"),
+    ("holy innocents", "Holy Innocents"),
+    ("the ascension", "Ascension"),
+    ("emmanuel", "Emmanuel"),
+    ("immanuel", "Emmanuel"),
+    ("the good shepherd", "Good Shepherd"),
+    ("sacred heart", "Sacred Heart"),
+    ("holy name", "Holy Name"),
+    ("immaculate conception", "Immaculate Conception"),
+    ("holy spirit", "Holy Spirit"),
+    ("holy ghost", "Holy Spirit"),
+    ("corpus christi", "Corpus Christi"),
+    ("holy nativity", "Nativity"),
+    ("the advent", "Advent"),
+    ("the assumption", "Assumption"),
+    ("assumption of bvm", "Assumption"),
+    ("assumption of our lady", "Assumption"),
+    ("nativity of bvm", "Nativity of Mary"),
+    ("annunciation bvm", "Annunciation"),
+];
+
+/// Abbreviated epithets used after saints' names, along with their expansions.  An empty
+/// expansion means that the epithet is dropped.
+static EPITHETS: [(&str, &str); 13] = [
+    ("V", ""),
+    ("BV", ""),
+    ("Bapt", "the Baptist"),
+    ("Ev", "the Evangelist"),
+    ("Div", "the Divine"),
+    ("Magd", "Magdalene"),
+    ("Gt", "the Great"),
+    ("Less", "the Less"),
+    ("Ap", "the Apostle"),
+    ("M", "the Martyr"),
+    ("K&M", "King and Martyr"),
+    ("Conf", "the Confessor"),
+    ("Archangel", "the Archangel"),
+];
+
+/// Alternative spellings of saints' names, along with the spelling we normalize them to
+static SPELLINGS: [(&str, &str); 9] = [
+    ("Nicolas", "Nicholas"),
+    ("Swithin", "Swithun"),
+    ("Laurence", "Lawrence"),
+    ("Katharine", "Catherine"),
+    ("Katherine", "Catherine"),
+    ("Catharine", "Catherine"),
+    ("Petrock", "Petroc"),
+    ("Editha", "Edith"),
+    ("Helena", "Helen"),
+];
+
+impl Dedication {
+    /// The dedication as written in Dove's Guide
+    pub fn as_str(&self) -> &str {
+        match self {
+            Dedication::Named { raw, .. } => raw,
+            Dedication::Unknown => UNKNOWN,
+            Dedication::Raw(raw) => raw,
+        }
+    }
+
+    /// The normalized names of the saints and feasts in this dedication (empty unless this is
+    /// [`Dedication::Named`])
+    pub fn names(&self) -> &[SmolStr] {
+        match self {
+            Dedication::Named { names, .. } => names,
+            Dedication::Unknown | Dedication::Raw(_) => &[],
+        }
+    }
+
+    /// `true` if this dedication includes `name`.  `name` is normalized in the same way as
+    /// dedications, so `"St Swithin"`, `"Saint Swithun"` and `"S Swithun"` are all equivalent.  A
+    /// name matches any dedication which starts with it, so `"John"` matches both `"John the
+    /// Baptist"` and `"John the Evangelist"`.
+    pub fn is_dedicated_to(&self, name: &str) -> bool {
+        let query = match Self::normalize_name(name) {
+            Some(query) => query,
+            None => return false,
+        };
+        self.names().iter().any(|n| {
+            n.eq_ignore_ascii_case(&query)
+                || (n.len() > query.len()
+                    && n.as_bytes()[query.len()] == b' '
+                    && n[..query.len()].eq_ignore_ascii_case(&query))
+        })
+    }
+
+    /// Normalizes the name of a single saint or feast (e.g. `"S Mary V"` becomes `"Mary"`, and
+    /// `"All Hallows"` becomes `"All Saints"`).  Saints can be written with an `S`, `St`, `St.`
+    /// or `Saint` prefix, or with no prefix at all, and capitalisation is ignored.  Returns `None`
+    /// if `name` is empty.
+    pub fn normalize_name(name: &str) -> Option<SmolStr> {
+        // Capitalise every word, since user input often won't be
+        let capitalised = name
+            .split_whitespace()
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(c) => c.to_uppercase().chain(chars).collect::<String>(),
+                    None => String::new(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        // Allow the prefix to be omitted
+        Self::normalize_part(&capitalised).or_else(|| Self::normalize_saint(&capitalised))
+    }
+
+    /// Normalizes a single part of a dedication, returning `None` if it doesn't look like either
+    /// a feast or a saint
+    fn normalize_part(part: &str) -> Option<SmolStr> {
+        let part = part.trim();
+        // Strip descriptions of the building (e.g. `Cath Ch of S Paul`)
+        let part = match part.find("Ch of ") {
+            Some(idx) => &part[idx + "Ch of ".len()..],
+            None => part,
+        };
+        if let Some((_, name)) = FEASTS.iter().find(|(f, _)| part.eq_ignore_ascii_case(f)) {
+            return Some(SmolStr::new(name));
+        }
+        let saint = ["S ", "St ", "St. ", "Saint ", "Ss "]
+            .iter()
+            .find_map(|prefix| part.strip_prefix(prefix))?;
+        Self::normalize_saint(saint)
+    }
+
+    /// Normalizes a saint's name, without the `S`/`St` prefix
+    fn normalize_saint(saint: &str) -> Option<SmolStr> {
+        let mut words = saint.split_whitespace();
+        let first = words.next()?;
+        if !first.chars().next()?.is_uppercase() {
+            return None;
+        }
+        let first = SPELLINGS
+            .iter()
+            .find(|(alt, _)| *alt == first)
+            .map_or(first, |(_, canonical)| canonical);
+        let mut name = first.to_owned();
+        let rest = words.collect::<Vec<_>>();
+        match rest.as_slice() {
+            // Thomas Becket has several names
+            ["a", "Becket"] | ["of", "Canterbury"] if first == "Thomas" => name.push_str(" Becket"),
+            _ => {
+                for word in rest {
+                    let expansion = EPITHETS
+                        .iter()
+                        .find(|(abbr, _)| *abbr == word)
+                        .map_or(word, |(_, expansion)| expansion);
+                    if !expansion.is_empty() {
+                        name.push(' ');
+                        name.push_str(expansion);
+                    }
+                }
+            }
+        }
+        Some(SmolStr::new(name))
+    }
+}
+
+impl Doves {
+    /// Gets every [`Ring`] in a church dedicated to `saint` (which can also be a feast, like `All
+    /// Saints`), in the order they appear in Dove's Guide.  `saint` is normalized in the same way
+    /// as in [`Dedication::is_dedicated_to`], so `"St Chad"`, `"Saint Chad"`, `"S Chad"` and
+    /// `"chad"` all find the same `Ring`s.
+    pub fn with_dedication(&self, saint: &str) -> Vec<&Ring> {
+        self.iter()
+            .filter(|ring| ring.dedication.is_dedicated_to(saint))
+            .collect()
+    }
+}
+
+impl Display for Dedication {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Dedication {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() || s.trim().eq_ignore_ascii_case(UNKNOWN) {
+            return Ok(Dedication::Unknown);
+        }
+        let names = s
+            .split(" & ")
+            .flat_map(|part| part.split(','))
+            .flat_map(|part| part.split(" and "))
+            .filter(|part| !part.trim().is_empty())
+            .map(Self::normalize_part)
+            .collect::<Option<Vec<_>>>();
+        let raw = SmolStr::new(s);
+        Ok(match names {
+            Some(names) if !names.is_empty() => Dedication::Named { raw, names },
+            _ => Dedication::Raw(raw),
+        })
+    }
+}
@@ -0,0 +1,153 @@
+//! Binning [`Ring`](crate::Ring)s into a grid of latitude and longitude, e.g. for tower-density
+//! heatmaps.
+
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{Bounds, Coordinates, Doves, RingFilter};
+
+/// What each [`Ring`](crate::Ring) contributes to the value of its cell in a [`DensityGrid`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DensityWeight {
+    /// Each `Ring` counts as one
+    Rings,
+    /// Each `Ring` counts as its number of bells
+    Bells,
+}
+
+/// The [`Ring`](crate::Ring)s of a [`Doves`], binned into cells of a fixed size in latitude and
+/// longitude.  Created by [`Doves::density_grid`].
+#[derive(Debug, Clone)]
+pub struct DensityGrid {
+    /// The height of each cell, in degrees of latitude
+    cell_lat: f64,
+    /// The width of each cell, in degrees of longitude
+    cell_long: f64,
+    /// The value of every non-empty cell, indexed by the number of cells north and east of
+    /// `(0, 0)` its south-west corner is
+    cells: BTreeMap<(i32, i32), usize>,
+}
+
+/// One non-empty cell of a [`DensityGrid`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridCell {
+    /// The area covered by this cell
+    pub bounds: Bounds,
+    /// The total [`DensityWeight`] of the `Ring`s in this cell
+    pub value: usize,
+}
+
+impl Doves {
+    /// Bins every [`Ring`](crate::Ring) which passes a [`RingFilter`] into a grid of cells
+    /// `cell_lat` degrees high and `cell_long` degrees wide, using the `Ring`s'
+    /// [best coordinates](crate::Ring::best_coordinates).  `Ring`s without coordinates are
+    /// ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either cell dimension isn't positive.
+    pub fn density_grid(
+        &self,
+        cell_lat: f64,
+        cell_long: f64,
+        weight: DensityWeight,
+        filter: impl RingFilter,
+    ) -> DensityGrid {
+        assert!(
+            cell_lat > 0.0 && cell_long > 0.0,
+            "Grid cells must have a positive size"
+        );
+        let mut cells = BTreeMap::new();
+        for ring in self.filter(filter) {
+            let (coords, _) = match ring.best_coordinates() {
+                Some(c) => c,
+                None => continue,
+            };
+            let cell = (
+                (coords.lat() / cell_lat).floor() as i32,
+                (coords.long() / cell_long).floor() as i32,
+            );
+            *cells.entry(cell).or_insert(0) += match weight {
+                DensityWeight::Rings => 1,
+                DensityWeight::Bells => usize::from(ring.bells),
+            };
+        }
+        DensityGrid {
+            cell_lat,
+            cell_long,
+            cells,
+        }
+    }
+}
+
+impl DensityGrid {
+    /// Returns an iterator over every non-empty cell, from south-west to north-east
+    pub fn cells(&self) -> impl Iterator<Item = GridCell> + '_ {
+        self.cells
+            .iter()
+            .map(move |(&(lat_idx, long_idx), &value)| {
+                // Clamp the edges, in case the cells don't divide the globe exactly
+                let lat = |idx: i32| (idx as f64 * self.cell_lat).clamp(-90.0, 90.0);
+                let long = |idx: i32| (idx as f64 * self.cell_long).clamp(-180.0, 180.0);
+                let corner =
+                    |lat_idx, long_idx| Coordinates::new(lat(lat_idx), long(long_idx)).unwrap();
+                GridCell {
+                    bounds: Bounds::new(
+                        corner(lat_idx, long_idx),
+                        corner(lat_idx + 1, long_idx + 1),
+                    ),
+                    value,
+                }
+            })
+    }
+
+    /// Renders every non-empty cell as CSV, with columns `min_lat`, `min_long`, `max_lat`,
+    /// `max_long` and `value`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("min_lat,min_long,max_lat,max_long,value\n");
+        for cell in self.cells() {
+            let (min, max) = (cell.bounds.min, cell.bounds.max);
+            writeln!(
+                csv,
+                "{},{},{},{},{}",
+                min.lat(),
+                min.long(),
+                max.lat(),
+                max.long(),
+                cell.value
+            )
+            .unwrap();
+        }
+        csv
+    }
+
+    /// Renders every non-empty cell as a GeoJSON `FeatureCollection`, where each cell is a
+    /// `Polygon` with its value in the `value` property
+    pub fn to_geojson(&self) -> String {
+        let features = self
+            .cells()
+            .map(|cell| {
+                let (min, max) = (cell.bounds.min, cell.bounds.max);
+                // GeoJSON positions are `[long, lat]`, and polygons are closed rings
+                let corners = [
+                    (min.long(), min.lat()),
+                    (max.long(), min.lat()),
+                    (max.long(), max.lat()),
+                    (min.long(), max.lat()),
+                    (min.long(), min.lat()),
+                ]
+                .iter()
+                .map(|(long, lat)| format!("[{},{}]", long, lat))
+                .collect::<Vec<_>>();
+                format!(
+                    r#"{{"type":"Feature","geometry":{{"type":"Polygon","coordinates":[[{}]]}},"properties":{{"value":{}}}}}"#,
+                    corners.join(","),
+                    cell.value
+                )
+            })
+            .collect::<Vec<_>>();
+        format!(
+            r#"{{"type":"FeatureCollection","features":[{}]}}"#,
+            features.join(",")
+        )
+    }
+}
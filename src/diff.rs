@@ -0,0 +1,136 @@
+//! Comparing two versions of a [`Ring`] field by field, or of a [`Doves`] ring by ring.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{Column, Doves, IndexKind, Ring, RingKey};
+
+/// A field which differs between two versions of a [`Ring`], as returned by [`Ring::diff`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct FieldChange {
+    /// The [`Column`] of Dove's CSV file containing the field
+    pub column: Column,
+    /// The value in the old `Ring`, formatted as in Dove's CSV file (see [`Column::value`])
+    pub old: String,
+    /// The value in the new `Ring`, formatted as in Dove's CSV file
+    pub new: String,
+}
+
+impl Ring {
+    /// Lists every field which differs between this `Ring` and `other`, in the order of the
+    /// [`Column`]s of Dove's CSV file.  Fields are compared by their values in the CSV file, so
+    /// (for example) `other` could be a locally corrected copy of this `Ring`.
+    pub fn diff(&self, other: &Ring) -> Vec<FieldChange> {
+        Column::all()
+            .filter_map(|column| {
+                let (old, new) = (column.value(self), column.value(other));
+                (old != new).then_some(FieldChange { column, old, new })
+            })
+            .collect()
+    }
+}
+
+/// The changes between two versions of Dove's Guide, with [`Ring`]s matched by [`RingKey`].
+/// Created with [`DovesDiff::between`] and applied to a [`Doves`] with [`Doves::apply`], so that
+/// a long-running application can update its data without reloading it.
+#[derive(Debug, Clone, Default)]
+pub struct DovesDiff {
+    /// `Ring`s which are only in the newer version
+    pub added: Vec<Ring>,
+    /// The keys of `Ring`s which are only in the older version
+    pub removed: Vec<RingKey>,
+    /// The newer versions of `Ring`s which are in both versions, but whose fields differ (see
+    /// [`Ring::diff`])
+    pub changed: Vec<Ring>,
+}
+
+impl DovesDiff {
+    /// Finds the changes needed to turn `old` into `new`
+    pub fn between(old: &Doves, new: &Doves) -> Self {
+        let old_rings = old.iter().map(|r| (r.key(), r)).collect::<HashMap<_, _>>();
+        let new_keys = new.iter().map(Ring::key).collect::<HashSet<_>>();
+        let mut diff = Self::default();
+        for ring in new {
+            match old_rings.get(&ring.key()) {
+                Some(old_ring) if old_ring.diff(ring).is_empty() => {}
+                Some(_) => diff.changed.push(ring.clone()),
+                None => diff.added.push(ring.clone()),
+            }
+        }
+        diff.removed = old
+            .iter()
+            .map(Ring::key)
+            .filter(|key| !new_keys.contains(key))
+            .collect();
+        diff
+    }
+
+    /// `true` if there are no changes
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl Doves {
+    /// Updates this `Doves` in place with the changes in a [`DovesDiff`]: removed [`Ring`]s are
+    /// deleted, changed `Ring`s are replaced (keeping their positions) and added `Ring`s are
+    /// appended.  Changed `Ring`s which aren't in this `Doves` are added, and removed `Ring`s
+    /// which aren't in it are ignored.  The indices are rebuilt as when loading.
+    pub fn apply(&mut self, diff: DovesDiff) {
+        let removed = diff.removed.into_iter().collect::<HashSet<_>>();
+        self.invalidate_indices();
+        self.towers.retain(|ring| !removed.contains(&ring.key()));
+
+        let keys = self.towers.iter().map(Ring::key).collect::<HashSet<_>>();
+        let (replacements, missing) = diff
+            .changed
+            .into_iter()
+            .partition::<Vec<_>, _>(|ring| keys.contains(&ring.key()));
+        let mut replacements = replacements
+            .into_iter()
+            .map(|ring| (ring.key(), ring))
+            .collect::<HashMap<_, _>>();
+        for ring in &mut self.towers {
+            if let Some(new_ring) = replacements.remove(&ring.key()) {
+                *ring = new_ring;
+            }
+        }
+        self.towers.extend(missing);
+        self.towers.extend(diff.added);
+        self.ensure_index(IndexKind::Id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DovesDiff;
+    use crate::{Doves, Ring, RingBuilder, TowerId, Weight};
+
+    fn ring(id: u32, bells: u8) -> Ring {
+        RingBuilder::new(TowerId::new(id), format!("Place {}", id), bells)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn apply_between_gives_new() {
+        let old = Doves::from(vec![ring(1, 6), ring(2, 8), ring(3, 10)]);
+        let mut reweighed = ring(2, 8);
+        reweighed.weight = Some(Weight::from_cwt_qr_lb(15, 2, 3));
+        let new = Doves::from(vec![ring(1, 6), reweighed, ring(4, 12)]);
+
+        let diff = DovesDiff::between(&old, &new);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.removed, vec![ring(3, 10).key()]);
+        assert_eq!(diff.changed.len(), 1);
+
+        let mut patched = old.clone();
+        patched.apply(diff);
+        assert_eq!(patched.len(), new.len());
+        for ring in &new {
+            let patched_ring = patched.get(ring.id).unwrap();
+            assert_eq!(patched_ring.diff(ring), vec![]);
+        }
+        assert!(patched.get(TowerId::new(3)).is_none());
+        assert!(DovesDiff::between(&patched, &new).is_empty());
+    }
+}
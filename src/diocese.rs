@@ -0,0 +1,219 @@
+use std::{
+    convert::Infallible,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::Symbol;
+
+/// The diocese which contains a [`Ring`](crate::Ring).
+///
+/// Every Anglican diocese in England and Wales gets its own variant, as do Church of Ireland
+/// towers (which Dove's doesn't split by diocese).  Anything else (Roman Catholic dioceses,
+/// trusts, secular towers, etc.) is stored verbatim in [`Diocese::Other`].
+///
+/// **CSV Header**: `Diocese`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Diocese {
+    /* CHURCH OF ENGLAND */
+    BathAndWells,
+    Birmingham,
+    Blackburn,
+    Bristol,
+    Canterbury,
+    Carlisle,
+    Chelmsford,
+    Chester,
+    Chichester,
+    Coventry,
+    Derby,
+    Durham,
+    Ely,
+    Europe,
+    Exeter,
+    Gloucester,
+    Guildford,
+    Hereford,
+    Leeds,
+    Leicester,
+    Lichfield,
+    Lincoln,
+    Liverpool,
+    London,
+    Manchester,
+    Newcastle,
+    Norwich,
+    Oxford,
+    Peterborough,
+    Portsmouth,
+    Rochester,
+    StAlbans,
+    StEdmundsburyAndIpswich,
+    Salisbury,
+    Sheffield,
+    SodorAndMan,
+    Southwark,
+    SouthwellAndNottingham,
+    Truro,
+    Winchester,
+    Worcester,
+    York,
+
+    /* CHURCH IN WALES */
+    Bangor,
+    Llandaff,
+    Monmouth,
+    StAsaph,
+    StDavids,
+    SwanseaAndBrecon,
+
+    /* CHURCH OF IRELAND */
+    /// Any Church of Ireland tower.  Dove's doesn't record which diocese these are in.
+    ///
+    /// **Dove's value:** `(Ireland)`
+    ChurchOfIreland,
+
+    /// Any value not covered by the other variants, stored exactly as it appears in Dove's.
+    Other(Symbol),
+}
+
+/// The `Diocese`s which have their own variants, along with their values in Dove's Guide and their
+/// human-readable names.
+static NAMES: [(Diocese, &str, &str); 49] = [
+    (Diocese::BathAndWells, "Bath+Wells", "Bath and Wells"),
+    (Diocese::Birmingham, "Birmingham", "Birmingham"),
+    (Diocese::Blackburn, "Blackburn", "Blackburn"),
+    (Diocese::Bristol, "Bristol", "Bristol"),
+    (Diocese::Canterbury, "Canterbury", "Canterbury"),
+    (Diocese::Carlisle, "Carlisle", "Carlisle"),
+    (Diocese::Chelmsford, "Chelmsford", "Chelmsford"),
+    (Diocese::Chester, "Chester", "Chester"),
+    (Diocese::Chichester, "Chichester", "Chichester"),
+    (Diocese::Coventry, "Coventry", "Coventry"),
+    (Diocese::Derby, "Derby", "Derby"),
+    (Diocese::Durham, "Durham", "Durham"),
+    (Diocese::Ely, "Ely", "Ely"),
+    (Diocese::Europe, "Europe", "Europe"),
+    (Diocese::Exeter, "Exeter", "Exeter"),
+    (Diocese::Gloucester, "Gloucester", "Gloucester"),
+    (Diocese::Guildford, "Guildford", "Guildford"),
+    (Diocese::Hereford, "Hereford", "Hereford"),
+    (Diocese::Leeds, "Leeds", "Leeds"),
+    (Diocese::Leicester, "Leicester", "Leicester"),
+    (Diocese::Lichfield, "Lichfield", "Lichfield"),
+    (Diocese::Lincoln, "Lincoln", "Lincoln"),
+    (Diocese::Liverpool, "Liverpool", "Liverpool"),
+    (Diocese::London, "London", "London"),
+    (Diocese::Manchester, "Manchester", "Manchester"),
+    (Diocese::Newcastle, "Newcastle", "Newcastle"),
+    (Diocese::Norwich, "Norwich", "Norwich"),
+    (Diocese::Oxford, "Oxford", "Oxford"),
+    (Diocese::Peterborough, "Peterborough", "Peterborough"),
+    (Diocese::Portsmouth, "Portsmouth", "Portsmouth"),
+    (Diocese::Rochester, "Rochester", "Rochester"),
+    (Diocese::StAlbans, "St Albans", "St Albans"),
+    (
+        Diocese::StEdmundsburyAndIpswich,
+        "StEds+Ipswich",
+        "St Edmundsbury and Ipswich",
+    ),
+    (Diocese::Salisbury, "Salisbury", "Salisbury"),
+    (Diocese::Sheffield, "Sheffield", "Sheffield"),
+    (Diocese::SodorAndMan, "Sodor+Man", "Sodor and Man"),
+    (Diocese::Southwark, "Southwark", "Southwark"),
+    (
+        Diocese::SouthwellAndNottingham,
+        "SouthwellNottm",
+        "Southwell and Nottingham",
+    ),
+    (Diocese::Truro, "Truro", "Truro"),
+    (Diocese::Winchester, "Winchester", "Winchester"),
+    (Diocese::Worcester, "Worcester", "Worcester"),
+    (Diocese::York, "York", "York"),
+    (Diocese::Bangor, "Bangor", "Bangor"),
+    (Diocese::Llandaff, "Llandaff", "Llandaff"),
+    (Diocese::Monmouth, "Monmouth", "Monmouth"),
+    (Diocese::StAsaph, "St Asaph", "St Asaph"),
+    (Diocese::StDavids, "St Davids", "St Davids"),
+    (
+        Diocese::SwanseaAndBrecon,
+        "Swansea+Brecon",
+        "Swansea and Brecon",
+    ),
+    (Diocese::ChurchOfIreland, "(Ireland)", "Church of Ireland"),
+];
+
+impl Diocese {
+    /// The string used for this `Diocese` in Dove's Guide
+    pub fn dove_code(&self) -> &str {
+        match self {
+            Diocese::Other(s) => s,
+            d => Self::lookup(d).1,
+        }
+    }
+
+    /// `true` if this `Diocese` belongs to the Church of England
+    pub fn is_church_of_england(&self) -> bool {
+        !matches!(
+            self,
+            Diocese::Bangor
+                | Diocese::Llandaff
+                | Diocese::Monmouth
+                | Diocese::StAsaph
+                | Diocese::StDavids
+                | Diocese::SwanseaAndBrecon
+                | Diocese::ChurchOfIreland
+                | Diocese::Other(_)
+        )
+    }
+
+    /// `true` if this `Diocese` belongs to the Church in Wales
+    pub fn is_church_in_wales(&self) -> bool {
+        matches!(
+            self,
+            Diocese::Bangor
+                | Diocese::Llandaff
+                | Diocese::Monmouth
+                | Diocese::StAsaph
+                | Diocese::StDavids
+                | Diocese::SwanseaAndBrecon
+        )
+    }
+
+    fn lookup(d: &Diocese) -> &'static (Diocese, &'static str, &'static str) {
+        NAMES
+            .iter()
+            .find(|(d2, _, _)| d2 == d)
+            .expect("Every non-`Other` diocese should be in `NAMES`")
+    }
+}
+
+impl Display for Diocese {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Diocese::Other(s) => write!(f, "{}", s),
+            d => write!(f, "{}", Self::lookup(d).2),
+        }
+    }
+}
+
+impl FromStr for Diocese {
+    type Err = Infallible;
+
+    /// Parses a `Diocese` from either its value in Dove's Guide or its human-readable name (i.e.
+    /// the output of [`Display`]).  Dove's sometimes lists several owners separated by `;` (e.g.
+    /// `Monmouth;FriendsOfFC`), in which case the first recognised diocese is used.  Unrecognised
+    /// strings become [`Diocese::Other`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let known = s.split(';').find_map(|part| {
+            let part = part.trim();
+            NAMES
+                .iter()
+                .find(|(_, code, name)| part == *code || part == *name)
+        });
+        Ok(match known {
+            Some((d, _, _)) => d.clone(),
+            None => Diocese::Other(Symbol::new(s)),
+        })
+    }
+}
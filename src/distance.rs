@@ -0,0 +1,81 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{Coordinates, Ring};
+
+/// The mean radius of the Earth, in kilometres
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+const KM_PER_MILE: f64 = 1.609344;
+
+/// A distance over the surface of the Earth, which can be read in either kilometres or miles.
+///
+/// `Distance`s are [`Display`]ed in kilometres (e.g. `12.3 km`).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Distance {
+    km: f64,
+}
+
+impl Distance {
+    /// Creates a `Distance` from a number of kilometres
+    pub const fn from_km(km: f64) -> Self {
+        Self { km }
+    }
+
+    /// Creates a `Distance` from a number of (statute) miles
+    pub fn from_miles(miles: f64) -> Self {
+        Self::from_km(miles * KM_PER_MILE)
+    }
+
+    /// This `Distance` in kilometres
+    pub fn km(self) -> f64 {
+        self.km
+    }
+
+    /// This `Distance` in (statute) miles
+    pub fn miles(self) -> f64 {
+        self.km / KM_PER_MILE
+    }
+
+    /// Creates a `Distance` from the angle (in radians) which it subtends at the centre of the
+    /// Earth
+    pub(crate) fn from_radians(angle: f64) -> Self {
+        Self::from_km(angle * EARTH_RADIUS_KM)
+    }
+}
+
+impl Display for Distance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1} km", self.km)
+    }
+}
+
+impl Coordinates {
+    /// The great-circle [`Distance`] between two points, using the haversine formula.  This
+    /// treats the Earth as a sphere, so is accurate to about 0.5%.
+    pub fn distance_to(self, other: Coordinates) -> Distance {
+        let (lat1, lat2) = (self.lat().to_radians(), other.lat().to_radians());
+        let d_lat = lat2 - lat1;
+        let d_long = (other.long() - self.long()).to_radians();
+        let a =
+            (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_long / 2.0).sin().powi(2);
+        // `min` guards against rounding errors taking `a` just above 1 for antipodal points
+        let angle = 2.0 * a.sqrt().min(1.0).asin();
+        Distance::from_radians(angle)
+    }
+}
+
+impl Ring {
+    /// The [`Distance`] from this `Ring` to some [`Coordinates`], using the
+    /// [best coordinates](Ring::best_coordinates) of this `Ring`.  Returns `None` if this `Ring`
+    /// has no coordinates.
+    pub fn distance_to(&self, coords: Coordinates) -> Option<Distance> {
+        let (own_coords, _) = self.best_coordinates()?;
+        Some(own_coords.distance_to(coords))
+    }
+
+    /// The [`Distance`] between two `Ring`s, using the [best coordinates](Ring::best_coordinates)
+    /// of each.  Returns `None` if either `Ring` has no coordinates.
+    pub fn distance_to_ring(&self, other: &Ring) -> Option<Distance> {
+        let (other_coords, _) = other.best_coordinates()?;
+        self.distance_to(other_coords)
+    }
+}
@@ -0,0 +1,131 @@
+//! Precomputed [`Distance`]s between every pair of a set of [`Ring`]s, for algorithms (such as
+//! outing planning or clustering) which look up the same distances many times.
+
+use std::cmp::Ordering;
+
+#[cfg(feature = "parallel")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{Coordinates, Distance, Ring};
+
+/// The [`Distance`] between every pair of a set of [`Ring`]s, indexed by the `Ring`s' positions
+/// in the slice passed to [`DistanceMatrix::new`].  Distances use the
+/// [best coordinates](Ring::best_coordinates) of each `Ring`, so are `None` for any `Ring` without
+/// coordinates.
+///
+/// Only one triangle of the matrix is stored, in single precision, so a matrix of every `Ring`
+/// in Dove's Guide takes about 100MB.
+#[derive(Debug, Clone)]
+pub struct DistanceMatrix {
+    /// Whether each `Ring` has coordinates
+    has_coords: Vec<bool>,
+    /// The distances in kilometres (or `NaN` if unknown) between each pair `(i, j)` where `i < j`,
+    /// ordered by `i` then `j`
+    km: Vec<f32>,
+}
+
+impl DistanceMatrix {
+    /// Computes the distances between every pair of `rings`
+    pub fn new(rings: &[&Ring]) -> Self {
+        let coords = coordinates(rings);
+        let km = (0..coords.len())
+            .flat_map(|i| row(&coords, i))
+            .collect::<Vec<_>>();
+        Self {
+            has_coords: coords.iter().map(Option::is_some).collect(),
+            km,
+        }
+    }
+
+    /// Computes the distances between every pair of `rings`, in parallel using [`rayon`].  The
+    /// result is identical to [`DistanceMatrix::new`].
+    #[cfg(feature = "parallel")]
+    pub fn new_parallel(rings: &[&Ring]) -> Self {
+        let coords = coordinates(rings);
+        let km = (0..coords.len())
+            .into_par_iter()
+            .flat_map_iter(|i| row(&coords, i))
+            .collect::<Vec<_>>();
+        Self {
+            has_coords: coords.iter().map(Option::is_some).collect(),
+            km,
+        }
+    }
+
+    /// The number of [`Ring`]s in this matrix
+    pub fn len(&self) -> usize {
+        self.has_coords.len()
+    }
+
+    /// `true` if this matrix contains no [`Ring`]s
+    pub fn is_empty(&self) -> bool {
+        self.has_coords.is_empty()
+    }
+
+    /// The [`Distance`] between the `Ring`s at indices `i` and `j`, or `None` if either has no
+    /// coordinates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is out of bounds.
+    pub fn get(&self, i: usize, j: usize) -> Option<Distance> {
+        assert!(
+            i < self.len() && j < self.len(),
+            "Index out of bounds of DistanceMatrix"
+        );
+        let km = match i.cmp(&j) {
+            Ordering::Less => self.km[self.index(i, j)],
+            Ordering::Greater => self.km[self.index(j, i)],
+            Ordering::Equal if self.has_coords[i] => 0.0,
+            Ordering::Equal => return None,
+        };
+        (!km.is_nan()).then(|| Distance::from_km(f64::from(km)))
+    }
+
+    /// The known [`Distance`]s from the `Ring` at index `i` to every other `Ring`, along with
+    /// their indices
+    pub fn distances_from(&self, i: usize) -> impl Iterator<Item = (usize, Distance)> + '_ {
+        (0..self.len())
+            .filter(move |&j| j != i)
+            .filter_map(move |j| Some((j, self.get(i, j)?)))
+    }
+
+    /// The index of the `Ring` nearest to the `Ring` at index `i` (excluding itself), along with
+    /// the [`Distance`] to it.  Returns `None` if no other `Ring` has a known distance.
+    pub fn nearest(&self, i: usize) -> Option<(usize, Distance)> {
+        self.distances_from(i)
+            .min_by(|(_, a), (_, b)| a.km().total_cmp(&b.km()))
+    }
+
+    /// The indices of the (up to) `k` `Ring`s nearest to the `Ring` at index `i` (excluding
+    /// itself), nearest first, along with the [`Distance`]s to them
+    pub fn k_nearest(&self, i: usize, k: usize) -> Vec<(usize, Distance)> {
+        let mut distances = self.distances_from(i).collect::<Vec<_>>();
+        distances.sort_by(|(_, a), (_, b)| a.km().total_cmp(&b.km()));
+        distances.truncate(k);
+        distances
+    }
+
+    /// The index in `self.km` of the pair `(i, j)`, where `i < j`
+    fn index(&self, i: usize, j: usize) -> usize {
+        i * self.len() - i * (i + 1) / 2 + (j - i - 1)
+    }
+}
+
+/// The best coordinates of every `Ring`
+fn coordinates(rings: &[&Ring]) -> Vec<Option<Coordinates>> {
+    rings
+        .iter()
+        .map(|ring| ring.best_coordinates().map(|(coords, _)| coords))
+        .collect()
+}
+
+/// The distances in kilometres from the `Ring` at index `i` to every `Ring` after it
+fn row(coords: &[Option<Coordinates>], i: usize) -> impl Iterator<Item = f32> + '_ {
+    coords[i + 1..]
+        .iter()
+        .map(move |&other| match (coords[i], other) {
+            (Some(a), Some(b)) => a.distance_to(b).km() as f32,
+            _ => f32::NAN,
+        })
+}
@@ -0,0 +1,116 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{normalize::fold, Doves, Ring};
+
+/// How much each kind of evidence contributes to a [`Duplicate`]'s similarity.  These add up to
+/// 1, so that the similarity is between 0 and 1.
+const COORDINATES_WEIGHT: f64 = 0.3;
+const PLACE_WEIGHT: f64 = 0.2;
+const DEDICATION_WEIGHT: f64 = 0.2;
+const COUNTY_WEIGHT: f64 = 0.1;
+const BELLS_WEIGHT: f64 = 0.1;
+const WEIGHT_WEIGHT: f64 = 0.1;
+
+/// Coordinates which are at least this far apart (in km) count as completely different
+const MAX_COORDINATE_KM: f64 = 1.0;
+/// Tenor weights which differ by less than this fraction count as the same
+const WEIGHT_TOLERANCE: f64 = 0.01;
+
+/// A pair of [`Ring`]s in different towers which look like two entries for the same
+/// installation, found by [`Doves::find_duplicates`]
+#[derive(Debug, Clone, Copy)]
+pub struct Duplicate<'d> {
+    /// The `Ring` which appears first in Dove's Guide
+    pub first: &'d Ring,
+    /// The `Ring` which appears later in Dove's Guide
+    pub second: &'d Ring,
+    /// `true` if both `Ring`s have exactly the same [coordinates](Ring::coordinates)
+    pub same_coordinates: bool,
+    /// `true` if both `Ring`s have the same place, county and dedication
+    pub same_place: bool,
+    /// How similar the two `Ring`s are, between 0 (nothing in common) and 1 (the same location,
+    /// place, dedication, county, number of bells and tenor weight)
+    pub similarity: f64,
+}
+
+impl Doves {
+    /// Finds pairs of [`Ring`]s with different [`TowerId`](crate::TowerId)s which have either
+    /// identical coordinates or the same place, county and dedication, and so could be duplicate
+    /// entries for the same installation.  Rings in the same tower (i.e. with the same
+    /// `TowerId`) are never reported.  The pairs are returned most similar first.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn find_duplicates(&self) -> Vec<Duplicate<'_>> {
+        let mut by_coords = HashMap::<(u64, u64), Vec<usize>>::new();
+        let mut by_place = HashMap::<_, Vec<usize>>::new();
+        for (idx, ring) in self.towers.iter().enumerate() {
+            if let Some(coords) = ring.coordinates {
+                let key = (coords.lat().to_bits(), coords.long().to_bits());
+                by_coords.entry(key).or_default().push(idx);
+            }
+            by_place.entry(place_key(ring)).or_default().push(idx);
+        }
+
+        let mut pairs = BTreeSet::new();
+        for group in by_coords.values().chain(by_place.values()) {
+            for (i, &first) in group.iter().enumerate() {
+                for &second in &group[i + 1..] {
+                    if self.towers[first].id != self.towers[second].id {
+                        pairs.insert((first, second));
+                    }
+                }
+            }
+        }
+
+        let mut duplicates = pairs
+            .into_iter()
+            .map(|(first, second)| Duplicate::new(&self.towers[first], &self.towers[second]))
+            .collect::<Vec<_>>();
+        duplicates.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        #[cfg(feature = "tracing")]
+        tracing::info!(pairs = duplicates.len(), "found possible duplicates");
+        duplicates
+    }
+}
+
+impl<'d> Duplicate<'d> {
+    fn new(first: &'d Ring, second: &'d Ring) -> Self {
+        let coordinates = match (first.coordinates, second.coordinates) {
+            (Some(a), Some(b)) => (1.0 - a.distance_to(b).km() / MAX_COORDINATE_KM).max(0.0),
+            _ => 0.0,
+        };
+        let same_place = fold(&first.place) == fold(&second.place)
+            && first.place2.as_deref().map(fold) == second.place2.as_deref().map(fold);
+        let same_weight = match (first.weight, second.weight) {
+            (Some(a), Some(b)) => {
+                let (a, b) = (a.lbs(), b.lbs());
+                (a - b).abs() <= a.max(b) * WEIGHT_TOLERANCE
+            }
+            (None, None) => true,
+            _ => false,
+        };
+        let similarity = COORDINATES_WEIGHT * coordinates
+            + PLACE_WEIGHT * f64::from(same_place)
+            + DEDICATION_WEIGHT * f64::from(first.dedication == second.dedication)
+            + COUNTY_WEIGHT * f64::from(first.county == second.county)
+            + BELLS_WEIGHT * f64::from(first.bells == second.bells)
+            + WEIGHT_WEIGHT * f64::from(same_weight);
+        Self {
+            first,
+            second,
+            same_coordinates: first.coordinates.is_some()
+                && first.coordinates == second.coordinates,
+            same_place: place_key(first) == place_key(second),
+            similarity,
+        }
+    }
+}
+
+/// The fields which identify the location of a `Ring` by name
+pub(crate) fn place_key(ring: &Ring) -> (String, Option<&str>, Option<&str>, &str) {
+    (
+        fold(&ring.place),
+        ring.place2.as_deref(),
+        ring.county.as_deref(),
+        ring.dedication.as_str(),
+    )
+}
@@ -0,0 +1,98 @@
+//! Attaching external data (e.g. visit records, local contacts or association notes) to the
+//! towers in a [`Doves`], so applications don't need to keep their own tables alongside it.
+
+use std::{
+    any::{Any, TypeId},
+    collections::{hash_map::Entry, HashMap},
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+
+use crate::{Doves, Ring, TowerId};
+
+/// A source of external data about towers, which can be attached to a [`Doves`] with
+/// [`Doves::enrich`].  Each type of data is stored separately, so several `Enricher`s can be
+/// used on the same `Doves` as long as their `Data` types differ.
+///
+/// This is implemented for `HashMap<TowerId, T>`, so data which is already keyed by [`TowerId`]
+/// can be attached directly.
+pub trait Enricher {
+    /// The data attached to each tower
+    type Data: Any + Send + Sync;
+
+    /// Produces the data for the tower containing `ring`, or `None` if there's nothing to attach.
+    /// This is called for each [`Ring`] in a tower in turn, until one of them produces data.
+    fn enrich(&mut self, ring: &Ring) -> Option<Self::Data>;
+}
+
+impl<T: Clone + Any + Send + Sync> Enricher for HashMap<TowerId, T> {
+    type Data = T;
+
+    fn enrich(&mut self, ring: &Ring) -> Option<T> {
+        self.get(&ring.id).cloned()
+    }
+}
+
+/// The data attached by every [`Enricher`], keyed by the type of the data.  Each value is a
+/// `HashMap<TowerId, Data>`, shared so that cloning a `Doves` doesn't copy it.
+#[derive(Clone, Default)]
+pub(crate) struct Enrichments {
+    tables: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Enrichments {
+    fn table<T: Any + Send + Sync>(&self) -> Option<&HashMap<TowerId, T>> {
+        self.tables.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+}
+
+impl Debug for Enrichments {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Enrichments")
+            .field("tables", &self.tables.len())
+            .finish()
+    }
+}
+
+impl Doves {
+    /// Runs an [`Enricher`] over every tower, attaching the data it produces.  This replaces any
+    /// data of the same type which was attached before.  Data is keyed by [`TowerId`], so it
+    /// stays attached when `Ring`s are added, removed or reordered.
+    pub fn enrich<E: Enricher>(&mut self, mut enricher: E) {
+        let mut table = HashMap::<TowerId, E::Data>::new();
+        for ring in &self.towers {
+            if let Entry::Vacant(entry) = table.entry(ring.id) {
+                if let Some(data) = enricher.enrich(ring) {
+                    entry.insert(data);
+                }
+            }
+        }
+        let table: Arc<dyn Any + Send + Sync> = Arc::new(table);
+        self.enrichments
+            .tables
+            .insert(TypeId::of::<E::Data>(), table);
+    }
+
+    /// The data of type `T` attached to the tower with a given [`TowerId`] by [`Doves::enrich`]
+    pub fn enrichment<T: Any + Send + Sync>(&self, id: TowerId) -> Option<&T> {
+        self.enrichments.table::<T>()?.get(&id)
+    }
+
+    /// Every tower with data of type `T` attached by [`Doves::enrich`], in no particular order
+    pub fn enrichments<T: Any + Send + Sync>(&self) -> impl Iterator<Item = (TowerId, &T)> + '_ {
+        let table = self.enrichments.table::<T>();
+        table.into_iter().flatten().map(|(&id, data)| (id, data))
+    }
+
+    /// Removes all the data of type `T` attached by [`Doves::enrich`]
+    pub fn remove_enrichments<T: Any + Send + Sync>(&mut self) {
+        self.enrichments.tables.remove(&TypeId::of::<T>());
+    }
+}
+
+impl Ring {
+    /// The data of type `T` attached to this `Ring`'s tower in `doves` by [`Doves::enrich`]
+    pub fn enrichment<'d, T: Any + Send + Sync>(&self, doves: &'d Doves) -> Option<&'d T> {
+        doves.enrichment(self.id)
+    }
+}
@@ -0,0 +1,297 @@
+//! Writing [`Ring`]s back out as CSV, using the same columns and formats as Dove's Guide.
+
+use std::{
+    fmt::{Display, Formatter},
+    io::Write,
+    str::FromStr,
+};
+
+use crate::{AccessStatus, Doves, Query, Ring};
+
+/// A column of Dove's CSV file, which can be written by [`Doves::write_csv`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Column {
+    TowerId,
+    RingType,
+    Place,
+    Place2,
+    PlaceCountyList,
+    Dedication,
+    AltName,
+    County,
+    Country,
+    Iso3166Code,
+    Diocese,
+    Lat,
+    Long,
+    Bells,
+    Unringable,
+    Semitones,
+    Weight,
+    Approximate,
+    Note,
+    Frequency,
+    Details,
+    GroundFloor,
+    Toilet,
+    Simulator,
+    ExtraInfo,
+    WebPage,
+    Affiliations,
+    OsGridRef,
+    Postcode,
+    Practice,
+    OverhaulYear,
+    Contractor,
+    TuneYear,
+    BuildingGrade,
+    BuildingId,
+    ChurchCare,
+    TowerBase,
+    DoveId,
+    SatnavLat,
+    SatnavLong,
+}
+
+/// Every [`Column`] with its header in Dove's CSV file, in the order that Dove's uses
+static HEADERS: [(Column, &str); 40] = [
+    (Column::TowerId, "TowerID"),
+    (Column::RingType, "RingType"),
+    (Column::Place, "Place"),
+    (Column::Place2, "Place2"),
+    (Column::PlaceCountyList, "PlaceCL"),
+    (Column::Dedication, "Dedicn"),
+    (Column::AltName, "AltName"),
+    (Column::County, "County"),
+    (Column::Country, "Country"),
+    (Column::Iso3166Code, "ISO3166code"),
+    (Column::Diocese, "Diocese"),
+    (Column::Lat, "Lat"),
+    (Column::Long, "Long"),
+    (Column::Bells, "Bells"),
+    (Column::Unringable, "UR"),
+    (Column::Semitones, "Semitones"),
+    (Column::Weight, "Wt"),
+    (Column::Approximate, "App"),
+    (Column::Note, "Note"),
+    (Column::Frequency, "Hz"),
+    (Column::Details, "Details"),
+    (Column::GroundFloor, "GF"),
+    (Column::Toilet, "Toilet"),
+    (Column::Simulator, "Simulator"),
+    (Column::ExtraInfo, "ExtraInfo"),
+    (Column::WebPage, "WebPage"),
+    (Column::Affiliations, "Affiliations"),
+    (Column::OsGridRef, "NG"),
+    (Column::Postcode, "Postcode"),
+    (Column::Practice, "Practice"),
+    (Column::OverhaulYear, "OvhaulYr"),
+    (Column::Contractor, "Contractor"),
+    (Column::TuneYear, "TuneYr"),
+    (Column::BuildingGrade, "LGrade"),
+    (Column::BuildingId, "BldgID"),
+    (Column::ChurchCare, "ChurchCare"),
+    (Column::TowerBase, "TowerBase"),
+    (Column::DoveId, "DoveID"),
+    (Column::SatnavLat, "SNLat"),
+    (Column::SatnavLong, "SNLong"),
+];
+
+impl Column {
+    /// Every `Column`, in the order they appear in Dove's CSV file
+    pub fn all() -> impl Iterator<Item = Column> {
+        HEADERS.iter().map(|(column, _)| *column)
+    }
+
+    /// The header of this `Column` in Dove's CSV file (e.g. `Dedicn` or `OvhaulYr`)
+    pub fn dove_header(self) -> &'static str {
+        HEADERS[self as usize].1
+    }
+
+    /// The value of this `Column` for a [`Ring`], formatted as in Dove's CSV file.  Values which
+    /// this crate doesn't store exactly are normalized, so aren't always the text that was loaded:
+    ///
+    /// - `ExtraInfo` features which this crate recognises are written in a standard form (e.g.
+    ///   `anticlockwise` becomes `Anticlockwise`)
+    /// - `OvhaulYr` and `TuneYr` are reduced to just the year (e.g. `27 Oct 2013` becomes
+    ///   `2013`), and implausible years are left out
+    /// - `LGrade` and `BldgID` are empty rather than `None`
+    /// - Numbers are written with a fixed precision (e.g. coordinates to 5 decimal places)
+    #[allow(deprecated)] // `DoveID` is still a column of Dove's Guide
+    pub fn value(self, ring: &Ring) -> String {
+        fn opt(value: Option<impl Display>) -> String {
+            value.map_or_else(String::new, |v| v.to_string())
+        }
+        fn flag(value: bool, text: &str) -> String {
+            if value { text } else { "" }.to_owned()
+        }
+        let coord = |value: Option<f64>| opt(value.map(|x| format!("{:.5}", x)));
+
+        let coords = ring.coordinates;
+        let satnav = ring.satnav_coordinates;
+        match self {
+            Column::TowerId => ring.id.to_string(),
+            Column::RingType => ring.ring_type.dove_name().to_owned(),
+            Column::Place => ring.place.to_string(),
+            Column::Place2 => opt(ring.place2.as_ref()),
+            Column::PlaceCountyList => opt(ring.place_county_list.as_ref()),
+            Column::Dedication => ring.dedication.to_string(),
+            Column::AltName => opt(ring.alt_name.as_ref()),
+            Column::County => opt(ring.county.as_ref()),
+            Column::Country => opt(ring.country.as_ref()),
+            Column::Iso3166Code => opt(ring.iso_3166_code.as_ref()),
+            Column::Diocese => opt(ring.diocese.as_ref().map(|d| d.dove_code())),
+            Column::Lat => coord(coords.map(|c| c.lat())),
+            Column::Long => coord(coords.map(|c| c.long())),
+            Column::Bells => ring.bells.to_string(),
+            Column::Unringable => flag(ring.access == AccessStatus::Unringable, "u/r"),
+            Column::Semitones => opt(ring.semitones.as_ref()),
+            Column::Weight => opt(ring.weight.map(|w| format!("{:.0}", w.lbs()))),
+            Column::Approximate => flag(ring.weight.is_some_and(|w| w.is_approximate()), "app"),
+            Column::Note => opt(ring.note),
+            Column::Frequency => opt(ring.freq.map(|f| format!("{:.1}", f.hz()))),
+            Column::Details => ring.details.dove_code().to_owned(),
+            Column::GroundFloor => flag(ring.ground_floor(), "GF"),
+            Column::Toilet => flag(ring.toilet(), "T"),
+            Column::Simulator => flag(ring.simulator(), "T"),
+            Column::ExtraInfo => ring
+                .extra_info
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; "),
+            Column::WebPage => opt(ring.url.as_ref()),
+            Column::Affiliations => {
+                // Sort by code, so that affiliations are listed alphabetically as in Dove's file
+                let mut codes = ring
+                    .affiliations
+                    .iter()
+                    .map(|a| a.dove_code())
+                    .collect::<Vec<_>>();
+                codes.sort_unstable();
+                codes.join(";")
+            }
+            Column::OsGridRef => opt(ring.os_grid_ref.as_ref()),
+            Column::Postcode => opt(ring.postcode.as_ref()),
+            Column::Practice => opt(ring.practice.as_ref()),
+            Column::OverhaulYear => opt(ring.overhaul_year),
+            Column::Contractor => opt(ring.contractor.as_ref()),
+            Column::TuneYear => opt(ring.tune_year),
+            Column::BuildingGrade => opt(ring.building_grade.as_ref()),
+            Column::BuildingId => opt(ring.building_id.as_ref()),
+            Column::ChurchCare => opt(ring.church_care.map(|id| id.get())),
+            Column::TowerBase => format!("{:04}", ring.towerbase_id),
+            Column::DoveId => opt(ring.dove_id.as_ref()),
+            Column::SatnavLat => coord(satnav.map(|c| c.lat())),
+            Column::SatnavLong => coord(satnav.map(|c| c.long())),
+        }
+    }
+}
+
+impl Display for Column {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.dove_header())
+    }
+}
+
+impl FromStr for Column {
+    type Err = ParseColumnError;
+
+    /// Parses a `Column` from its header in Dove's CSV file (e.g. `"TowerID"`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HEADERS
+            .iter()
+            .find(|(_, header)| *header == s.trim())
+            .map(|(column, _)| *column)
+            .ok_or_else(|| ParseColumnError(s.to_owned()))
+    }
+}
+
+/// The error generated when parsing a [`Column`] from a string which isn't one of Dove's headers.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseColumnError(String);
+
+impl Display for ParseColumnError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a column of Dove's Guide", self.0)
+    }
+}
+
+impl std::error::Error for ParseColumnError {}
+
+impl Doves {
+    /// Writes every [`Ring`] in this `Doves` as CSV, with the given [`Column`]s in the given
+    /// order.  Passing every column (i.e. `Column::all()`) produces a file in the same format as
+    /// Dove's `dove.csv`, which loads to the same `Ring`s.  However, it isn't a byte-for-byte copy
+    /// of the file they were loaded from, since some values are normalized (see
+    /// [`Column::value`]).
+    pub fn write_csv(&self, writer: impl Write, columns: &[Column]) -> Result<(), csv::Error> {
+        write_rings_csv(self.iter(), writer, columns)
+    }
+}
+
+impl Query<'_> {
+    /// Writes every [`Ring`] which matches this `Query` as CSV, with the given [`Column`]s in the
+    /// given order (see [`Doves::write_csv`]).
+    pub fn write_csv(&self, writer: impl Write, columns: &[Column]) -> Result<(), csv::Error> {
+        write_rings_csv(self.iter(), writer, columns)
+    }
+}
+
+fn write_rings_csv<'d>(
+    rings: impl IntoIterator<Item = &'d Ring>,
+    writer: impl Write,
+    columns: &[Column],
+) -> Result<(), csv::Error> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(columns.iter().map(|c| c.dove_header()))?;
+    for ring in rings {
+        writer.write_record(columns.iter().map(|c| c.value(ring)))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Column, Doves};
+
+    const ROW: &str = "12574,Full circle ring,Ab Kettleby,,,S James,,Leicestershire,England,GB,\
+        Leicester,52.79858,-0.92747,6,,,974,,G,783.0,C,GF,,,unringable; anticlockwise,\
+        http://www.abkettlebybells.co.uk/,LeiDG,SK724228,LE14 3HS,Wed,27 Oct 2013,,,None,None,\
+        619058,6918,AB KETTLEB,52.79968,-0.92635";
+
+    fn export(doves: &Doves) -> String {
+        let mut csv = Vec::new();
+        let columns = Column::all().collect::<Vec<_>>();
+        doves.write_csv(&mut csv, &columns).unwrap();
+        String::from_utf8(csv).unwrap()
+    }
+
+    /// The value of `column` in the first row of `csv`
+    fn field(csv: &str, column: Column) -> String {
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        let record = reader.records().next().unwrap().unwrap();
+        record[column as usize].to_owned()
+    }
+
+    #[test]
+    fn export_is_normalized() {
+        let headers = Column::all().map(Column::dove_header).collect::<Vec<_>>();
+        let original = format!("{}\n{}\n", headers.join(","), ROW);
+        let exported = export(&Doves::from_bytes_fast(original.as_bytes()).unwrap());
+
+        assert_eq!(
+            field(&exported, Column::ExtraInfo),
+            "Unringable; Anticlockwise"
+        );
+        assert_eq!(field(&exported, Column::OverhaulYear), "2013");
+        assert_eq!(field(&exported, Column::BuildingGrade), "");
+        assert_eq!(field(&exported, Column::Weight), "974");
+        assert_eq!(field(&exported, Column::Lat), "52.79858");
+
+        // Normalized values are stable, so exporting again changes nothing
+        let reloaded = Doves::from_bytes_fast(exported.as_bytes()).unwrap();
+        assert_eq!(export(&reloaded), exported);
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    convert::Infallible,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// A single piece of extra information about a [`Ring`](crate::Ring), as listed in the `ExtraInfo`
+/// column of Dove's Guide.
+///
+/// Dove's isn't consistent with capitalisation, so known values are matched case-insensitively.
+/// Anything else is kept verbatim in [`RingFeature::Other`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum RingFeature {
+    /* RINGING */
+    Anticlockwise,
+    RopesFallInStraightLine,
+    NoStaysOrSliders,
+    EllacombeApparatus,
+    HungForDeadRopeRinging,
+    NotWellInTune,
+    TunedToOldConcertPitch,
+    SteelBells,
+
+    /* ACCESS AND STATUS */
+    Unringable,
+    Derelict,
+    TowerUnsafe,
+    CrackedBell,
+    BellsNotHung,
+    NoRegularRinging,
+    RingingNotPermitted,
+    RingingProhibited,
+    RingingSuspended,
+    LimitedRinging,
+    RingingRestricted,
+    NoVisitingBands,
+    ChurchClosed,
+
+    /* BUILDING */
+    DetachedTower,
+    RoundTower,
+    RungFromChancel,
+    RungFromChancelCrossing,
+    RungFromPorch,
+    NoMainsElectricity,
+    RingingCentre,
+
+    /// Any value not covered by the other variants, stored exactly as it appears in Dove's.
+    Other(String),
+}
+
+/// The `RingFeature`s which have their own variants, along with their (lower-case) values in
+/// Dove's Guide.  The first letter is capitalised when displaying.
+static NAMES: [(RingFeature, &str); 28] = [
+    (RingFeature::Anticlockwise, "anticlockwise"),
+    (
+        RingFeature::RopesFallInStraightLine,
+        "ropes fall in a straight line",
+    ),
+    (RingFeature::NoStaysOrSliders, "no stays or sliders"),
+    (RingFeature::EllacombeApparatus, "ellacombe apparatus"),
+    (
+        RingFeature::HungForDeadRopeRinging,
+        "hung for dead-rope ringing",
+    ),
+    (RingFeature::NotWellInTune, "not well in tune"),
+    (
+        RingFeature::TunedToOldConcertPitch,
+        "tuned to old concert pitch (a=454hz)",
+    ),
+    (RingFeature::SteelBells, "steel bells"),
+    (RingFeature::Unringable, "unringable"),
+    (RingFeature::Derelict, "derelict"),
+    (RingFeature::TowerUnsafe, "tower unsafe"),
+    (
+        RingFeature::CrackedBell,
+        "one bell (or more) cracked or broken",
+    ),
+    (RingFeature::BellsNotHung, "one or more bells not hung"),
+    (RingFeature::NoRegularRinging, "no regular ringing"),
+    (
+        RingFeature::RingingNotPermitted,
+        "ringing currently not permitted",
+    ),
+    (RingFeature::RingingProhibited, "ringing prohibited"),
+    (
+        RingFeature::RingingSuspended,
+        "ringing temporarily suspended",
+    ),
+    (RingFeature::LimitedRinging, "only limited ringing allowed"),
+    (
+        RingFeature::RingingRestricted,
+        "ringing restricted (invitees only)",
+    ),
+    (RingFeature::NoVisitingBands, "no visiting bands at present"),
+    (RingFeature::ChurchClosed, "church closed"),
+    (RingFeature::DetachedTower, "detached tower"),
+    (RingFeature::RoundTower, "round tower"),
+    (RingFeature::RungFromChancel, "rung from chancel"),
+    (
+        RingFeature::RungFromChancelCrossing,
+        "rung from chancel crossing",
+    ),
+    (RingFeature::RungFromPorch, "rung from porch"),
+    (RingFeature::NoMainsElectricity, "no mains electricity"),
+    (RingFeature::RingingCentre, "cc recognised ringing centre"),
+];
+
+impl RingFeature {
+    /// Parses a `;`-delimited list of `RingFeature`s, ignoring any empty entries
+    pub(crate) fn parse_list(s: &str) -> Vec<Self> {
+        s.split(';')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse().unwrap())
+            .collect()
+    }
+}
+
+impl Display for RingFeature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            RingFeature::Other(s) => return write!(f, "{}", s),
+            RingFeature::TunedToOldConcertPitch => "Tuned to old concert pitch (A=454Hz)",
+            RingFeature::RingingCentre => "CC recognised Ringing Centre",
+            feature => {
+                NAMES
+                    .iter()
+                    .find(|(f2, _)| f2 == feature)
+                    .expect("Every non-`Other` feature should be in `NAMES`")
+                    .1
+            }
+        };
+        // Capitalise the first letter, as Dove's usually does
+        let mut chars = name.chars();
+        if let Some(c) = chars.next() {
+            write!(f, "{}{}", c.to_ascii_uppercase(), chars.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RingFeature {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let known = NAMES
+            .iter()
+            .find(|(_, name)| s.trim().eq_ignore_ascii_case(name));
+        Ok(match known {
+            Some((feature, _)) => feature.clone(),
+            None => RingFeature::Other(s.to_owned()),
+        })
+    }
+}
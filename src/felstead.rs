@@ -0,0 +1,79 @@
+//! Cross-referencing [`Ring`]s with the [Felstead database](https://felstead.cccbr.org.uk) of
+//! peals.
+
+use std::collections::HashMap;
+#[cfg(feature = "serde")]
+use std::{fs::File, io::Read, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+use crate::{Doves, Ring, TowerId};
+
+/// A table mapping [`TowerId`]s to the IDs of the same towers in the Felstead database.  This is
+/// loaded from a CSV file with the headers `TowerID` and `FelsteadID`, and applied to a [`Doves`]
+/// with [`Doves::set_felstead_ids`].
+#[derive(Debug, Clone, Default)]
+pub struct FelsteadTable {
+    ids: HashMap<TowerId, u32>,
+}
+
+/// A row of a Felstead cross-reference CSV file
+#[cfg(feature = "serde")]
+#[derive(Debug, Deserialize)]
+struct FelsteadRecord {
+    #[serde(rename = "TowerID")]
+    tower_id: TowerId,
+    #[serde(rename = "FelsteadID")]
+    felstead_id: u32,
+}
+
+impl FelsteadTable {
+    /// Reads a `FelsteadTable` from the contents of a CSV file
+    #[cfg(feature = "serde")]
+    pub fn from_reader(reader: impl Read) -> Result<Self, csv::Error> {
+        let ids = csv::Reader::from_reader(reader)
+            .deserialize()
+            .map(|record| {
+                let record: FelsteadRecord = record?;
+                Ok((record.tower_id, record.felstead_id))
+            })
+            .collect::<Result<_, csv::Error>>()?;
+        Ok(Self { ids })
+    }
+
+    /// Reads a `FelsteadTable` from a CSV file
+    #[cfg(feature = "serde")]
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, csv::Error> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Gets the Felstead ID of the tower with a given [`TowerId`]
+    pub fn get(&self, id: TowerId) -> Option<u32> {
+        self.ids.get(&id).copied()
+    }
+
+    /// Adds (or replaces) the Felstead ID for a tower
+    pub fn insert(&mut self, id: TowerId, felstead_id: u32) {
+        self.ids.insert(id, felstead_id);
+    }
+}
+
+impl Doves {
+    /// Sets the [`Ring::felstead_id`] of every [`Ring`] using a [`FelsteadTable`].  `Ring`s
+    /// whose towers aren't in the table have their Felstead ID cleared.
+    pub fn set_felstead_ids(&mut self, table: &FelsteadTable) {
+        for ring in self.iter_mut() {
+            ring.felstead_id = table.get(ring.id);
+        }
+    }
+}
+
+impl Ring {
+    /// A link to this tower's page in the Felstead database, or `None` if this `Ring` has no
+    /// [Felstead ID](Ring::felstead_id)
+    pub fn felstead_url(&self) -> Option<String> {
+        let id = self.felstead_id?;
+        Some(format!("https://felstead.cccbr.org.uk/tbid.php?tid={}", id))
+    }
+}
@@ -0,0 +1,377 @@
+//! A C ABI (enabled by the `ffi` feature), so that apps written in other languages can embed this
+//! parser rather than shipping their own.  The declarations are in `include/doves_guide.h`, and
+//! a shared library can be built with:
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! ```
+//!
+//! A loaded [`Doves`] is an opaque pointer which must be freed with `doves_free`.  [`Ring`]s are
+//! borrowed from their `Doves`, so are only valid until it's freed.  None of these functions
+//! unwind into C: a panic aborts the process instead.
+
+use std::{
+    ffi::{c_char, c_void, CStr},
+    ptr,
+};
+
+use crate::{Column, Coordinates, Doves, Query, Ring, TowerId, Weight};
+
+/// The constraints on the [`Ring`]s visited by `doves_for_each` and `doves_nearest`.  Zeroed
+/// fields (and null pointers) don't constrain anything.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct DovesFilter {
+    /// The minimum number of bells, or 0 for no minimum
+    pub min_bells: u8,
+    /// The maximum number of bells, or 0 for no maximum
+    pub max_bells: u8,
+    /// Only match `Ring`s which can currently be rung
+    pub ringable_only: bool,
+    /// Only match `Ring`s which are rung from the ground floor
+    pub ground_floor: bool,
+    /// Only match `Ring`s in churches with a toilet
+    pub toilet: bool,
+    /// Only match `Ring`s with a simulator
+    pub simulator: bool,
+    /// The minimum weight of the tenor in pounds, or 0 for no minimum
+    pub min_weight_lbs: f64,
+    /// A NUL-terminated county name (normalized as in `Query::county`), or null for any county
+    pub county: *const c_char,
+}
+
+/// A function called with each [`Ring`] visited by `doves_for_each`, along with the `user_data`
+/// passed to it.  Returning `false` stops the iteration.  This is `None` if C passes a null
+/// pointer.
+pub type DovesCallback = Option<extern "C" fn(ring: *const Ring, user_data: *mut c_void) -> bool>;
+
+/// Loads Dove's `dove.csv` file from a NUL-terminated UTF-8 path, returning null if it can't be
+/// read or parsed.
+///
+/// # Safety
+///
+/// `path` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn doves_load_path(path: *const c_char) -> *mut Doves {
+    if path.is_null() {
+        return ptr::null_mut();
+    }
+    match CStr::from_ptr(path).to_str() {
+        Ok(path) => into_raw(Doves::from_path_fast(path).ok()),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Loads the contents of Dove's `dove.csv` file, returning null if it can't be parsed.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn doves_load_bytes(data: *const u8, len: usize) -> *mut Doves {
+    if data.is_null() {
+        return ptr::null_mut();
+    }
+    into_raw(Doves::from_bytes_fast(std::slice::from_raw_parts(data, len)).ok())
+}
+
+/// Frees a `Doves` returned by `doves_load_path` or `doves_load_bytes`.  Does nothing if
+/// `doves` is null.
+///
+/// # Safety
+///
+/// `doves` must have been returned by one of the loading functions, and not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn doves_free(doves: *mut Doves) {
+    if !doves.is_null() {
+        drop(Box::from_raw(doves));
+    }
+}
+
+/// The number of [`Ring`]s in a `Doves`
+///
+/// # Safety
+///
+/// `doves` must be a live pointer returned by one of the loading functions.
+#[no_mangle]
+pub unsafe extern "C" fn doves_len(doves: *const Doves) -> usize {
+    (*doves).len()
+}
+
+/// The [`Ring`] at index `idx` (in the order of Dove's Guide), or null if `idx` is out of range
+///
+/// # Safety
+///
+/// `doves` must be a live pointer returned by one of the loading functions.
+#[no_mangle]
+pub unsafe extern "C" fn doves_ring_at(doves: *const Doves, idx: usize) -> *const Ring {
+    opt_ptr((*doves).towers().get(idx))
+}
+
+/// The [`Ring`] with a given tower ID (following any redirects, as in [`Doves::get`]), or null
+/// if there isn't one
+///
+/// # Safety
+///
+/// `doves` must be a live pointer returned by one of the loading functions.
+#[no_mangle]
+pub unsafe extern "C" fn doves_get(doves: *const Doves, id: u32) -> *const Ring {
+    opt_ptr((*doves).get(TowerId::new(id)))
+}
+
+/// Writes the (up to) `n` [`Ring`]s nearest to a point which pass `filter` into `out`, nearest
+/// first, returning how many were written.  `filter` can be null to match every `Ring`.
+/// Returns 0 if the coordinates are invalid.
+///
+/// # Safety
+///
+/// `doves` must be a live pointer returned by one of the loading functions, `filter` must be null
+/// or point to a valid [`DovesFilter`], and `out` must have space for `n` pointers.
+#[no_mangle]
+pub unsafe extern "C" fn doves_nearest(
+    doves: *const Doves,
+    lat: f64,
+    long: f64,
+    n: usize,
+    filter: *const DovesFilter,
+    out: *mut *const Ring,
+) -> usize {
+    let doves = &*doves;
+    let coords = match Coordinates::new(lat, long) {
+        Ok(coords) => coords,
+        Err(_) => return 0,
+    };
+    let nearest = doves.nearest(coords, n, query(doves, filter));
+    for (i, nearby) in nearest.iter().enumerate() {
+        *out.add(i) = nearby.ring;
+    }
+    nearest.len()
+}
+
+/// Calls `callback` with every [`Ring`] which passes `filter` (or every `Ring`, if `filter` is
+/// null), in the order of Dove's Guide, until it returns `false`.  Returns the number of `Ring`s
+/// passed to `callback`, which is 0 if `callback` is null.
+///
+/// # Safety
+///
+/// `doves` must be a live pointer returned by one of the loading functions, and `filter` must be
+/// null or point to a valid [`DovesFilter`].
+#[no_mangle]
+pub unsafe extern "C" fn doves_for_each(
+    doves: *const Doves,
+    filter: *const DovesFilter,
+    callback: DovesCallback,
+    user_data: *mut c_void,
+) -> usize {
+    let callback = match callback {
+        Some(callback) => callback,
+        None => return 0,
+    };
+    let doves = &*doves;
+    let mut visited = 0;
+    for ring in query(doves, filter) {
+        visited += 1;
+        if !callback(ring, user_data) {
+            break;
+        }
+    }
+    visited
+}
+
+/// The tower ID of a [`Ring`]
+///
+/// # Safety
+///
+/// `ring` must be a `Ring` borrowed from a live `Doves`.
+#[no_mangle]
+pub unsafe extern "C" fn doves_ring_id(ring: *const Ring) -> u32 {
+    (*ring).id.get()
+}
+
+/// The number of bells in a [`Ring`]
+///
+/// # Safety
+///
+/// `ring` must be a `Ring` borrowed from a live `Doves`.
+#[no_mangle]
+pub unsafe extern "C" fn doves_ring_bells(ring: *const Ring) -> u8 {
+    (*ring).bells
+}
+
+/// The weight of a [`Ring`]'s tenor in pounds, or NaN if it isn't known
+///
+/// # Safety
+///
+/// `ring` must be a `Ring` borrowed from a live `Doves`.
+#[no_mangle]
+pub unsafe extern "C" fn doves_ring_weight_lbs(ring: *const Ring) -> f64 {
+    (*ring).weight.map_or(f64::NAN, Weight::lbs)
+}
+
+/// Writes a [`Ring`]'s best known coordinates into `lat` and `long`, returning `false` (and
+/// leaving them unchanged) if it has none
+///
+/// # Safety
+///
+/// `ring` must be a `Ring` borrowed from a live `Doves`, and `lat` and `long` must be valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn doves_ring_coordinates(
+    ring: *const Ring,
+    lat: *mut f64,
+    long: *mut f64,
+) -> bool {
+    match (*ring).best_coordinates() {
+        Some((coords, _)) => {
+            *lat = coords.lat();
+            *long = coords.long();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Writes the value of one of a [`Ring`]'s fields, named by its header in Dove's CSV file (e.g.
+/// `"Place"` or `"Dedicn"`), into `buf` as a NUL-terminated UTF-8 string.  Like `snprintf`, this
+/// returns the length of the whole value (excluding the NUL), and truncates it if `buf` is too
+/// small.  Truncation never splits a UTF-8 character, so `buf` is always valid UTF-8.  Returns -1
+/// if the column doesn't exist.
+///
+/// # Safety
+///
+/// `ring` must be a `Ring` borrowed from a live `Doves`, `column` must be a valid NUL-terminated
+/// string, and `buf` must be null or have space for `buf_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn doves_ring_field(
+    ring: *const Ring,
+    column: *const c_char,
+    buf: *mut c_char,
+    buf_len: usize,
+) -> isize {
+    let column = match CStr::from_ptr(column).to_str().map(str::parse::<Column>) {
+        Ok(Ok(column)) => column,
+        _ => return -1,
+    };
+    let value = column.value(&*ring);
+    if !buf.is_null() && buf_len > 0 {
+        let mut len = value.len().min(buf_len - 1);
+        while !value.is_char_boundary(len) {
+            len -= 1;
+        }
+        ptr::copy_nonoverlapping(value.as_ptr(), buf.cast::<u8>(), len);
+        *buf.add(len) = 0;
+    }
+    value.len() as isize
+}
+
+/// Converts a [`DovesFilter`] into the equivalent [`Query`]
+unsafe fn query<'d>(doves: &'d Doves, filter: *const DovesFilter) -> Query<'d> {
+    let mut query = doves.query();
+    let filter = match filter.as_ref() {
+        Some(filter) => filter,
+        None => return query,
+    };
+    if filter.min_bells > 0 {
+        query = query.min_bells(filter.min_bells);
+    }
+    if filter.max_bells > 0 {
+        query = query.max_bells(filter.max_bells);
+    }
+    if filter.ringable_only {
+        query = query.ringable_only();
+    }
+    if filter.ground_floor {
+        query = query.ground_floor();
+    }
+    if filter.toilet {
+        query = query.toilet();
+    }
+    if filter.simulator {
+        query = query.simulator();
+    }
+    if filter.min_weight_lbs > 0.0 {
+        query = query.min_weight(Weight::from_lbs(filter.min_weight_lbs));
+    }
+    if !filter.county.is_null() {
+        query = query.county(CStr::from_ptr(filter.county).to_string_lossy().as_ref());
+    }
+    query
+}
+
+fn into_raw(doves: Option<Doves>) -> *mut Doves {
+    doves.map_or(ptr::null_mut(), |doves| Box::into_raw(Box::new(doves)))
+}
+
+fn opt_ptr(ring: Option<&Ring>) -> *const Ring {
+    ring.map_or(ptr::null(), |ring| ring as *const Ring)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        ffi::{c_char, c_void, CStr},
+        ptr,
+    };
+
+    use super::{doves_for_each, doves_ring_field};
+    use crate::{Doves, Ring, RingBuilder, TowerId};
+
+    fn doves() -> Doves {
+        let ring = RingBuilder::new(TowerId::new(1), "Sainte-Mère-Église", 6)
+            .build()
+            .unwrap();
+        Doves::from(vec![ring])
+    }
+
+    /// Calls `doves_ring_field` for the `Place` of `ring` with a buffer of `buf_len` bytes
+    fn place(ring: &Ring, buf_len: usize) -> (isize, String) {
+        let mut buf = vec![b'x' as c_char; buf_len.max(1)];
+        let len = unsafe {
+            doves_ring_field(ring, b"Place\0".as_ptr().cast(), buf.as_mut_ptr(), buf_len)
+        };
+        let value = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        (len, value.to_str().unwrap().to_owned())
+    }
+
+    #[test]
+    fn field_truncates_at_char_boundary() {
+        let doves = doves();
+        let full = "Sainte-Mère-Église";
+        assert_eq!(place(&doves[0], 64), (full.len() as isize, full.to_owned()));
+        // "Sainte-M" is 8 bytes and `è` is 2, so 9 bytes of space (plus the NUL) can't fit it
+        assert_eq!(
+            place(&doves[0], 10),
+            (full.len() as isize, "Sainte-M".to_owned())
+        );
+        assert_eq!(
+            place(&doves[0], 11),
+            (full.len() as isize, "Sainte-Mè".to_owned())
+        );
+        assert_eq!(place(&doves[0], 1), (full.len() as isize, String::new()));
+    }
+
+    #[test]
+    fn unknown_field() {
+        let doves = doves();
+        let len = unsafe {
+            doves_ring_field(&doves[0], b"Nonsense\0".as_ptr().cast(), ptr::null_mut(), 0)
+        };
+        assert_eq!(len, -1);
+    }
+
+    #[test]
+    fn null_callback() {
+        let doves = doves();
+        let visited = unsafe { doves_for_each(&doves, ptr::null(), None, ptr::null_mut()) };
+        assert_eq!(visited, 0);
+
+        extern "C" fn count(_ring: *const Ring, user_data: *mut c_void) -> bool {
+            unsafe { *user_data.cast::<usize>() += 1 };
+            true
+        }
+        let mut count_seen = 0usize;
+        let user_data = (&mut count_seen as *mut usize).cast::<c_void>();
+        let visited = unsafe { doves_for_each(&doves, ptr::null(), Some(count), user_data) };
+        assert_eq!((visited, count_seen), (1, 1));
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Doves, Query, Ring};
+
+/// A predicate on [`Ring`]s, which can be combined with other `RingFilter`s using
+/// [`and`](RingFilter::and), [`or`](RingFilter::or) and [`not`](RingFilter::not).
+///
+/// This is implemented for every `Fn(&Ring) -> bool` closure, and for [`Query`].  Applications can
+/// also implement it on their own types to make reusable filter presets; if those types implement
+/// [`Serialize`] and [`Deserialize`], then so will any combination of them.
+pub trait RingFilter {
+    /// `true` if `ring` passes this filter
+    fn matches(&self, ring: &Ring) -> bool;
+
+    /// Combines two `RingFilter`s, matching [`Ring`]s which pass both
+    fn and<F: RingFilter>(self, other: F) -> And<Self, F>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Combines two `RingFilter`s, matching [`Ring`]s which pass either
+    fn or<F: RingFilter>(self, other: F) -> Or<Self, F>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+
+    /// Inverts this `RingFilter`, matching exactly the [`Ring`]s which it doesn't
+    fn not(self) -> Not<Self>
+    where
+        Self: Sized,
+    {
+        Not(self)
+    }
+}
+
+impl<F: Fn(&Ring) -> bool> RingFilter for F {
+    fn matches(&self, ring: &Ring) -> bool {
+        self(ring)
+    }
+}
+
+impl RingFilter for Query<'_> {
+    fn matches(&self, ring: &Ring) -> bool {
+        Query::matches(self, ring)
+    }
+}
+
+/// A [`RingFilter`] which matches [`Ring`]s passing both of its filters (see [`RingFilter::and`])
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct And<A, B>(pub A, pub B);
+
+/// A [`RingFilter`] which matches [`Ring`]s passing either of its filters (see
+/// [`RingFilter::or`])
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Or<A, B>(pub A, pub B);
+
+/// A [`RingFilter`] which matches [`Ring`]s failing its filter (see [`RingFilter::not`])
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Not<A>(pub A);
+
+impl<A: RingFilter, B: RingFilter> RingFilter for And<A, B> {
+    fn matches(&self, ring: &Ring) -> bool {
+        self.0.matches(ring) && self.1.matches(ring)
+    }
+}
+
+impl<A: RingFilter, B: RingFilter> RingFilter for Or<A, B> {
+    fn matches(&self, ring: &Ring) -> bool {
+        self.0.matches(ring) || self.1.matches(ring)
+    }
+}
+
+impl<A: RingFilter> RingFilter for Not<A> {
+    fn matches(&self, ring: &Ring) -> bool {
+        !self.0.matches(ring)
+    }
+}
+
+impl Doves {
+    /// Returns an iterator over every [`Ring`] which passes a [`RingFilter`], in the order they
+    /// appear in Dove's Guide
+    pub fn filter<F: RingFilter>(&self, filter: F) -> impl Iterator<Item = &Ring> {
+        self.iter().filter(move |ring| filter.matches(ring))
+    }
+}
@@ -0,0 +1,56 @@
+//! The yes/no facts about a [`Ring`](crate::Ring), packed into a single byte.
+
+use std::ops::{BitOr, BitOrAssign};
+
+/// A set of yes/no facts about a [`Ring`](crate::Ring), stored as one bit each.  These are usually
+/// read through the `Ring`'s methods (e.g. [`Ring::ground_floor`](crate::Ring::ground_floor)).
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct RingFlags(u8);
+
+impl RingFlags {
+    /// The `Ring` is rung from the ground floor.
+    ///
+    /// **CSV Header**: `GF`; `""` if unset, `"GF"` if set
+    pub const GROUND_FLOOR: Self = Self(1 << 0);
+    /// The church has toilet facilities.
+    ///
+    /// **CSV Header**: `Toilet`; `""` if unset, `"T"` if set
+    pub const TOILET: Self = Self(1 << 1);
+    /// The bells can be rung silently using a simulator.
+    ///
+    /// **CSV Header**: `Simulator`; `""` if unset, `"T"` if set
+    pub const SIMULATOR: Self = Self(1 << 2);
+
+    /// The empty set of flags
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    /// `true` if every flag in `other` is also set in `self`
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets or clears every flag in `other`
+    pub fn set(&mut self, other: Self, value: bool) {
+        if value {
+            self.0 |= other.0;
+        } else {
+            self.0 &= !other.0;
+        }
+    }
+}
+
+impl BitOr for RingFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for RingFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
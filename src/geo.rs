@@ -0,0 +1,108 @@
+//! Conversions to and from the types of the [`geo_types`] crate (used by `geo` and most of the
+//! Rust geospatial ecosystem).  `geo_types` uses `x` for longitude and `y` for latitude.
+
+use std::convert::TryFrom;
+
+use geo_types::{coord, LineString, Point, Rect};
+
+use crate::{
+    Bounds, Coordinates, Distance, Doves, InvalidCoordinates, Nearby, Ring, RingFilter, RouteMatch,
+};
+
+impl From<Coordinates> for Point<f64> {
+    fn from(coords: Coordinates) -> Self {
+        Point::new(coords.long(), coords.lat())
+    }
+}
+
+impl TryFrom<Point<f64>> for Coordinates {
+    type Error = InvalidCoordinates;
+
+    fn try_from(point: Point<f64>) -> Result<Self, Self::Error> {
+        Coordinates::new(point.y(), point.x())
+    }
+}
+
+impl TryFrom<Rect<f64>> for Bounds {
+    type Error = InvalidCoordinates;
+
+    fn try_from(rect: Rect<f64>) -> Result<Self, Self::Error> {
+        Ok(Bounds::new(
+            Coordinates::try_from(Point::from(rect.min()))?,
+            Coordinates::try_from(Point::from(rect.max()))?,
+        ))
+    }
+}
+
+impl Bounds {
+    /// Converts these `Bounds` into [`Rect`]s.  This returns one `Rect`, unless the `Bounds`
+    /// cross the antimeridian, in which case they are split into one `Rect` on either side.
+    pub fn to_rects(self) -> Vec<Rect<f64>> {
+        let (min, max) = (self.min, self.max);
+        let rect = |min_long: f64, max_long: f64| {
+            Rect::new(
+                coord! { x: min_long, y: min.lat() },
+                coord! { x: max_long, y: max.lat() },
+            )
+        };
+        if min.long() <= max.long() {
+            vec![rect(min.long(), max.long())]
+        } else {
+            vec![rect(min.long(), 180.0), rect(-180.0, max.long())]
+        }
+    }
+}
+
+impl Ring {
+    /// The [best coordinates](Ring::best_coordinates) of this `Ring` as a [`Point`], or `None` if
+    /// it has no coordinates
+    pub fn point(&self) -> Option<Point<f64>> {
+        Some(Point::from(self.best_coordinates()?.0))
+    }
+}
+
+impl Doves {
+    /// Like [`Doves::nearest`], but taking a [`Point`].  Returns an error if the `Point` isn't a
+    /// valid longitude and latitude.
+    pub fn nearest_to_point(
+        &self,
+        point: Point<f64>,
+        n: usize,
+        filter: impl RingFilter,
+    ) -> Result<Vec<Nearby<'_>>, InvalidCoordinates> {
+        Ok(self.nearest(Coordinates::try_from(point)?, n, filter))
+    }
+
+    /// Like [`Doves::within_radius`], but taking a [`Point`].  Returns an error if the `Point`
+    /// isn't a valid longitude and latitude.
+    pub fn within_radius_of_point(
+        &self,
+        point: Point<f64>,
+        radius: Distance,
+        filter: impl RingFilter,
+    ) -> Result<Vec<Nearby<'_>>, InvalidCoordinates> {
+        Ok(self.within_radius(Coordinates::try_from(point)?, radius, filter))
+    }
+
+    /// Like [`Doves::in_bounds`], but taking a [`Rect`].  Returns an error if the corners of the
+    /// `Rect` aren't valid longitudes and latitudes.
+    pub fn in_rect(&self, rect: Rect<f64>) -> Result<Vec<&Ring>, InvalidCoordinates> {
+        let bounds = Bounds::try_from(rect)?;
+        Ok(self.in_bounds(bounds.min, bounds.max))
+    }
+
+    /// Like [`Doves::along_route`], but taking the route as a [`LineString`].  Returns an error if
+    /// any point on the route isn't a valid longitude and latitude.
+    pub fn along_line_string(
+        &self,
+        route: &LineString<f64>,
+        width: Distance,
+        filter: impl RingFilter,
+    ) -> Result<Vec<RouteMatch<'_>>, InvalidCoordinates> {
+        let route = route
+            .points()
+            .map(Coordinates::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(self.along_route(&route, width, filter))
+    }
+}
@@ -0,0 +1,39 @@
+//! Exporting rings as GeoJSON, for loading into GIS software and web mapping libraries.
+
+use crate::{
+    bundle::{json_string, DOVE_COORDINATE_DECIMALS},
+    Column, Doves, Ring, RingFilter,
+};
+
+impl Doves {
+    /// Renders every [`Ring`] which passes a [`RingFilter`] as a GeoJSON (RFC 7946)
+    /// `FeatureCollection`.  Each `Ring` is a `Point` at its tower's
+    /// [coordinates](Ring::coordinates), and `Ring`s without coordinates are left out.  The
+    /// properties of each feature are its non-empty [`Column`]s, keyed by their headers in Dove's
+    /// CSV file and encoded as in a [`JsonBundle`](crate::JsonBundle).
+    pub fn to_geojson(&self, filter: impl RingFilter) -> String {
+        let features = self.filter(filter).filter_map(feature).collect::<Vec<_>>();
+        format!(
+            r#"{{"type":"FeatureCollection","features":[{}]}}"#,
+            features.join(",")
+        )
+    }
+}
+
+/// The GeoJSON `Feature` for one [`Ring`], or `None` if it has no coordinates
+fn feature(ring: &Ring) -> Option<String> {
+    let coords = ring.coordinates?;
+    let properties = Column::all()
+        .filter_map(|column| {
+            let value = column.json_value(ring, DOVE_COORDINATE_DECIMALS)?;
+            Some(format!("{}:{}", json_string(column.dove_header()), value))
+        })
+        .collect::<Vec<_>>();
+    // GeoJSON positions are `[long, lat]`
+    Some(format!(
+        r#"{{"type":"Feature","geometry":{{"type":"Point","coordinates":[{},{}]}},"properties":{{{}}}}}"#,
+        coords.long(),
+        coords.lat(),
+        properties.join(",")
+    ))
+}
@@ -0,0 +1,56 @@
+//! Exporting rings as GPX waypoints, for loading into sat-navs and GPS apps.
+
+use std::fmt::Write;
+
+use crate::{report::escape_html, Doves, NameStyle, Ring, RingFilter};
+
+impl Doves {
+    /// Renders every [`Ring`] which passes a [`RingFilter`] as a GPX 1.1 file, with one waypoint
+    /// per `Ring`.  Waypoints are placed at each `Ring`'s
+    /// [best coordinates](Ring::best_coordinates) (so at the sat-nav coordinates where Dove's
+    /// gives them), and `Ring`s without coordinates are left out.  Each waypoint is named in
+    /// [Dove's style](NameStyle::Dove), described by the `Ring`'s bells and tenor, and links to the
+    /// `Ring`'s page on Dove's Guide.
+    pub fn to_gpx(&self, filter: impl RingFilter) -> String {
+        let mut gpx = String::new();
+        gpx.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        gpx.push('\n');
+        gpx.push_str(
+            r#"<gpx version="1.1" creator="doves_guide" xmlns="http://www.topografix.com/GPX/1/1">"#,
+        );
+        gpx.push('\n');
+        for ring in self.filter(filter) {
+            write_waypoint(&mut gpx, ring);
+        }
+        gpx.push_str("</gpx>\n");
+        gpx
+    }
+}
+
+/// Appends the `<wpt>` element for one [`Ring`], if it has coordinates
+fn write_waypoint(gpx: &mut String, ring: &Ring) {
+    let (coords, _) = match ring.best_coordinates() {
+        Some(coords) => coords,
+        None => return,
+    };
+    let mut desc = format!("{} bells", ring.bells);
+    if let Some(weight) = ring.weight {
+        write!(desc, ", {}", weight).unwrap();
+    }
+    if let Some(note) = ring.note {
+        write!(desc, " in {}", note).unwrap();
+    }
+    if !ring.is_ringable() {
+        desc.push_str(" (unringable)");
+    }
+    writeln!(
+        gpx,
+        r#"  <wpt lat="{}" lon="{}"><name>{}</name><desc>{}</desc><link href="{}"/></wpt>"#,
+        coords.lat(),
+        coords.long(),
+        escape_html(&ring.display_name(NameStyle::Dove)),
+        escape_html(&desc),
+        ring.doves_url()
+    )
+    .unwrap();
+}
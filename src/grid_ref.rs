@@ -0,0 +1,508 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{Coordinates, Distance, Ring};
+
+/// A national grid whose references can be written as a [`GridRef`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Grid {
+    /// The Ordnance Survey National Grid of Great Britain (and the Isle of Man), whose
+    /// references start with two letters (e.g. `SP513061`)
+    British,
+    /// The Irish Grid, used throughout the island of Ireland, whose references start with one
+    /// letter (e.g. `O159340`)
+    Irish,
+}
+
+/// A reference to a square on a national [`Grid`] (e.g. `SP513061`), as found in the `NG`
+/// column of Dove's Guide.  The square is identified by its south-west corner, and its size
+/// depends on the number of digits in the reference (6 digits gives a 100m square).
+///
+/// Channel Islands references in Dove's Guide are on the UTM grid (e.g. `WV653489`), so can't
+/// be parsed as a `GridRef`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct GridRef {
+    grid: Grid,
+    /// Metres east of the grid's false origin
+    easting: u32,
+    /// Metres north of the grid's false origin
+    northing: u32,
+    /// The side length of the square, in metres
+    precision: u32,
+}
+
+impl GridRef {
+    /// The [`Grid`] which this reference is on
+    pub fn grid(self) -> Grid {
+        self.grid
+    }
+
+    /// The easting of the square's south-west corner, in metres
+    pub fn easting(self) -> u32 {
+        self.easting
+    }
+
+    /// The northing of the square's south-west corner, in metres
+    pub fn northing(self) -> u32 {
+        self.northing
+    }
+
+    /// The side length of the square, in metres
+    pub fn precision(self) -> u32 {
+        self.precision
+    }
+
+    /// The WGS84 [`Coordinates`] of the centre of the square.  The conversion between datums uses
+    /// a Helmert transformation, so is only accurate to about 5m (which is much smaller than the
+    /// squares referenced by Dove's Guide).
+    pub fn to_coordinates(self) -> Coordinates {
+        let params = self.grid.params();
+        let half = f64::from(self.precision) / 2.0;
+        let (lat, long) = params.projection.inverse(
+            f64::from(self.easting) + half,
+            f64::from(self.northing) + half,
+        );
+        let (lat, long) = params
+            .helmert
+            .to_wgs84(params.projection.ellipsoid, lat, long);
+        Coordinates::new(lat.to_degrees(), long.to_degrees())
+            .expect("Grid squares should always have valid coordinates")
+    }
+}
+
+impl Ring {
+    /// The [`GridRef`] of this `Ring` (see [`Ring::os_grid_ref`]), or `None` if it doesn't have
+    /// one which can be parsed
+    pub fn grid_ref(&self) -> Option<GridRef> {
+        self.os_grid_ref.as_deref()?.parse().ok()
+    }
+
+    /// The [`Distance`] between this `Ring`'s [`coordinates`](Ring::coordinates) and the centre
+    /// of its [`GridRef`], or `None` if it doesn't have both.  Dove's Guide usually gives 6-digit
+    /// grid references, so even correct data can be up to about 70m apart.
+    pub fn grid_ref_distance(&self) -> Option<Distance> {
+        let coords = self.coordinates?;
+        Some(coords.distance_to(self.grid_ref()?.to_coordinates()))
+    }
+}
+
+/////////////
+// LETTERS //
+/////////////
+
+/// Grid squares are labelled with the letters `A` to `Z` (skipping `I`) in a 5x5 block, starting
+/// from the north-west corner.  Returns the letter's column and row (counting from the south).
+fn letter_position(letter: u8) -> Option<(u32, u32)> {
+    let idx = match letter {
+        b'A'..=b'H' => letter - b'A',
+        b'J'..=b'Z' => letter - b'A' - 1,
+        _ => return None,
+    };
+    Some((u32::from(idx % 5), 4 - u32::from(idx / 5)))
+}
+
+/// The inverse of [`letter_position`]
+fn position_letter(column: u32, row: u32) -> char {
+    let idx = (4 - row) * 5 + column;
+    let idx = if idx >= 8 { idx + 1 } else { idx };
+    char::from(b'A' + idx as u8)
+}
+
+/// The index (east and north) of the 500km square labelled `S` in the British grid, which
+/// contains the grid's false origin
+const BRITISH_ORIGIN_SQUARE: (u32, u32) = (2, 1);
+/// The number of 100km squares in each direction which make up the British grid
+const BRITISH_SQUARES: (u32, u32) = (7, 13);
+
+impl Grid {
+    /// Converts the letters at the start of a reference into the position of its 100km square,
+    /// as a number of squares east and north of the false origin
+    fn square_from_letters(self, letters: &[u8]) -> Option<(u32, u32)> {
+        match (self, letters) {
+            (Grid::Irish, [letter]) => letter_position(*letter),
+            (Grid::British, [major, minor]) => {
+                let (major_e, major_n) = letter_position(*major)?;
+                let (minor_e, minor_n) = letter_position(*minor)?;
+                let east = (major_e * 5 + minor_e).checked_sub(BRITISH_ORIGIN_SQUARE.0 * 5)?;
+                let north = (major_n * 5 + minor_n).checked_sub(BRITISH_ORIGIN_SQUARE.1 * 5)?;
+                (east < BRITISH_SQUARES.0 && north < BRITISH_SQUARES.1).then_some((east, north))
+            }
+            _ => None,
+        }
+    }
+
+    /// The inverse of [`Grid::square_from_letters`]
+    fn letters_from_square(self, east: u32, north: u32) -> String {
+        match self {
+            Grid::Irish => position_letter(east, north).to_string(),
+            Grid::British => {
+                let east = east + BRITISH_ORIGIN_SQUARE.0 * 5;
+                let north = north + BRITISH_ORIGIN_SQUARE.1 * 5;
+                let major = position_letter(east / 5, north / 5);
+                let minor = position_letter(east % 5, north % 5);
+                format!("{}{}", major, minor)
+            }
+        }
+    }
+}
+
+impl Display for GridRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let letters = self
+            .grid
+            .letters_from_square(self.easting / 100_000, self.northing / 100_000);
+        let digits = (100_000 / self.precision).to_string().len() - 1;
+        write!(
+            f,
+            "{}{:0width$}{:0width$}",
+            letters,
+            self.easting % 100_000 / self.precision,
+            self.northing % 100_000 / self.precision,
+            width = digits
+        )
+    }
+}
+
+impl FromStr for GridRef {
+    type Err = ParseGridRefError;
+
+    /// Parses a grid reference made of one (Irish) or two (British) letters followed by an even
+    /// number of digits (up to 10).  Spaces are ignored and letters can be in either case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseGridRefError(s.to_owned());
+        let chars = s
+            .bytes()
+            .filter(|b| !b.is_ascii_whitespace())
+            .map(|b| b.to_ascii_uppercase())
+            .collect::<Vec<u8>>();
+        let num_letters = chars.iter().take_while(|b| b.is_ascii_alphabetic()).count();
+        let (letters, digits) = chars.split_at(num_letters);
+        let grid = match num_letters {
+            1 => Grid::Irish,
+            2 => Grid::British,
+            _ => return Err(err()),
+        };
+        let (east_square, north_square) = grid.square_from_letters(letters).ok_or_else(err)?;
+
+        let half_len = digits.len() / 2;
+        if digits.len() % 2 != 0
+            || !(1..=5).contains(&half_len)
+            || !digits.iter().all(u8::is_ascii_digit)
+        {
+            return Err(err());
+        }
+        let parse = |digits: &[u8]| {
+            digits
+                .iter()
+                .fold(0, |acc, d| acc * 10 + u32::from(d - b'0'))
+        };
+        let precision = 10u32.pow(5 - half_len as u32);
+        Ok(Self {
+            grid,
+            easting: east_square * 100_000 + parse(&digits[..half_len]) * precision,
+            northing: north_square * 100_000 + parse(&digits[half_len..]) * precision,
+            precision,
+        })
+    }
+}
+
+/// The error generated when parsing an invalid [`GridRef`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseGridRefError(String);
+
+impl Display for ParseGridRefError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a British or Irish grid reference", self.0)
+    }
+}
+
+impl std::error::Error for ParseGridRefError {}
+
+////////////////
+// PROJECTION //
+////////////////
+
+/// An ellipsoid approximating the shape of the Earth, with axes in metres
+#[derive(Debug, Clone, Copy)]
+struct Ellipsoid {
+    a: f64,
+    b: f64,
+}
+
+/// The ellipsoid used by GPS, to which [`Coordinates`] refer
+const WGS84: Ellipsoid = Ellipsoid {
+    a: 6_378_137.0,
+    b: 6_356_752.314_245,
+};
+
+impl Ellipsoid {
+    /// The square of the eccentricity
+    fn e2(self) -> f64 {
+        1.0 - (self.b * self.b) / (self.a * self.a)
+    }
+
+    /// Converts a latitude and longitude (in radians, at zero height) to geocentric cartesian
+    /// coordinates
+    fn to_cartesian(self, lat: f64, long: f64) -> [f64; 3] {
+        let nu = self.a / (1.0 - self.e2() * lat.sin().powi(2)).sqrt();
+        [
+            nu * lat.cos() * long.cos(),
+            nu * lat.cos() * long.sin(),
+            (1.0 - self.e2()) * nu * lat.sin(),
+        ]
+    }
+
+    /// Converts geocentric cartesian coordinates to a latitude and longitude (in radians), ignoring
+    /// the height
+    fn lat_long(self, [x, y, z]: [f64; 3]) -> (f64, f64) {
+        let e2 = self.e2();
+        let p = x.hypot(y);
+        let mut lat = z.atan2(p * (1.0 - e2));
+        for _ in 0..10 {
+            let nu = self.a / (1.0 - e2 * lat.sin().powi(2)).sqrt();
+            lat = (z + e2 * nu * lat.sin()).atan2(p);
+        }
+        (lat, y.atan2(x))
+    }
+}
+
+/// A transverse Mercator projection, as used by the British and Irish grids
+#[derive(Debug, Clone, Copy)]
+struct Projection {
+    ellipsoid: Ellipsoid,
+    /// The scale factor on the central meridian
+    f0: f64,
+    /// The latitude and longitude of the true origin, in degrees
+    origin: (f64, f64),
+    /// The easting and northing of the true origin, in metres
+    false_origin: (f64, f64),
+}
+
+/// A seven-parameter transformation from a grid's datum to WGS84
+#[derive(Debug, Clone, Copy)]
+struct Helmert {
+    /// Translation, in metres
+    t: [f64; 3],
+    /// Scale change, in parts per million
+    s: f64,
+    /// Rotation, in arc-seconds
+    r: [f64; 3],
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GridParams {
+    projection: Projection,
+    helmert: Helmert,
+}
+
+impl Grid {
+    fn params(self) -> GridParams {
+        match self {
+            // OSGB36, on the Airy 1830 ellipsoid
+            Grid::British => GridParams {
+                projection: Projection {
+                    ellipsoid: Ellipsoid {
+                        a: 6_377_563.396,
+                        b: 6_356_256.909,
+                    },
+                    f0: 0.999_601_271_7,
+                    origin: (49.0, -2.0),
+                    false_origin: (400_000.0, -100_000.0),
+                },
+                helmert: Helmert {
+                    t: [446.448, -125.157, 542.060],
+                    s: -20.4894,
+                    r: [0.1502, 0.2470, 0.8421],
+                },
+            },
+            // Ireland 1965, on the modified Airy ellipsoid
+            Grid::Irish => GridParams {
+                projection: Projection {
+                    ellipsoid: Ellipsoid {
+                        a: 6_377_340.189,
+                        b: 6_356_034.447,
+                    },
+                    f0: 1.000_035,
+                    origin: (53.5, -8.0),
+                    false_origin: (200_000.0, 250_000.0),
+                },
+                helmert: Helmert {
+                    t: [482.530, -130.596, 564.557],
+                    s: 8.150,
+                    r: [-1.042, -0.214, -0.631],
+                },
+            },
+        }
+    }
+}
+
+impl Projection {
+    /// The meridional arc from the true origin to latitude `lat` (in radians), scaled by `f0`
+    fn meridional_arc(self, lat: f64) -> f64 {
+        let Ellipsoid { a, b } = self.ellipsoid;
+        let n = (a - b) / (a + b);
+        let (n2, n3) = (n * n, n * n * n);
+        let lat0 = self.origin.0.to_radians();
+        let (d, s) = (lat - lat0, lat + lat0);
+        b * self.f0
+            * ((1.0 + n + 1.25 * n2 + 1.25 * n3) * d
+                - (3.0 * n + 3.0 * n2 + 2.625 * n3) * d.sin() * s.cos()
+                + (1.875 * n2 + 1.875 * n3) * (2.0 * d).sin() * (2.0 * s).cos()
+                - (35.0 / 24.0) * n3 * (3.0 * d).sin() * (3.0 * s).cos())
+    }
+
+    /// Converts an easting and northing (in metres) into a latitude and longitude (in radians) on
+    /// this projection's ellipsoid, using the formulae from the Ordnance Survey's _A Guide to
+    /// Coordinate Systems in Great Britain_
+    fn inverse(self, easting: f64, northing: f64) -> (f64, f64) {
+        let (a, e2, f0) = (self.ellipsoid.a, self.ellipsoid.e2(), self.f0);
+        let (e0, n0) = self.false_origin;
+        let lat0 = self.origin.0.to_radians();
+        let long0 = self.origin.1.to_radians();
+
+        // Find the latitude at which the meridional arc matches the northing
+        let mut lat = lat0;
+        let mut m = 0.0;
+        loop {
+            lat += (northing - n0 - m) / (a * f0);
+            m = self.meridional_arc(lat);
+            if (northing - n0 - m).abs() < 0.00001 {
+                break;
+            }
+        }
+
+        let sin2 = lat.sin().powi(2);
+        let nu = a * f0 / (1.0 - e2 * sin2).sqrt();
+        let rho = a * f0 * (1.0 - e2) / (1.0 - e2 * sin2).powf(1.5);
+        let eta2 = nu / rho - 1.0;
+        let (tan, sec) = (lat.tan(), 1.0 / lat.cos());
+        let (tan2, tan4, tan6) = (tan.powi(2), tan.powi(4), tan.powi(6));
+
+        let vii = tan / (2.0 * rho * nu);
+        let viii = tan / (24.0 * rho * nu.powi(3)) * (5.0 + 3.0 * tan2 + eta2 - 9.0 * tan2 * eta2);
+        let ix = tan / (720.0 * rho * nu.powi(5)) * (61.0 + 90.0 * tan2 + 45.0 * tan4);
+        let x = sec / nu;
+        let xi = sec / (6.0 * nu.powi(3)) * (nu / rho + 2.0 * tan2);
+        let xii = sec / (120.0 * nu.powi(5)) * (5.0 + 28.0 * tan2 + 24.0 * tan4);
+        let xiia =
+            sec / (5040.0 * nu.powi(7)) * (61.0 + 662.0 * tan2 + 1320.0 * tan4 + 720.0 * tan6);
+
+        let de = easting - e0;
+        (
+            lat - vii * de.powi(2) + viii * de.powi(4) - ix * de.powi(6),
+            long0 + x * de - xi * de.powi(3) + xii * de.powi(5) - xiia * de.powi(7),
+        )
+    }
+}
+
+impl Helmert {
+    /// Converts a latitude and longitude (in radians) on `ellipsoid` into WGS84
+    fn to_wgs84(self, ellipsoid: Ellipsoid, lat: f64, long: f64) -> (f64, f64) {
+        let [x, y, z] = ellipsoid.to_cartesian(lat, long);
+        let [tx, ty, tz] = self.t;
+        let [rx, ry, rz] = self.r.map(|r| (r / 3600.0).to_radians());
+        let s = 1.0 + self.s / 1e6;
+        WGS84.lat_long([
+            tx + s * x - rz * y + ry * z,
+            ty + rz * x + s * y - rx * z,
+            tz - ry * x + rx * y + s * z,
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, GridRef};
+    use crate::{Coordinates, Distance};
+
+    fn grid_ref(s: &str) -> GridRef {
+        s.parse().unwrap()
+    }
+
+    /// Checks that the centre of a grid square is within `tolerance_m` of some WGS84 coordinates
+    fn assert_near(s: &str, lat: f64, long: f64, tolerance_m: f64) {
+        let expected = Coordinates::new(lat, long).unwrap();
+        let distance = grid_ref(s).to_coordinates().distance_to(expected);
+        assert!(
+            distance <= Distance::from_km(tolerance_m / 1000.0),
+            "{} is {} from ({}, {})",
+            s,
+            distance,
+            lat,
+            long
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let sp = grid_ref("SP513061");
+        assert_eq!(sp.grid(), Grid::British);
+        assert_eq!(
+            (sp.easting(), sp.northing(), sp.precision()),
+            (451_300, 206_100, 100)
+        );
+        assert_eq!(grid_ref("sp 51300 06100"), grid_ref("SP5130006100"));
+        assert_eq!(grid_ref("SP5130006100").precision(), 1);
+        assert_eq!(grid_ref("TQ38").precision(), 10_000);
+
+        let o = grid_ref("O159340");
+        assert_eq!(o.grid(), Grid::Irish);
+        assert_eq!(
+            (o.easting(), o.northing(), o.precision()),
+            (315_900, 234_000, 100)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        for s in [
+            "",
+            "SP",
+            "SP51306",
+            "SP51a061",
+            "SPQ513061",
+            "SI513061",
+            "WV653489",
+            "I123456",
+        ] {
+            assert!(s.parse::<GridRef>().is_err(), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in ["SP513061", "O159340", "NN1666671268", "TQ38"] {
+            assert_eq!(grid_ref(s).to_string(), s);
+        }
+    }
+
+    #[test]
+    fn os_worked_example() {
+        // The worked example of the inverse Transverse Mercator projection in the Ordnance
+        // Survey's "A guide to coordinate systems in Great Britain", which gives OSGB36
+        // coordinates of 52°39'27.2531"N, 1°43'4.5177"E
+        let params = Grid::British.params();
+        let (lat, long) = params.projection.inverse(651_409.903, 313_177.270);
+        let dms = |d: f64, m: f64, s: f64| d + m / 60.0 + s / 3600.0;
+        assert!((lat.to_degrees() - dms(52.0, 39.0, 27.2531)).abs() < 1e-6);
+        assert!((long.to_degrees() - dms(1.0, 43.0, 4.5177)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn british_to_wgs84() {
+        // The trig points on the summits of mountains, whose WGS84 coordinates are well known
+        assert_near("NN1666971271", 56.796_85, -5.003_57, 50.0); // Ben Nevis
+        assert_near("SH6098554375", 53.068_50, -4.076_19, 50.0); // Snowdon
+
+        // A 6-figure reference is only accurate to within its 100m square
+        assert_near("NN166712", 56.796_85, -5.003_57, 120.0);
+    }
+
+    #[test]
+    fn irish_to_wgs84() {
+        assert_near("V8036784424", 51.999_45, -9.742_87, 50.0); // Carrauntoohil
+        assert_near("V803844", 51.999_45, -9.742_87, 120.0);
+    }
+}
@@ -0,0 +1,52 @@
+//! Sharing one [`Doves`] between threads (e.g. the request handlers of a web server), and
+//! replacing it when a new version of Dove's Guide is released.
+
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::Doves;
+
+// `Doves` (including its lazily built indices) must be shareable between threads
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Doves>();
+};
+
+/// A cheaply cloneable handle to a shared [`Doves`], which can be atomically replaced.
+///
+/// Queries take a snapshot with [`DovesHandle::load`], which stays valid (and unchanged) for as
+/// long as it's held, even if a new `Doves` is [stored](DovesHandle::store) in the meantime.  A
+/// `Doves` builds its indices on first use, so call [`Doves::ensure_index`] before storing it to
+/// avoid the first queries paying for them.
+#[derive(Debug, Clone, Default)]
+pub struct DovesHandle {
+    current: Arc<RwLock<Arc<Doves>>>,
+}
+
+impl DovesHandle {
+    /// Creates a `DovesHandle` sharing `doves`
+    pub fn new(doves: Doves) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(doves))),
+        }
+    }
+
+    /// A snapshot of the current [`Doves`]
+    pub fn load(&self) -> Arc<Doves> {
+        // The lock is never held while running user code, so can't be poisoned
+        let current = self.current.read().unwrap_or_else(PoisonError::into_inner);
+        Arc::clone(&current)
+    }
+
+    /// Replaces the shared [`Doves`], returning the previous one.  Snapshots taken before the
+    /// swap keep using the previous `Doves`, which is dropped once they're all gone.
+    pub fn store(&self, doves: Doves) -> Arc<Doves> {
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        std::mem::replace(&mut current, Arc::new(doves))
+    }
+}
+
+impl From<Doves> for DovesHandle {
+    fn from(doves: Doves) -> Self {
+        Self::new(doves)
+    }
+}
@@ -0,0 +1,227 @@
+//! Tracking how towers change over a series of dated snapshots of Dove's Guide (e.g. when rings
+//! are augmented, tenors are reweighed, or ringing is suspended).
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::SystemTime,
+};
+
+use crate::{AccessStatus, Doves, Ring, RingKey, TowerId, Weight};
+
+/// The changes to every tower over a series of dated snapshots of Dove's Guide.  Snapshots are
+/// added in chronological order with [`History::add_snapshot`], and each one is compared to the
+/// one before it.  Only the latest snapshot is kept, so long histories can be built without
+/// keeping every snapshot in memory.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    /// The dates of the snapshots, in the order they were added
+    snapshots: Vec<SystemTime>,
+    /// The `Ring`s in the latest snapshot
+    latest: HashMap<RingKey, Ring>,
+    changes: BTreeMap<TowerId, Vec<Change>>,
+}
+
+/// A change to one [`Ring`] between two snapshots in a [`History`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// The date of the first snapshot containing this change
+    pub date: SystemTime,
+    /// The [`RingKey`] of the `Ring` which changed
+    pub ring: RingKey,
+    pub kind: ChangeKind,
+}
+
+/// What changed about a [`Ring`] (see [`Change`])
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    /// The `Ring` was added to Dove's Guide
+    Added,
+    /// The `Ring` was removed from Dove's Guide
+    Removed,
+    /// The number of bells increased
+    Augmented { from: u8, to: u8 },
+    /// The number of bells decreased
+    Reduced { from: u8, to: u8 },
+    /// The tenor's [`Weight`] changed, either because the bells were recast or rehung, or because
+    /// the weight was corrected
+    WeightChanged {
+        from: Option<Weight>,
+        to: Option<Weight>,
+    },
+    /// The `Ring`'s [`AccessStatus`] changed (e.g. it became unringable)
+    AccessChanged {
+        from: AccessStatus,
+        to: AccessStatus,
+    },
+}
+
+impl History {
+    /// Creates a `History` with no snapshots
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a snapshot of Dove's Guide, taken on `date` (e.g. its
+    /// [export time](crate::Metadata::exported)), recording every change since the previous
+    /// snapshot.  The first snapshot is the baseline, so its `Ring`s aren't reported as
+    /// [added](ChangeKind::Added).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `date` isn't later than the date of the previous snapshot.
+    pub fn add_snapshot(&mut self, date: SystemTime, doves: &Doves) {
+        if let Some(&previous) = self.snapshots.last() {
+            assert!(
+                date > previous,
+                "Snapshots must be added in chronological order"
+            );
+        }
+        let is_baseline = self.snapshots.is_empty();
+        self.snapshots.push(date);
+
+        let mut latest = HashMap::with_capacity(doves.len());
+        for ring in doves {
+            let key = ring.key();
+            match self.latest.remove(&key) {
+                Some(old) => self.compare(date, &old, ring),
+                None if !is_baseline => self.record(date, key, ChangeKind::Added),
+                None => {}
+            }
+            latest.insert(key, ring.clone());
+        }
+        // Any `Ring`s left over weren't in the new snapshot
+        let mut removed = self.latest.keys().copied().collect::<Vec<_>>();
+        removed.sort();
+        for key in removed {
+            self.record(date, key, ChangeKind::Removed);
+        }
+        self.latest = latest;
+    }
+
+    /// The dates of every snapshot, in chronological order
+    pub fn snapshots(&self) -> &[SystemTime] {
+        &self.snapshots
+    }
+
+    /// Every [`Change`] to the [`Ring`]s in a tower, in chronological order
+    pub fn tower(&self, id: TowerId) -> &[Change] {
+        self.changes.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every [`Change`] to one [`Ring`], in chronological order
+    pub fn ring(&self, key: RingKey) -> impl Iterator<Item = &Change> + '_ {
+        self.tower(key.tower_id)
+            .iter()
+            .filter(move |change| change.ring == key)
+    }
+
+    /// The [`TowerId`]s of every tower which has changed, along with their changes
+    pub fn iter(&self) -> impl Iterator<Item = (TowerId, &[Change])> + '_ {
+        self.changes
+            .iter()
+            .map(|(&id, changes)| (id, changes.as_slice()))
+    }
+
+    /// Records every difference between two versions of the same `Ring`
+    fn compare(&mut self, date: SystemTime, old: &Ring, new: &Ring) {
+        let key = new.key();
+        let (from, to) = (old.bells, new.bells);
+        if to > from {
+            self.record(date, key, ChangeKind::Augmented { from, to });
+        } else if to < from {
+            self.record(date, key, ChangeKind::Reduced { from, to });
+        }
+        if old.weight != new.weight {
+            let (from, to) = (old.weight, new.weight);
+            self.record(date, key, ChangeKind::WeightChanged { from, to });
+        }
+        if old.access != new.access {
+            let (from, to) = (old.access, new.access);
+            self.record(date, key, ChangeKind::AccessChanged { from, to });
+        }
+    }
+
+    fn record(&mut self, date: SystemTime, ring: RingKey, kind: ChangeKind) {
+        let change = Change { date, ring, kind };
+        self.changes.entry(ring.tower_id).or_default().push(change);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::{ChangeKind, History};
+    use crate::{AccessStatus, Doves, Ring, RingBuilder, TowerId, Weight};
+
+    fn ring(id: u32, bells: u8) -> RingBuilder {
+        RingBuilder::new(TowerId::new(id), format!("Place {}", id), bells)
+    }
+
+    fn doves(rings: Vec<RingBuilder>) -> Doves {
+        Doves::from(
+            rings
+                .into_iter()
+                .map(|r| r.build().unwrap())
+                .collect::<Vec<Ring>>(),
+        )
+    }
+
+    fn kinds(history: &History, id: u32) -> Vec<ChangeKind> {
+        let changes = history.tower(TowerId::new(id)).iter();
+        changes.map(|change| change.kind.clone()).collect()
+    }
+
+    #[test]
+    fn add_snapshot() {
+        let first = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        let second = first + Duration::from_secs(86_400);
+        let weight = Weight::from_cwt_qr_lb(12, 0, 0);
+
+        let mut history = History::new();
+        history.add_snapshot(first, &doves(vec![ring(1, 6), ring(2, 8), ring(3, 5)]));
+        // The baseline's `Ring`s aren't reported as added
+        assert_eq!(history.iter().count(), 0);
+
+        history.add_snapshot(
+            second,
+            &doves(vec![
+                ring(1, 8),
+                ring(2, 8).weight(weight).access(AccessStatus::Unringable),
+                ring(4, 6),
+            ]),
+        );
+        assert_eq!(history.snapshots(), [first, second]);
+        assert_eq!(
+            kinds(&history, 1),
+            [ChangeKind::Augmented { from: 6, to: 8 }]
+        );
+        assert_eq!(
+            kinds(&history, 2),
+            [
+                ChangeKind::WeightChanged {
+                    from: None,
+                    to: Some(weight)
+                },
+                ChangeKind::AccessChanged {
+                    from: AccessStatus::Ringable,
+                    to: AccessStatus::Unringable
+                },
+            ]
+        );
+        assert_eq!(kinds(&history, 3), [ChangeKind::Removed]);
+        assert_eq!(kinds(&history, 4), [ChangeKind::Added]);
+        assert_eq!(history.tower(TowerId::new(3))[0].date, second);
+    }
+
+    #[test]
+    #[should_panic]
+    fn snapshots_must_be_chronological() {
+        let mut history = History::new();
+        history.add_snapshot(
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1),
+            &Doves::new(),
+        );
+        history.add_snapshot(SystemTime::UNIX_EPOCH, &Doves::new());
+    }
+}
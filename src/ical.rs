@@ -0,0 +1,419 @@
+//! Exporting practice nights as an iCalendar feed, so ringers can subscribe to the practices of
+//! nearby towers in their calendar app.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    metadata::civil_date, Doves, NameStyle, PracticeSession, Ring, RingFilter, WeekOfMonth,
+    Weekday, Weeks,
+};
+
+/// How long each practice event lasts.  Dove's doesn't record when practices end, but most last
+/// about this long.
+const PRACTICE_HOURS: u8 = 2;
+/// The year and month of the earliest event in the feed.  Every practice is a recurring event
+/// starting from its first session in or after this month.
+const FIRST_YEAR: u32 = 2024;
+const FIRST_MONTH: u32 = 1;
+/// 1st January 2024 was a Monday
+const FIRST_WEEKDAY: Weekday = Weekday::Monday;
+
+impl Doves {
+    /// Renders the practices of every [`Ring`] which passes a [`RingFilter`] as an iCalendar
+    /// (RFC 5545) feed, generated now (see [`Doves::practice_calendar_at`]).
+    pub fn practice_calendar(&self, filter: impl RingFilter) -> String {
+        self.practice_calendar_at(filter, SystemTime::now())
+    }
+
+    /// Renders the practices of every [`Ring`] which passes a [`RingFilter`] as an iCalendar
+    /// (RFC 5545) feed, stamped as being `generated` at the given time.  Each
+    /// [`PracticeSession`] becomes a recurring event (or two, if it can't be described by one
+    /// `RRULE`), located at the `Ring`'s [best coordinates](Ring::best_coordinates).  Practices
+    /// without a start time are all-day events, and `Ring`s whose practice descriptions can't be
+    /// parsed (see [`Ring::practice_schedule`]) are left out.  Times are in the local time of
+    /// each tower.
+    pub fn practice_calendar_at(&self, filter: impl RingFilter, generated: SystemTime) -> String {
+        let stamp = timestamp(generated);
+        let mut ical = String::new();
+        let mut line = |s: &str| push_folded(&mut ical, s);
+        line("BEGIN:VCALENDAR");
+        line("VERSION:2.0");
+        line("PRODID:-//doves_guide//Practice nights//EN");
+        line("CALSCALE:GREGORIAN");
+        for ring in self.filter(filter) {
+            let schedule = match ring.practice_schedule() {
+                Some(schedule) => schedule,
+                None => continue,
+            };
+            for (idx, session) in schedule.sessions.iter().enumerate() {
+                for (rule_idx, recurrence) in recurrences(&session.weeks).iter().enumerate() {
+                    let uid = match rule_idx {
+                        0 => idx.to_string(),
+                        _ => format!("{}-{}", idx, rule_idx),
+                    };
+                    for event_line in event(ring, session, recurrence, &uid, &stamp) {
+                        line(&event_line);
+                    }
+                }
+            }
+        }
+        line("END:VCALENDAR");
+        ical
+    }
+}
+
+/// The lines of the `VEVENT` for one recurrence of a practice session
+fn event(
+    ring: &Ring,
+    session: &PracticeSession,
+    recurrence: &Recurrence,
+    uid: &str,
+    stamp: &str,
+) -> Vec<String> {
+    let key = ring.key();
+    let (year, month, day) = first_session(session.day, recurrence);
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_owned(),
+        format!(
+            "UID:practice-{}-{}-{}@dove.cccbr.org.uk",
+            key.tower_id.get(),
+            key.towerbase_id,
+            uid
+        ),
+        format!("DTSTAMP:{}", stamp),
+    ];
+    match session.time {
+        Some((hour, minute)) => {
+            lines.push(format!(
+                "DTSTART:{:04}{:02}{:02}T{:02}{:02}00",
+                year, month, day, hour, minute
+            ));
+            lines.push(format!("DURATION:PT{}H", PRACTICE_HOURS));
+        }
+        None => lines.push(format!(
+            "DTSTART;VALUE=DATE:{:04}{:02}{:02}",
+            year, month, day
+        )),
+    }
+    lines.push(format!("RRULE:{}", recurrence.rrule(session.day)));
+    lines.push(format!(
+        "SUMMARY:{}",
+        escape(&format!("Practice: {}", ring.display_name(NameStyle::Dove)))
+    ));
+    // The note of each session is part of the practice description, so needn't be repeated
+    if let Some(practice) = &ring.practice {
+        lines.push(format!("DESCRIPTION:{}", escape(practice)));
+    }
+    let location = [
+        Some(ring.place.as_str()),
+        ring.place2.as_deref(),
+        ring.county.as_deref(),
+    ]
+    .iter()
+    .flatten()
+    .copied()
+    .collect::<Vec<_>>()
+    .join(", ");
+    lines.push(format!("LOCATION:{}", escape(&location)));
+    if let Some((coords, _)) = ring.best_coordinates() {
+        lines.push(format!("GEO:{:.5};{:.5}", coords.lat(), coords.long()));
+    }
+    lines.push(format!("URL:{}", ring.doves_url()));
+    lines.push("END:VEVENT".to_owned());
+    lines
+}
+
+/// Which occurrences of a [`PracticeSession`]'s weekday are covered by one `RRULE`
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Recurrence {
+    /// Every `n` weeks
+    Weekly(u8),
+    /// The given occurrences of the weekday in each month, counting back from the end of the
+    /// month if negative (as in `BYSETPOS`)
+    Monthly(Vec<i8>),
+    /// The 4th occurrence of the weekday, but only in months which also have a 5th
+    FourthNotLast,
+}
+
+/// The [`Recurrence`]s which together cover the weeks of a [`PracticeSession`].  This is usually
+/// one, but a practice held every week except the last needs two: whether the 4th week is the
+/// last depends on the month, and `BYSETPOS` can't express that on its own.
+fn recurrences(weeks: &Weeks) -> Vec<Recurrence> {
+    let position = |week: &WeekOfMonth| match week {
+        WeekOfMonth::Nth(n) => *n as i8,
+        WeekOfMonth::Last => -1,
+    };
+    let recurrences = match weeks {
+        Weeks::Every => vec![Recurrence::Weekly(1)],
+        Weeks::Alternate => vec![Recurrence::Weekly(2)],
+        Weeks::Only(weeks) => vec![Recurrence::Monthly(weeks.iter().map(position).collect())],
+        Weeks::Except(excluded) => {
+            let is_included = |n: &u8| !excluded.contains(&WeekOfMonth::Nth(*n));
+            if excluded.contains(&WeekOfMonth::Last) {
+                // The 1st to 3rd weeks are never the last, and the 5th always is
+                let mut recurrences = vec![Recurrence::Monthly(
+                    (1..=3).filter(is_included).map(|n| n as i8).collect(),
+                )];
+                if is_included(&4) {
+                    recurrences.push(Recurrence::FourthNotLast);
+                }
+                recurrences
+            } else {
+                vec![Recurrence::Monthly(
+                    (1..=5).filter(is_included).map(|n| n as i8).collect(),
+                )]
+            }
+        }
+    };
+    // Excluding every week leaves nothing to recur
+    recurrences
+        .into_iter()
+        .filter(|r| !matches!(r, Recurrence::Monthly(positions) if positions.is_empty()))
+        .collect()
+}
+
+impl Recurrence {
+    /// The `RRULE` for this `Recurrence` of practices on `day`
+    fn rrule(&self, day: Weekday) -> String {
+        let day = day.dove_abbreviation()[..2].to_ascii_uppercase();
+        match self {
+            Recurrence::Weekly(1) => format!("FREQ=WEEKLY;BYDAY={}", day),
+            Recurrence::Weekly(n) => format!("FREQ=WEEKLY;INTERVAL={};BYDAY={}", n, day),
+            Recurrence::Monthly(positions) => {
+                let positions = positions
+                    .iter()
+                    .map(i8::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!("FREQ=MONTHLY;BYDAY={};BYSETPOS={}", day, positions)
+            }
+            // Of the (at most two) occurrences on or after the 22nd, the 4th is the
+            // second-to-last only if there's a 5th
+            Recurrence::FourthNotLast => format!(
+                "FREQ=MONTHLY;BYDAY={};BYMONTHDAY=22,23,24,25,26,27,28,29,30,31;BYSETPOS=-2",
+                day
+            ),
+        }
+    }
+
+    /// Whether this `Recurrence` includes its weekday on the given `day` of a month with
+    /// `month_len` days
+    fn includes(&self, day: u32, month_len: u32) -> bool {
+        let occurrence = (day - 1) / 7 + 1;
+        let occurrences = occurrence + (month_len - day) / 7;
+        match self {
+            Recurrence::Weekly(_) => true,
+            Recurrence::Monthly(positions) => positions.iter().any(|&p| {
+                let p = i64::from(p);
+                let from_start = if p > 0 {
+                    p
+                } else {
+                    i64::from(occurrences) + 1 + p
+                };
+                from_start == i64::from(occurrence)
+            }),
+            Recurrence::FourthNotLast => occurrence == 4 && occurrences == 5,
+        }
+    }
+}
+
+/// The date of the first practice on `weekday` covered by a [`Recurrence`], on or after the first
+/// day of the feed
+fn first_session(weekday: Weekday, recurrence: &Recurrence) -> (u32, u32, u32) {
+    let (mut year, mut month, mut day) = (FIRST_YEAR, FIRST_MONTH, 1);
+    let mut current_weekday = FIRST_WEEKDAY as u32;
+    loop {
+        let month_len = days_in_month(year, month);
+        if current_weekday == weekday as u32 && recurrence.includes(day, month_len) {
+            return (year, month, day);
+        }
+        current_weekday = (current_weekday + 1) % 7;
+        day += 1;
+        if day > month_len {
+            day = 1;
+            month += 1;
+            if month > 12 {
+                month = 1;
+                year += 1;
+            }
+        }
+    }
+}
+
+/// Formats a [`SystemTime`] as an iCalendar `DATE-TIME` in UTC (e.g. `20240131T193000Z`)
+fn timestamp(time: SystemTime) -> String {
+    let (year, month, day) = civil_date(time);
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()) % 86_400;
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Escapes a string for use as an iCalendar `TEXT` value
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Appends a content line to `ical`, folding it onto continuation lines so that no line is
+/// longer than 75 bytes (as required by RFC 5545)
+fn push_folded(ical: &mut String, line: &str) {
+    const MAX_LINE_BYTES: usize = 75;
+    let mut line_len = 0;
+    for c in line.chars() {
+        if line_len + c.len_utf8() > MAX_LINE_BYTES {
+            ical.push_str("\r\n ");
+            line_len = 1;
+        }
+        ical.push(c);
+        line_len += c.len_utf8();
+    }
+    ical.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::recurrences;
+    use crate::{practice::parse_schedule, Doves, Ring, RingBuilder, TowerId};
+
+    /// The `RRULE`s of the first session of a practice description
+    fn rrules(practice: &str) -> Vec<String> {
+        let session = parse_schedule(practice).unwrap().sessions.remove(0);
+        recurrences(&session.weeks)
+            .iter()
+            .map(|r| r.rrule(session.day))
+            .collect()
+    }
+
+    fn calendar(practice: &str) -> String {
+        let ring = RingBuilder::new(TowerId::new(1), "Anytown", 6)
+            .practice(practice)
+            .build()
+            .unwrap();
+        // 14th November 2023 at 22:13:20 UTC
+        let generated = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        Doves::from(vec![ring]).practice_calendar_at(|_: &Ring| true, generated)
+    }
+
+    #[test]
+    fn weekly() {
+        assert_eq!(rrules("Mon 19:30"), ["FREQ=WEEKLY;BYDAY=MO"]);
+        assert_eq!(rrules("Tue (alt)"), ["FREQ=WEEKLY;INTERVAL=2;BYDAY=TU"]);
+    }
+
+    #[test]
+    fn only() {
+        assert_eq!(
+            rrules("Thu (1st, 3rd) 19:30"),
+            ["FREQ=MONTHLY;BYDAY=TH;BYSETPOS=1,3"]
+        );
+        assert_eq!(
+            rrules("Fri (2nd, last)"),
+            ["FREQ=MONTHLY;BYDAY=FR;BYSETPOS=2,-1"]
+        );
+    }
+
+    #[test]
+    fn except() {
+        assert_eq!(
+            rrules("Wed (exc 1st)"),
+            ["FREQ=MONTHLY;BYDAY=WE;BYSETPOS=2,3,4,5"]
+        );
+        assert_eq!(
+            rrules("Wed (exc 2nd, 4th)"),
+            ["FREQ=MONTHLY;BYDAY=WE;BYSETPOS=1,3,5"]
+        );
+    }
+
+    #[test]
+    fn except_last() {
+        let fourth_not_last =
+            "FREQ=MONTHLY;BYDAY=TH;BYMONTHDAY=22,23,24,25,26,27,28,29,30,31;BYSETPOS=-2";
+        assert_eq!(
+            rrules("Thu (exc last)"),
+            ["FREQ=MONTHLY;BYDAY=TH;BYSETPOS=1,2,3", fourth_not_last]
+        );
+        assert_eq!(
+            rrules("Thu (exc 1st, last)"),
+            ["FREQ=MONTHLY;BYDAY=TH;BYSETPOS=2,3", fourth_not_last]
+        );
+        assert_eq!(
+            rrules("Thu (exc 4th, last)"),
+            ["FREQ=MONTHLY;BYDAY=TH;BYSETPOS=1,2,3"]
+        );
+        assert_eq!(rrules("Thu (exc 1st, 2nd, 3rd, last)"), [fourth_not_last]);
+    }
+
+    #[test]
+    fn no_positions_are_negative_except_last() {
+        for practice in ["Sat (exc last)", "Sat (exc 3rd, last)", "Sat (exc 5th)"] {
+            for rrule in rrules(practice) {
+                assert!(!rrule.contains("BYSETPOS=-1"), "{}: {}", practice, rrule);
+                assert!(!rrule.contains(",-"), "{}: {}", practice, rrule);
+            }
+        }
+    }
+
+    #[test]
+    fn events_start_on_a_practice() {
+        let ical = calendar("Thu (exc last) 19:30");
+        // January 2024 has four Thursdays, so the first 4th-but-not-last is in February
+        assert!(ical.contains("DTSTART:20240104T193000\r\n"));
+        assert!(ical.contains("DTSTART:20240222T193000\r\n"));
+        assert!(ical.contains("UID:practice-1-0-0@dove.cccbr.org.uk\r\n"));
+        assert!(ical.contains("UID:practice-1-0-0-1@dove.cccbr.org.uk\r\n"));
+        assert_eq!(ical.matches("BEGIN:VEVENT").count(), 2);
+    }
+
+    #[test]
+    fn dtstamp_is_generation_time() {
+        let ical = calendar("Mon 19:30");
+        assert!(ical.contains("DTSTAMP:20231114T221320Z\r\n"));
+        assert!(ical.contains("DTSTART:20240101T193000\r\n"));
+        assert!(ical.contains("DURATION:PT2H\r\n"));
+    }
+
+    #[test]
+    fn all_day_events() {
+        let ical = calendar("Sun (2nd)");
+        assert!(ical.contains("DTSTART;VALUE=DATE:20240114\r\n"));
+        assert!(!ical.contains("DURATION"));
+    }
+
+    #[test]
+    fn by_arrangement_has_no_events() {
+        let ical = calendar("PN: by arrangement");
+        assert!(!ical.contains("BEGIN:VEVENT"));
+        assert!(ical.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ical.ends_with("END:VCALENDAR\r\n"));
+    }
+}
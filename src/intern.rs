@@ -0,0 +1,178 @@
+//! Interning strings which are repeated across many [`Ring`](crate::Ring)s (e.g. county names),
+//! so that every copy shares one allocation and can be compared by address.
+
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    collections::HashSet,
+    fmt::{Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
+
+use crate::SmolStr;
+
+/// Every string which has ever been interned
+static POOL: OnceLock<Mutex<HashSet<Arc<str>>>> = OnceLock::new();
+
+/// An interned string, used for the values which Dove's repeats across thousands of [`Ring`]s:
+/// counties, and the names of [`Country`]s, [`Diocese`]s, [`Contractor`]s and [`Affiliation`]s
+/// which don't have their own variants.
+///
+/// Every `Symbol` with the same value shares a single allocation, so cloning one only increments
+/// a reference count and comparing two only compares their addresses.  Interned strings are never
+/// freed, so `Symbol`s should only be used for values from a small vocabulary.
+///
+/// [`Ring`]: crate::Ring
+/// [`Country`]: crate::Country
+/// [`Diocese`]: crate::Diocese
+/// [`Contractor`]: crate::Contractor
+/// [`Affiliation`]: crate::Affiliation
+#[derive(Clone)]
+pub struct Symbol(Arc<str>);
+
+impl Symbol {
+    /// Interns `s`, returning a `Symbol` which shares its allocation with every other `Symbol`
+    /// with the same value
+    pub fn new(s: &str) -> Self {
+        let pool = POOL.get_or_init(Default::default);
+        let mut pool = pool.lock().unwrap_or_else(PoisonError::into_inner);
+        match pool.get(s) {
+            Some(existing) => Symbol(existing.clone()),
+            None => {
+                let interned = Arc::<str>::from(s);
+                pool.insert(interned.clone());
+                Symbol(interned)
+            }
+        }
+    }
+
+    /// The interned string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        // Equal strings are always interned into the same allocation
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash the string (not the address), so that `Symbol`s can be looked up by `&str`
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(s: String) -> Self {
+        Self::new(&s)
+    }
+}
+
+impl From<SmolStr> for Symbol {
+    fn from(s: SmolStr) -> Self {
+        Self::new(&s)
+    }
+}
+
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::Symbol;
+
+    #[test]
+    fn equal_strings_share_an_allocation() {
+        let county = String::from("Leicestershire");
+        let (a, b) = (Symbol::new("Leicestershire"), Symbol::from(county));
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_eq!(a, b);
+        assert_eq!(a, "Leicestershire");
+    }
+
+    #[test]
+    fn different_strings_stay_different() {
+        let (a, b) = (Symbol::new("Devon"), Symbol::new("Dorset"));
+        assert_ne!(a, b);
+        assert!(a < b);
+        assert_eq!(b.to_string(), "Dorset");
+    }
+
+    #[test]
+    fn lookup_by_str() {
+        let mut counts = HashMap::new();
+        *counts.entry(Symbol::new("Kent")).or_insert(0) += 1;
+        *counts.entry(Symbol::new("Kent")).or_insert(0) += 1;
+        assert_eq!(counts.get("Kent"), Some(&2));
+        assert_eq!(counts.len(), 1);
+    }
+}
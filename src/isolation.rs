@@ -0,0 +1,47 @@
+//! Finding how far each [`Ring`] is from its nearest neighbour, to identify the most isolated
+//! towers.
+
+use crate::{Doves, Nearby, Ring, RingFilter};
+
+/// A [`Ring`] along with its nearest neighbour, as returned by [`Doves::isolation`]
+#[derive(Debug, Clone, Copy)]
+pub struct Isolation<'d> {
+    pub ring: &'d Ring,
+    /// The nearest `Ring` in another tower, and its distance from `ring`
+    pub nearest: Nearby<'d>,
+}
+
+impl Doves {
+    /// Finds the nearest neighbour of every [`Ring`] which passes a [`RingFilter`], sorted from the
+    /// most isolated to the least.  Neighbours must also pass the filter, so (for example) using
+    /// `And(AtLeast(6), Ring::is_ringable)` finds the distance from each ringable 6 or more to the
+    /// next one.  `Ring`s in the same tower aren't counted as neighbours, and `Ring`s without
+    /// coordinates (or with no neighbours at all) are skipped.
+    pub fn isolation(&self, filter: impl RingFilter) -> Vec<Isolation<'_>> {
+        let index = self.spatial_index();
+        let mut isolation = self
+            .iter()
+            .filter(|ring| filter.matches(ring))
+            .filter_map(|ring| {
+                let (coords, _) = ring.best_coordinates()?;
+                let nearest = index
+                    .by_distance(coords)
+                    .map(|idx| &self.towers[idx])
+                    .find(|other| other.id != ring.id && filter.matches(other))?;
+                Some(Isolation {
+                    ring,
+                    nearest: Nearby {
+                        ring: nearest,
+                        distance: nearest.distance_to(coords).unwrap(),
+                    },
+                })
+            })
+            .collect::<Vec<_>>();
+        isolation.sort_by(|a, b| {
+            (b.nearest.distance.km())
+                .total_cmp(&a.nearest.distance.km())
+                .then_with(|| a.ring.key().cmp(&b.ring.key()))
+        });
+        isolation
+    }
+}
@@ -0,0 +1,89 @@
+//! The musical keys of [`Ring`]s.
+
+use std::fmt::{Display, Formatter};
+
+use crate::{Accidental, Note, NoteName, Ring};
+
+/// The natural notes in the order of the circle of fifths, starting from `F` (the first note with
+/// a flat in its key signature)
+const FIFTHS: [NoteName; 7] = [
+    NoteName::F,
+    NoteName::C,
+    NoteName::G,
+    NoteName::D,
+    NoteName::A,
+    NoteName::E,
+    NoteName::B,
+];
+/// The position of each degree of a major scale in the circle of fifths, relative to the tonic
+const SCALE_FIFTHS: [i8; 7] = [0, 2, 4, -1, 1, 3, 5];
+/// The most sharps or flats that a key signature can have
+const MAX_ACCIDENTALS: i8 = 7;
+
+/// The major key in which a [`Ring`] is tuned, as returned by [`Ring::musical_key`].  Rings are
+/// tuned to a major scale with the tenor as the tonic, so `MusicalKey`s are always major.
+///
+/// `MusicalKey`s are always spelled using a real key signature, so a tenor of `G♯` gives the key
+/// of `A♭` (rather than `G♯` major, which would need 8 sharps).  They're [`Display`]ed as just
+/// the tonic (e.g. `F♯`), as in "10 bells in F♯".
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct MusicalKey {
+    /// The number of sharps (if positive) or flats (if negative) in the key signature
+    signature: i8,
+}
+
+impl MusicalKey {
+    /// The major key whose tonic is `tonic`
+    pub fn major(tonic: Note) -> Self {
+        let position = FIFTHS.iter().position(|&n| n == tonic.name).unwrap() as i8;
+        let mut signature = position - 1 + tonic.accidental.offset() * MAX_ACCIDENTALS;
+        // Respell keys which would need more than 7 sharps or flats
+        if signature > MAX_ACCIDENTALS {
+            signature -= 12;
+        } else if signature < -MAX_ACCIDENTALS {
+            signature += 12;
+        }
+        Self { signature }
+    }
+
+    /// The tonic of this key (i.e. the note of the tenor)
+    pub fn tonic(self) -> Note {
+        note_at_fifth(self.signature)
+    }
+
+    /// The number of sharps (if positive) or flats (if negative) in this key's signature
+    pub fn signature(self) -> i8 {
+        self.signature
+    }
+
+    /// The seven notes of this key's major scale, starting from the tonic
+    pub fn scale(self) -> [Note; 7] {
+        SCALE_FIFTHS.map(|offset| note_at_fifth(self.signature + offset))
+    }
+}
+
+impl Ring {
+    /// The [`MusicalKey`] of this `Ring`, derived from the [note](Ring::note) of the tenor, or
+    /// `None` if the note isn't known.  Any [semitone](Ring::semitones) bells are outside the
+    /// key, so don't change it.
+    pub fn musical_key(&self) -> Option<MusicalKey> {
+        self.note.map(MusicalKey::major)
+    }
+}
+
+impl Display for MusicalKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tonic())
+    }
+}
+
+/// The note at a given position in the circle of fifths, where `0` is `C`
+fn note_at_fifth(position: i8) -> Note {
+    let name = FIFTHS[(position + 1).rem_euclid(7) as usize];
+    let accidental = match (position + 1).div_euclid(7) {
+        n if n < 0 => Accidental::Flat,
+        0 => Accidental::Natural,
+        _ => Accidental::Sharp,
+    };
+    Note::new(name, accidental)
+}
@@ -0,0 +1,271 @@
+//! A two-phase way of loading Dove's Guide, which decodes the commonly used fields of every row
+//! up front and leaves the rest until they're first needed.
+
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::Read,
+    path::Path,
+    sync::{Arc, OnceLock},
+};
+
+use csv::StringRecord;
+use serde::Deserialize;
+
+use crate::{
+    practice::parse_schedule,
+    record::{deser_not_empty, parse_affiliations},
+    Affiliation, Coordinates, Doves, PracticeSchedule, Ring, SmolStr, TowerId, Weight,
+};
+
+/// The columns of Dove's CSV file which are decoded as soon as a [`LazyRing`] is loaded
+#[derive(Debug, Deserialize)]
+struct HotFields {
+    #[serde(rename = "TowerID")]
+    id: TowerId,
+    #[serde(rename = "Place")]
+    place: SmolStr,
+    #[serde(rename = "Bells")]
+    bells: u8,
+    #[serde(rename = "Wt")]
+    weight_lbs: Option<f64>,
+    #[serde(rename = "App", deserialize_with = "deser_not_empty")]
+    weight_approximate: bool,
+    #[serde(rename = "Lat")]
+    lat: Option<f64>,
+    #[serde(rename = "Long")]
+    long: Option<f64>,
+}
+
+/// A [`Ring`] whose commonly used fields (ID, place, bells, weight and coordinates) are decoded
+/// when it's loaded, created by a [`LazyDoves`].  The rarely used fields which are expensive to
+/// decode (affiliations and practice schedules) are parsed on first access and then cached, and
+/// everything else is available by converting into a full `Ring` with [`LazyRing::to_ring`].
+/// Fields have the same meanings as the fields of `Ring` with the same names.
+#[derive(Debug, Clone)]
+pub struct LazyRing {
+    pub id: TowerId,
+    pub place: SmolStr,
+    pub bells: u8,
+    pub weight: Option<Weight>,
+    /// The [`Coordinates`] of the tower, or `None` if Dove's doesn't give valid ones
+    pub coordinates: Option<Coordinates>,
+
+    /// The CSV record this `LazyRing` was read from
+    record: StringRecord,
+    /// The headers of the CSV file, shared between every `LazyRing` loaded from it
+    headers: Arc<StringRecord>,
+    affiliations: OnceLock<BTreeSet<Affiliation>>,
+    practice_schedule: OnceLock<Option<PracticeSchedule>>,
+}
+
+impl LazyRing {
+    fn new(record: StringRecord, headers: Arc<StringRecord>) -> Result<Self, csv::Error> {
+        let hot: HotFields = record.deserialize(Some(&headers))?;
+        let approximate = hot.weight_approximate;
+        Ok(Self {
+            id: hot.id,
+            place: hot.place,
+            bells: hot.bells,
+            weight: hot
+                .weight_lbs
+                .map(|lbs| Weight::from_lbs(lbs).with_approximate(approximate)),
+            coordinates: hot
+                .lat
+                .zip(hot.long)
+                .and_then(|(lat, long)| Coordinates::new(lat, long).ok()),
+            record,
+            headers,
+            affiliations: OnceLock::new(),
+            practice_schedule: OnceLock::new(),
+        })
+    }
+
+    /// Set of [`Affiliation`]s to which this tower belongs, parsed on first access
+    pub fn affiliations(&self) -> &BTreeSet<Affiliation> {
+        self.affiliations
+            .get_or_init(|| parse_affiliations(self.column("Affiliations").unwrap_or("")))
+    }
+
+    /// The semitone bells of this ring, exactly as written in Dove's
+    pub fn semitones(&self) -> Option<&str> {
+        self.column("Semitones")
+    }
+
+    /// The practice night description, exactly as written in Dove's
+    pub fn practice(&self) -> Option<&str> {
+        self.column("Practice")
+    }
+
+    /// The [`PracticeSchedule`] parsed from the [practice night description](Self::practice) on
+    /// first access (see [`Ring::practice_schedule`])
+    pub fn practice_schedule(&self) -> Option<&PracticeSchedule> {
+        self.practice_schedule
+            .get_or_init(|| self.practice().and_then(parse_schedule))
+            .as_ref()
+    }
+
+    /// Decodes the full CSV record into an owned [`Ring`], with the same validation as when
+    /// loading a [`Doves`]
+    pub fn to_ring(&self) -> Result<Ring, csv::Error> {
+        self.record.deserialize(Some(&self.headers))
+    }
+
+    /// The value in the column with the given header, or `None` if it's empty or missing
+    fn column(&self, header: &str) -> Option<&str> {
+        let idx = self.headers.iter().position(|h| h == header)?;
+        self.record.get(idx).filter(|s| !s.is_empty())
+    }
+}
+
+/// A list of [`LazyRing`]s read from Dove's Guide.  This loads considerably faster than a
+/// [`Doves`], at the cost of only validating the commonly used fields up front.
+#[derive(Debug, Clone, Default)]
+pub struct LazyDoves {
+    rings: Vec<LazyRing>,
+}
+
+impl LazyDoves {
+    /// Reads a `LazyDoves` from the contents of Dove's `dove.csv` file
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "load_lazy", skip_all))]
+    pub fn from_reader(reader: impl Read) -> Result<Self, csv::Error> {
+        let mut reader = csv::Reader::from_reader(reader);
+        let headers = Arc::new(reader.headers()?.clone());
+        let rings = reader
+            .into_records()
+            .map(|record| LazyRing::new(record?, headers.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        #[cfg(feature = "tracing")]
+        tracing::info!(rings = rings.len(), "loaded Dove's Guide lazily");
+        Ok(Self { rings })
+    }
+
+    /// Reads a `LazyDoves` from Dove's `dove.csv` file
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, csv::Error> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// All the [`LazyRing`]s, in the order they appear in Dove's Guide
+    pub fn rings(&self) -> &[LazyRing] {
+        &self.rings
+    }
+
+    /// The number of [`LazyRing`]s
+    pub fn len(&self) -> usize {
+        self.rings.len()
+    }
+
+    /// `true` if there are no [`LazyRing`]s
+    pub fn is_empty(&self) -> bool {
+        self.rings.is_empty()
+    }
+
+    /// Iterates over the [`LazyRing`]s, in the order they appear in Dove's Guide
+    pub fn iter(&self) -> std::slice::Iter<'_, LazyRing> {
+        self.rings.iter()
+    }
+
+    /// Fully decodes every [`LazyRing`] into a [`Doves`]
+    pub fn to_doves(&self) -> Result<Doves, csv::Error> {
+        let towers = self
+            .rings
+            .iter()
+            .map(LazyRing::to_ring)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Doves::from(towers))
+    }
+}
+
+impl<'d> IntoIterator for &'d LazyDoves {
+    type Item = &'d LazyRing;
+    type IntoIter = std::slice::Iter<'d, LazyRing>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LazyDoves;
+    use crate::{Affiliation, Column, Doves, Ring};
+
+    const CSV: &str = "TowerID,RingType,Place,Place2,PlaceCL,Dedicn,AltName,County,Country,\
+        ISO3166code,Diocese,Lat,Long,Bells,UR,Semitones,Wt,App,Note,Hz,Details,GF,Toilet,\
+        Simulator,ExtraInfo,WebPage,Affiliations,NG,Postcode,Practice,OvhaulYr,Contractor,TuneYr,\
+        LGrade,BldgID,ChurchCare,TowerBase,DoveID,SNLat,SNLong
+12574,Full circle ring,Ab Kettleby,,,S James,,Leicestershire,England,GB,Leicester,52.79858,\
+        -0.92747,6,,,974,,G,783.0,C,GF,,,,http://www.abkettlebybells.co.uk/,LeiDG;CUG,SK724228,\
+        LE14 3HS,Wed 19:30,2016,,,II*,1295154,619058,6918,AB KETTLEB,52.79968,-0.92635
+12575,Full circle ring,Abbots Bromley,,,S Nicholas,,Staffordshire,England,GB,Lichfield,,,8,,,\
+        1200,app,F,,,,T,,,,,SK079245,WS15 3BP,,,,,,,,0,ABBOTS BRO,,
+";
+
+    fn load() -> (LazyDoves, Doves) {
+        let lazy = LazyDoves::from_reader(CSV.as_bytes()).unwrap();
+        let doves = Doves::from_reader(CSV.as_bytes()).unwrap();
+        (lazy, doves)
+    }
+
+    /// Every value of `ring`, formatted as in Dove's CSV file
+    fn values(ring: &Ring) -> Vec<String> {
+        Column::all().map(|column| column.value(ring)).collect()
+    }
+
+    #[test]
+    fn hot_fields_match_doves() {
+        let (lazy, doves) = load();
+        assert_eq!(lazy.len(), doves.len());
+        for (lazy_ring, ring) in lazy.iter().zip(&doves) {
+            assert_eq!(lazy_ring.id, ring.id);
+            assert_eq!(lazy_ring.place, ring.place);
+            assert_eq!(lazy_ring.bells, ring.bells);
+            assert_eq!(lazy_ring.weight, ring.weight);
+            assert_eq!(
+                lazy_ring.weight.map(|w| w.is_approximate()),
+                ring.weight.map(|w| w.is_approximate())
+            );
+            assert_eq!(lazy_ring.coordinates, ring.coordinates);
+            assert_eq!(lazy_ring.semitones(), ring.semitones.as_deref());
+            assert_eq!(lazy_ring.practice(), ring.practice.as_deref());
+        }
+        assert_eq!(lazy.rings()[1].coordinates, None);
+    }
+
+    #[test]
+    fn cold_fields_are_parsed_once() {
+        let (lazy, doves) = load();
+        let (lazy_ring, ring) = (&lazy.rings()[0], &doves[0]);
+        assert!(lazy_ring.affiliations.get().is_none());
+        assert!(lazy_ring.practice_schedule.get().is_none());
+
+        let affiliations = lazy_ring.affiliations();
+        assert!(affiliations.contains(&Affiliation::CambridgeUni));
+        assert_eq!(affiliations, &ring.affiliations);
+        assert_eq!(
+            lazy_ring.practice_schedule(),
+            ring.practice_schedule().as_ref()
+        );
+        // Later accesses return the cached values, rather than parsing again
+        assert!(std::ptr::eq(affiliations, lazy_ring.affiliations()));
+        assert!(std::ptr::eq(
+            lazy_ring.practice_schedule().unwrap(),
+            lazy_ring.practice_schedule().unwrap()
+        ));
+        // Cached values are kept by clones
+        assert!(lazy_ring.clone().affiliations.get().is_some());
+    }
+
+    #[test]
+    fn to_doves_matches_doves() {
+        let (lazy, doves) = load();
+        let converted = lazy.to_doves().unwrap();
+        assert_eq!(converted.len(), doves.len());
+        for (a, b) in converted.iter().zip(&doves) {
+            assert_eq!(values(a), values(b));
+        }
+        for (lazy_ring, ring) in lazy.iter().zip(&doves) {
+            assert_eq!(values(&lazy_ring.to_ring().unwrap()), values(ring));
+        }
+    }
+}
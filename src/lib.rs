@@ -1,200 +1,787 @@
+#[cfg(feature = "proptest")]
+mod arbitrary;
+mod bounds;
+mod builder;
+mod bundle;
+mod church_care;
+mod cluster;
+mod contractor;
+mod coords;
+mod country;
+mod country_summary;
+mod county_list;
+mod dedication;
+mod density;
+mod diff;
+mod diocese;
+mod distance;
+mod distance_matrix;
+mod duplicate;
+mod enrich;
+mod export;
+mod fast_csv;
+mod feature;
+mod felstead;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod filter;
+mod flags;
+#[cfg(feature = "geo")]
+mod geo;
+mod geojson;
+mod gpx;
+mod grid_ref;
+mod handle;
+mod history;
+mod ical;
+mod intern;
+mod isolation;
+mod key;
+#[cfg(feature = "serde")]
+mod lazy;
+mod lint;
+#[cfg(feature = "serde")]
+mod load;
+mod lookup;
+mod maintenance;
+mod metadata;
+mod name;
+mod normalize;
+mod note;
+mod osm;
+mod outing;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod postcode;
+mod practice;
+mod query;
+#[cfg(feature = "rand")]
+mod random;
+mod record;
+mod redirect;
+mod region;
+mod report;
+#[cfg(feature = "serde")]
+mod ring_ref;
+mod route;
+mod search;
+mod semitones;
+mod simulator;
+mod size;
+mod sort;
+mod spatial;
+mod sql;
+mod stats;
+mod sub_ring;
+mod tower;
+mod tower_record;
+mod url;
+mod weight;
+
 use std::{
-    collections::HashSet,
+    cmp::Ordering,
+    collections::BTreeSet,
+    convert::Infallible,
     fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    iter::FromIterator,
+    num::ParseIntError,
+    ops::{Index, IndexMut},
+    str::FromStr,
 };
 
-use serde::{de::Error, Deserialize, Deserializer};
+#[cfg(feature = "serde")]
+use std::{io::Read, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+pub use smol_str::SmolStr;
+
+use enrich::Enrichments;
+use lookup::Indices;
+#[cfg(feature = "serde")]
+use record::RingRecord;
+
+pub use bounds::{Bounds, Extent};
+pub use builder::{BuildRingError, RingBuilder};
+pub use bundle::JsonBundle;
+pub use church_care::ChurchCareId;
+pub use contractor::Contractor;
+pub use coords::{CoordinateSource, Coordinates, DmsCoordinates, InvalidCoordinates};
+pub use country::{Country, CountryCode, ParseCountryCodeError, SubdivisionCode};
+pub use country_summary::CountrySummary;
+pub use county_list::CountyGroup;
+pub use dedication::Dedication;
+pub use density::{DensityGrid, DensityWeight, GridCell};
+pub use diff::{DovesDiff, FieldChange};
+pub use diocese::Diocese;
+pub use distance::Distance;
+pub use distance_matrix::DistanceMatrix;
+pub use duplicate::Duplicate;
+pub use enrich::Enricher;
+pub use export::{Column, ParseColumnError};
+pub use fast_csv::FastParseError;
+pub use feature::RingFeature;
+pub use felstead::FelsteadTable;
+pub use filter::{And, Not, Or, RingFilter};
+pub use flags::RingFlags;
+pub use grid_ref::{Grid, GridRef, ParseGridRefError};
+pub use handle::DovesHandle;
+pub use history::{Change, ChangeKind, History};
+pub use intern::Symbol;
+pub use isolation::Isolation;
+pub use key::MusicalKey;
+#[cfg(feature = "serde")]
+pub use lazy::{LazyDoves, LazyRing};
+pub use lint::{Lint, LintKind, Severity};
+#[cfg(feature = "serde")]
+pub use load::{FieldGroup, LoadWarning, Loaded, Loader};
+pub use lookup::IndexKind;
+pub use maintenance::ContractorWork;
+pub use metadata::{Metadata, Source, SCHEMA_VERSION};
+pub use name::NameStyle;
+pub use normalize::{counties_match, expand_dedication, normalize_county, place_variants};
+pub use note::{
+    Accidental, AsciiNote, Frequency, Note, NoteMismatch, NoteName, ParseNoteError, CONCERT_A,
+};
+pub use osm::{OsmCandidate, OsmElement, OsmId, OsmMatch, OsmType};
+pub use outing::{Outing, OutingPlanner, OutingStop};
+pub use postcode::Postcode;
+pub use practice::{PracticeSchedule, PracticeSession, WeekOfMonth, Weekday, Weeks};
+pub use query::{Query, QueryIter};
+pub use redirect::{Redirect, Redirects, Resolution};
+pub use report::{ReportFormat, Table};
+#[cfg(feature = "serde")]
+pub use ring_ref::{RingRef, RingRefReader};
+pub use route::RouteMatch;
+pub use search::{MatchQuality, PlaceMatch, SearchHit, SearchIndex};
+pub use semitones::{ParseSemitonesError, SemitoneBell, Semitones, TuningLayout};
+pub use simulator::{BellTuning, SimulatorFormat};
+pub use size::{AtLeast, RingOf, StandardRing};
+pub use sort::SortKey;
+pub use spatial::Nearby;
+pub use stats::Stats;
+pub use sub_ring::{BellPosition, SubRing};
+pub use tower::Tower;
+pub use tower_record::{TowerMismatch, TowerRecord};
+pub use url::MapProvider;
+pub use weight::{ParseWeightError, Weight, WeightClass};
+
+/// The dedication given to mobile [`Ring`]s
+const MOBILE_RING_DEDICATION: &str = "Mobile Ring";
 
 /// A list of towers read from Dove's Guide.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Doves {
     towers: Vec<Ring>,
+    metadata: Metadata,
+    redirects: Redirects,
+    enrichments: Enrichments,
+    indices: Indices,
+}
+
+impl Doves {
+    /// Creates an empty `Doves`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a `Doves` from the contents of Dove's `dove.csv` file
+    #[cfg(feature = "serde")]
+    pub fn from_reader(reader: impl Read) -> Result<Self, csv::Error> {
+        Loader::new().from_reader(reader).map(|loaded| loaded.doves)
+    }
+
+    /// Reads a `Doves` from Dove's `dove.csv` file, as downloaded from
+    /// <https://dove.cccbr.org.uk/downloads.php>
+    #[cfg(feature = "serde")]
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, csv::Error> {
+        Loader::new().from_path(path).map(|loaded| loaded.doves)
+    }
+
+    /// All the [`Ring`]s in this `Doves`, in the order they appear in Dove's Guide
+    pub fn towers(&self) -> &[Ring] {
+        &self.towers
+    }
+
+    /// The number of [`Ring`]s in this `Doves`
+    pub fn len(&self) -> usize {
+        self.towers.len()
+    }
+
+    /// `true` if this `Doves` contains no [`Ring`]s
+    pub fn is_empty(&self) -> bool {
+        self.towers.is_empty()
+    }
+
+    /// Adds a [`Ring`] to the end of this `Doves`
+    pub fn push(&mut self, ring: Ring) {
+        self.invalidate_indices();
+        self.towers.push(ring);
+    }
+
+    /// Removes every [`Ring`] in the tower with a given [`TowerId`], returning the removed
+    /// `Ring`s in their original order.
+    pub fn remove_by_id(&mut self, id: TowerId) -> Vec<Ring> {
+        self.invalidate_indices();
+        let (removed, kept) = self.towers.drain(..).partition(|r| r.id == id);
+        self.towers = kept;
+        removed
+    }
+
+    /// Keeps only the [`Ring`]s for which `f` returns `true`, preserving their order
+    pub fn retain(&mut self, f: impl FnMut(&Ring) -> bool) {
+        self.invalidate_indices();
+        self.towers.retain(f);
+    }
+
+    /// Creates a new `Doves` containing copies of only the [`Ring`]s which pass a [`RingFilter`]
+    /// (e.g. every `Ring` in one association), in the same order.  The new `Doves` keeps this
+    /// `Doves`'s [`Metadata`] (except the `record_count`), [`Redirects`] and
+    /// [enrichments](Doves::enrich), and its lookup is built straight away, so it can be cached and
+    /// queried just like the original.
+    pub fn subset(&self, filter: impl RingFilter) -> Doves {
+        let towers = self
+            .iter()
+            .filter(|ring| filter.matches(ring))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut subset = Doves::from(towers);
+        subset.metadata = Metadata {
+            record_count: subset.len(),
+            ..self.metadata.clone()
+        };
+        subset.redirects = self.redirects.clone();
+        subset.enrichments = self.enrichments.clone();
+        subset.ensure_index(IndexKind::Id); // Build the lookup now, rather than on the first query
+        subset
+    }
+
+    /// Sorts the [`Ring`]s using a comparison function.  This sort is stable.
+    pub fn sort_by(&mut self, compare: impl FnMut(&Ring, &Ring) -> Ordering) {
+        self.invalidate_indices();
+        self.towers.sort_by(compare);
+    }
+
+    /// Returns an iterator over the [`Ring`]s
+    pub fn iter(&self) -> std::slice::Iter<'_, Ring> {
+        self.towers.iter()
+    }
+
+    /// Returns an iterator over mutable references to the [`Ring`]s
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Ring> {
+        self.invalidate_indices();
+        self.towers.iter_mut()
+    }
+
+    /// Returns an iterator over every mobile [`Ring`] (see [`Ring::is_mobile`])
+    pub fn mobile_rings(&self) -> impl Iterator<Item = &Ring> {
+        self.towers.iter().filter(|r| r.is_mobile())
+    }
+
+    /// Finds every [`Ring`] whose [`Frequency`] is more than `max_cents` away from its stated
+    /// [`Note`] (see [`Ring::note_mismatch`]).
+    pub fn note_mismatches(&self, max_cents: f64) -> Vec<NoteMismatch> {
+        self.towers
+            .iter()
+            .filter_map(|r| r.note_mismatch(max_cents))
+            .collect()
+    }
+}
+
+impl IntoIterator for Doves {
+    type Item = Ring;
+    type IntoIter = std::vec::IntoIter<Ring>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.towers.into_iter()
+    }
+}
+
+impl<'d> IntoIterator for &'d Doves {
+    type Item = &'d Ring;
+    type IntoIter = std::slice::Iter<'d, Ring>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'d> IntoIterator for &'d mut Doves {
+    type Item = &'d mut Ring;
+    type IntoIter = std::slice::IterMut<'d, Ring>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl From<Vec<Ring>> for Doves {
+    fn from(towers: Vec<Ring>) -> Self {
+        Self {
+            metadata: Metadata {
+                record_count: towers.len(),
+                ..Metadata::default()
+            },
+            towers,
+            redirects: Redirects::default(),
+            enrichments: Enrichments::default(),
+            indices: Indices::default(),
+        }
+    }
+}
+
+impl FromIterator<Ring> for Doves {
+    fn from_iter<I: IntoIterator<Item = Ring>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl Extend<Ring> for Doves {
+    fn extend<I: IntoIterator<Item = Ring>>(&mut self, iter: I) {
+        self.invalidate_indices();
+        self.towers.extend(iter);
+    }
+}
+
+impl Index<usize> for Doves {
+    type Output = Ring;
+
+    fn index(&self, index: usize) -> &Ring {
+        &self.towers[index]
+    }
+}
+
+impl IndexMut<usize> for Doves {
+    fn index_mut(&mut self, index: usize) -> &mut Ring {
+        self.invalidate_indices();
+        &mut self.towers[index]
+    }
 }
 
 /// A `Ring` of bells in Dove's Guide.  Note that the same tower could contain multiple `Ring`s.
-#[derive(Debug, Clone, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(try_from = "RingRecord"))]
 pub struct Ring {
-    /// The Dove's tower ID.  This is unique for each tower and will never change between updates
-    /// to Dove's Guide.
+    /// The Dove's [`TowerId`].  This is unique for each tower and will never change between
+    /// updates to Dove's Guide.  Note that towers containing multiple `Ring`s will have several
+    /// `Ring`s with the same `id`.
     ///
     /// **CSV Header**: `TowerID`
-    #[serde(rename = "TowerID")]
-    pub id: usize,
+    pub id: TowerId,
     /// What type of ring this is.
     ///
     /// **CSV Header**: `RingType`
-    #[serde(rename = "RingType")]
     pub ring_type: RingType,
-    #[serde(rename = "Bells")]
-    pub bells: usize,
-    /// `true` if the set of bells cannot be safely rung.
-    ///
-    /// **CSV Header**: `UR`; `""` if `false`, `"u/r"` if `true`
-    #[serde(rename = "UR", deserialize_with = "deser_not_empty")]
-    pub unringable: bool,
-    /// `true` if the set of bells are rung from the floor.
-    ///
-    /// **CSV Header**: `GF`; `""` if `false`, `"GF"` if `true`
-    #[serde(rename = "GF", deserialize_with = "deser_not_empty")]
-    pub ground_floor: bool,
-    /// `true` if the church has toilet facilities.
-    ///
-    /// **CSV Header**: `Toilet`; `""` if `false`, `"T"` if `true`
-    #[serde(rename = "Toilet", deserialize_with = "deser_not_empty")]
-    pub toilet: bool,
-    /// `true` if the bells can be rung without making a sound using a simulator.
-    ///
-    /// **CSV Header**: `Toilet`; `""` if `false`, `"T"` if `true`
-    #[serde(rename = "Simulator", deserialize_with = "deser_not_empty")]
-    pub simulator: bool,
+    pub bells: u8,
+    /// Whether or not this `Ring` can be rung, and by whom.
+    ///
+    /// **CSV Header**: `UR` (`""` or `"u/r"`), refined using the restrictions in `ExtraInfo`
+    pub access: AccessStatus,
+    /// Yes/no facts about this `Ring` (whether it's rung from the ground floor, has a toilet or
+    /// has a simulator), packed into [`RingFlags`].
+    ///
+    /// **CSV Header**: `GF`, `Toilet` and `Simulator`
+    pub flags: RingFlags,
     /// Set of [`Affiliation`]s to which this tower belongs.
     ///
     /// **CSV Header**: `Affiliations`
-    #[serde(rename = "Affiliations", deserialize_with = "deser_affiliations")]
-    pub affiliations: HashSet<Affiliation>,
+    pub affiliations: BTreeSet<Affiliation>,
     /// String describing when this tower holds a practice night.
     ///
     /// **CSV Header**: `Practice`
-    #[serde(rename = "Practice")]
     pub practice: Option<String>,
 
     /// Identifier for this tower used in TowerBase.  These are not unique between every [`Tower`]
     /// object.
     ///
     /// **CSV Header**: `Practice`
-    #[serde(rename = "TowerBase")]
-    pub towerbase_id: usize,
+    pub towerbase_id: u32,
+    /// Identifier for this tower used in the Felstead database of peals.  This isn't part of
+    /// Dove's Guide, so is only set by [`Doves::set_felstead_ids`].
+    pub felstead_id: Option<u32>,
     /// Unique text identifier for towers.  Doves have deprecated this; use the `id` field instead.
     ///
     /// **CSV Header**: `Practice`
-    #[serde(rename = "DoveID")]
     #[deprecated(note = "please use `id` instead")]
-    pub dove_id: Option<String>, // PERF: Use SmolStr here to prevent allocs
+    pub dove_id: Option<SmolStr>,
 
-    /// The [`Weight`] of the heaviest bell in this `Ring`.
+    /// The [`Weight`] of the heaviest bell in this `Ring`, or `None` if Dove's doesn't know it.
     ///
-    /// **CSV Header**: `Wt`
-    #[serde(rename = "Wt", deserialize_with = "deser_weight")]
-    pub weight: Weight,
+    /// **CSV Header**: `Wt` (in lbs), combined with `App` (`"app"` if the weight is approximate)
+    pub weight: Option<Weight>,
     /// The [`Note`] of the heaviest bell in this `Ring`.
     ///
     /// **CSV Header**: `Note`
-    #[serde(rename = "Note", deserialize_with = "deser_option_note")]
     pub note: Option<Note>,
-    /// The frequency (in Hz) of the heaviest bell in this `Ring`.
+    /// The measured [`Frequency`] of the heaviest bell in this `Ring`.  This should be close to
+    /// `note`, but Dove's doesn't guarantee this.
     ///
     /// **CSV Header**: `Hz`
-    #[serde(rename = "Hz")]
-    pub freq: Option<f64>, // TODO: Is this linked with `note`
+    pub freq: Option<Frequency>,
 
-    /// Details about this ring.  This is either `"P"` or `"C"`, and I don't know what this means.
+    /// How complete Dove's records of the individual bells in this `Ring` are.
     ///
-    /// **CSV Header**: `Details`
-    #[serde(rename = "Details")]
+    /// **CSV Header**: `Details`; `"C"` for complete, `"P"` for partial
     pub details: Details,
     /// List of extra pieces of information about this `Ring`.
     ///
     /// **CSV Header**: `ExtraInfo`, semicolon-delimited list
-    #[serde(rename = "ExtraInfo", deserialize_with = "deser_semicolon_list")]
-    pub extra_info: Vec<String>,
+    pub extra_info: Vec<RingFeature>,
     /// URL to the web-page of this `Ring`
     ///
     /// **CSV Header**: `WebPage`
-    #[serde(rename = "WebPage")]
     pub url: Option<String>,
 
-    /// '+'-delimited list of semitones bells in this `Ring`.
+    /// '+'-delimited list of semitones bells in this `Ring` (parsed by [`Ring::semitone_bells`]).
     ///
     /// **CSV Header**: `Semitones`, '+'-delimited list
-    #[serde(rename = "Semitones")]
-    pub semitones: Option<String>, // TODO: Parse this into a struct
-    /// TODO: What does this do?
-    ///
-    /// **CSV Header**: `App`; `""` for `false`, `"app"` for `true`.
-    #[serde(rename = "App", deserialize_with = "deser_not_empty")]
-    pub app: bool,
+    pub semitones: Option<Box<str>>,
 
     // TODO: Figure out what values are legal
     /// The name of the place which contains this `Ring`.  If the ring is mobile, then this is set
     /// to the name of the `Ring`.
     ///
     /// **CSV Header**: `Place`
-    #[serde(rename = "Place")]
-    pub place: String,
+    pub place: SmolStr,
     /// An alternative place name for this `Ring`, more specific than `place`.
     ///
     /// **CSV Header**: `Place2`
-    #[serde(rename = "Place2")]
-    pub place2: Option<String>,
+    pub place2: Option<Box<str>>,
     /// The name of this `Ring` used in county lists.
     ///
     /// **CSV Header**: `PlaceCL`
-    #[serde(rename = "PlaceCL")]
-    pub place_county_list: Option<String>,
-    #[serde(rename = "County")]
-    pub county: Option<String>,
-    #[serde(rename = "Country")]
-    pub country: Option<String>,
-    #[serde(rename = "ISO3166code")]
-    pub iso_3166_code: Option<String>,
-    #[serde(rename = "NG")]
-    pub os_grid_ref: Option<String>, // TODO: Parse this into a struct?
-    #[serde(rename = "Postcode")]
-    pub postcode: Option<String>, // TODO: Parse this into a struct?
-
-    #[serde(rename = "Long")]
-    pub long: Option<f64>,
-    #[serde(rename = "Lat")]
-    pub lat: Option<f64>,
-
-    // TODO: What are these for?
-    #[serde(rename = "SNLong")]
-    pub satnav_long: Option<f64>,
-    #[serde(rename = "SNLat")]
-    pub satnav_lat: Option<f64>,
-
-    #[serde(rename = "OvhaulYr")]
-    pub overhaul_year: Option<usize>,
-    #[serde(rename = "Contractor")]
-    pub contractor: Option<String>, // TODO: Is this linked to `overhaul_yr`
-    #[serde(rename = "TuneYr")]
-    pub tune_year: Option<usize>,
-
-    #[serde(rename = "BldgID")]
-    pub building_id: Option<usize>, // TODO: Can this be empty
-    #[serde(rename = "LGrade")]
-    pub building_grade: Option<String>, // TODO: Make a struct for this?
-
-    #[serde(rename = "ChurchCare")]
-    pub church_care: Option<usize>, // TODO: What is this?
-
-    #[serde(rename = "Dedicn")]
-    pub dedication: String, // TODO: Is this optional?
-    #[serde(rename = "AltName")]
-    pub alt_name: Option<String>,
-    #[serde(rename = "Diocese")]
-    pub diocese: Option<String>, // TODO: Could this be an enum?
+    pub place_county_list: Option<Box<str>>,
+    pub county: Option<Symbol>,
+    /// The [`Country`] containing this `Ring`, as named by Dove's Guide.
+    ///
+    /// **CSV Header**: `Country`
+    pub country: Option<Country>,
+    /// The ISO 3166-1 [`CountryCode`] of the state containing this `Ring`.
+    ///
+    /// **CSV Header**: `ISO3166code`
+    pub iso_3166_code: Option<CountryCode>,
+    /// The national grid reference of this `Ring`'s tower, exactly as written in Dove's Guide.  Use
+    /// [`Ring::grid_ref`] to parse it.
+    ///
+    /// **CSV Header**: `NG`
+    pub os_grid_ref: Option<SmolStr>,
+    /// The [`Postcode`] of the church containing this `Ring`.
+    ///
+    /// **CSV Header**: `Postcode`
+    pub postcode: Option<Postcode>,
+
+    /// The [`Coordinates`] of this `Ring`'s tower.
+    ///
+    /// **CSV Header**: `Lat` and `Long`
+    pub coordinates: Option<Coordinates>,
+    /// The [`Coordinates`] to use for sat-navs, if different to `coordinates` (e.g. if the tower
+    /// is best reached from a car park some distance away).
+    ///
+    /// **CSV Header**: `SNLat` and `SNLong`
+    pub satnav_coordinates: Option<Coordinates>,
+
+    /// The year this `Ring` was last overhauled.  Dove's sometimes gives a full date, in which case
+    /// only the year is kept.  Years before 1500 or in the future are dropped when loading (and
+    /// reported as [`LoadWarning`]s by [`Loader`]).
+    ///
+    /// **CSV Header**: `OvhaulYr`
+    pub overhaul_year: Option<u16>,
+    /// The [`Contractor`] who carried out the overhaul in `overhaul_year`.
+    ///
+    /// **CSV Header**: `Contractor`
+    pub contractor: Option<Contractor>,
+    /// The year this `Ring` was last tuned.  Years before 1500 or in the future are dropped when
+    /// loading (and reported as [`LoadWarning`]s by [`Loader`]).
+    ///
+    /// **CSV Header**: `TuneYr`
+    pub tune_year: Option<u16>,
+
+    /// The ID of the church in its country's register of listed buildings (e.g. `1295154` for
+    /// Historic England, or `LB19957` for Historic Environment Scotland).
+    ///
+    /// **CSV Header**: `BldgID`; `"None"` is treated as empty
+    pub building_id: Option<SmolStr>,
+    /// The listed building grade of the church (e.g. `II*`).
+    ///
+    /// **CSV Header**: `LGrade`; `"None"` is treated as empty
+    pub building_grade: Option<SmolStr>, // TODO: Make a struct for this?
+
+    /// The ID of the church in its denomination's buildings database, if it has one.
+    ///
+    /// **CSV Header**: `ChurchCare` (interpreted using `Diocese` and `Country`)
+    pub church_care: Option<ChurchCareId>,
+
+    /// The [`Dedication`] of the church containing this `Ring`.
+    ///
+    /// **CSV Header**: `Dedicn`
+    pub dedication: Dedication,
+    pub alt_name: Option<Box<str>>,
+    /// The [`Diocese`] containing this `Ring`, if any.
+    ///
+    /// **CSV Header**: `Diocese`
+    pub diocese: Option<Diocese>,
+}
+
+impl Ring {
+    /// The [`RingKey`] which uniquely identifies this `Ring`
+    pub fn key(&self) -> RingKey {
+        RingKey {
+            tower_id: self.id,
+            towerbase_id: self.towerbase_id,
+        }
+    }
+
+    /// The number of bells which can currently be rung: [`Ring::bells`] if the `Ring` is
+    /// [ringable](Ring::is_ringable), or `0` if ringing is prohibited or the bells are unringable
+    pub fn ringable_bells(&self) -> u8 {
+        if self.is_ringable() {
+            self.bells
+        } else {
+            0
+        }
+    }
+
+    /// `true` if the bells are rung from the ground floor
+    pub fn ground_floor(&self) -> bool {
+        self.flags.contains(RingFlags::GROUND_FLOOR)
+    }
+
+    /// `true` if the church has toilet facilities
+    pub fn toilet(&self) -> bool {
+        self.flags.contains(RingFlags::TOILET)
+    }
+
+    /// `true` if the bells can be rung without making a sound using a simulator
+    pub fn simulator(&self) -> bool {
+        self.flags.contains(RingFlags::SIMULATOR)
+    }
+
+    /// `true` if this `Ring` is in the United Kingdom (including Northern Ireland, but not the
+    /// Crown Dependencies).
+    pub fn is_uk(&self) -> bool {
+        self.iso_3166_code.is_some_and(CountryCode::is_uk)
+    }
+
+    /// `true` if this is a mobile `Ring`, and therefore has no fixed location.  Dove's marks
+    /// these by setting the dedication to `"Mobile Ring"` (and the place to the name of the
+    /// `Ring`), leaving all the location fields empty.
+    pub fn is_mobile(&self) -> bool {
+        self.dedication
+            .as_str()
+            .eq_ignore_ascii_case(MOBILE_RING_DEDICATION)
+            || (self.coordinates.is_none() && self.county.is_none() && self.postcode.is_none())
+    }
+
+    /// `true` if this `Ring` can currently be rung by visiting bands, possibly with restrictions
+    pub fn is_ringable(&self) -> bool {
+        self.access.is_ringable()
+    }
+
+    /// `true` if this `Ring`'s `extra_info` contains `feature`
+    pub fn has_feature(&self, feature: &RingFeature) -> bool {
+        self.extra_info.contains(feature)
+    }
+
+    /// `true` if this `Ring` is hung anticlockwise
+    pub fn is_anticlockwise(&self) -> bool {
+        self.has_feature(&RingFeature::Anticlockwise)
+    }
+
+    /// `true` if this `Ring`'s ropes fall in a straight line rather than a circle
+    pub fn has_straight_line_ropes(&self) -> bool {
+        self.has_feature(&RingFeature::RopesFallInStraightLine)
+    }
+
+    /// `true` if this `Ring` is in a tower which is detached from its church
+    pub fn is_detached_tower(&self) -> bool {
+        self.has_feature(&RingFeature::DetachedTower)
+    }
+
+    /// The best [`Coordinates`] to use for navigating to this `Ring`, along with where they came
+    /// from.  The sat-nav coordinates are preferred (since they point to where visitors should
+    /// arrive), falling back to the tower's own coordinates.
+    pub fn best_coordinates(&self) -> Option<(Coordinates, CoordinateSource)> {
+        match (self.satnav_coordinates, self.coordinates) {
+            (Some(c), _) => Some((c, CoordinateSource::SatNav)),
+            (None, Some(c)) => Some((c, CoordinateSource::Tower)),
+            (None, None) => None,
+        }
+    }
+
+    /// Checks this `Ring`'s [`Frequency`] against its stated [`Note`], returning a
+    /// [`NoteMismatch`] if they are more than `max_cents` apart.  Rings without both a note and a
+    /// frequency are never reported.
+    pub fn note_mismatch(&self, max_cents: f64) -> Option<NoteMismatch> {
+        let note = self.note?;
+        let freq = self.freq?;
+        let cents = freq.cents_from(note);
+        (cents.abs() > max_cents).then(|| NoteMismatch {
+            id: self.id,
+            place: self.place.clone(),
+            note,
+            freq,
+            cents,
+            nearest_note: freq.nearest_note().0,
+        })
+    }
+
+    /// The ISO 3166-2 [`SubdivisionCode`] of the region containing this `Ring`, if known.  This is
+    /// currently only known for the four countries of the UK.
+    pub fn iso_3166_subdivision(&self) -> Option<SubdivisionCode> {
+        self.country.as_ref()?.subdivision(self.iso_3166_code?)
+    }
+}
+
+/// Formats a `Ring` as a compact one-line summary of its name, number of bells, tenor weight and
+/// note (e.g. `Oxford, S Mary Magd — 10, 14-0-7 in F♯`).  Any unknown parts are left out.
+impl Display for Ring {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} — {}", self.display_name(NameStyle::Dove), self.bells)?;
+        if let Some(weight) = self.weight {
+            write!(f, ", {}", weight)?;
+        }
+        if let Some(note) = self.note {
+            let separator = if self.weight.is_some() { " in" } else { "," };
+            write!(f, "{} {}", separator, note)?;
+        }
+        Ok(())
+    }
+}
+
+/// `Ring`s are compared by their [`RingKey`], so two copies of the same `Ring` are equal even if
+/// one has been edited.
+impl PartialEq for Ring {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Ring {}
+
+impl Hash for Ring {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl PartialOrd for Ring {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// `Ring`s are ordered by their [`RingKey`] (i.e. by [`TowerId`], then by TowerBase ID)
+impl Ord for Ring {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// The key which uniquely identifies a [`Ring`].  A [`TowerId`] isn't enough on its own, because
+/// towers can contain several `Ring`s; these are distinguished by their TowerBase IDs.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct RingKey {
+    pub tower_id: TowerId,
+    pub towerbase_id: u32,
+}
+
+impl Display for RingKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.tower_id, self.towerbase_id)
+    }
+}
+
+/// The unique identifier given to each tower by Dove's Guide.  This is kept separate from the other
+/// numeric IDs (e.g. [`Ring::towerbase_id`]) so that they can't be mixed up.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct TowerId(u32);
+
+impl TowerId {
+    /// Creates a `TowerId` from its numeric value
+    pub fn new(id: u32) -> Self {
+        Self(id)
+    }
+
+    /// Gets the numeric value of this `TowerId`
+    pub fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for TowerId {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl Display for TowerId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for TowerId {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
 }
 
 /// The possible types of a rings documented in Dove's Guide.
-#[derive(Debug, Clone, Deserialize)]
-#[serde(field_identifier)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum RingType {
-    #[serde(rename = "Full circle ring")]
+    /// A ring hung for full-circle ringing.
+    ///
+    /// **Dove's value:** `Full circle ring`
     FullCircle,
+    /// A carillon, played from a keyboard.
+    ///
+    /// **Dove's value:** `Carillon`
     Carillon,
+    /// A chime of bells hung dead or for chiming only.
+    ///
+    /// **Dove's value:** `Chime`
+    Chime,
+    /// Any ring type not covered by the other variants, stored exactly as it appears in Dove's.
+    Other(String),
 }
 
-/// The `Weight` of the heaviest bell in a [`Ring`]
-///
-/// TODO: Add more methods for this
-#[derive(Debug, Clone)]
-pub struct Weight {
-    lbs: f64,
+impl RingType {
+    /// The string used for this `RingType` in Dove's Guide
+    pub fn dove_name(&self) -> &str {
+        match self {
+            RingType::FullCircle => "Full circle ring",
+            RingType::Carillon => "Carillon",
+            RingType::Chime => "Chime",
+            RingType::Other(s) => s,
+        }
+    }
 }
 
-/// An organisation to which a tower can be affiliated.
+impl Display for RingType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.dove_name())
+    }
+}
+
+impl FromStr for RingType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "Full circle ring" => RingType::FullCircle,
+            "Carillon" => RingType::Carillon,
+            "Chime" => RingType::Chime,
+            _ => RingType::Other(s.to_owned()),
+        })
+    }
+}
+
+/// An organisation to which a tower can be affiliated.  Affiliations without their own variant are
+/// stored as [`Affiliation::Other`].
 ///
 /// TODO: Make this a bit-field.
 /// TODO: Populate this.  Options are:
@@ -204,7 +791,7 @@ pub struct Weight {
 /// 'ANZAB', 'DDA', 'Salis', 'ULSCR', 'PDG', 'Bev&D', 'NWA', 'CovDG', 'Irish', 'Middx',
 /// 'Salop', 'CheDG', 'D&N', 'WDA', 'NAG', 'DCA', 'SAG', 'SRCY', 'ASCY', 'DevAs', 'SDDG', 'KCA',
 /// 'Zimb', 'Scot', 'GDR', 'S&B', 'LWAS'}
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub enum Affiliation {
     /* UNIVERSITY SOCIETIES */
     /// Cambridge University Guild
@@ -229,136 +816,150 @@ pub enum Affiliation {
     ///
     /// **Dove's value:** `Surr`
     Surrey,
-}
 
-/// Details related to any given tower.  I have no idea what this means.  TODO: Find out
-#[derive(Debug, Clone, Deserialize)]
-#[serde(field_identifier)]
-pub enum Details {
-    P,
-    C,
+    /// Any affiliation not covered by the other variants, stored exactly as it appears in Dove's.
+    Other(Symbol),
 }
 
-///////////
-// NOTES //
-///////////
-
-const FLAT: char = '♭';
-const SHARP: char = '♯';
-
-/// The `Weight` of the heaviest bell in a [`Ring`]
-#[derive(Debug, Clone)]
-pub struct Note {
-    pub name: NoteName,
-    pub accidental: Accidental,
+impl Affiliation {
+    /// The string used for this `Affiliation` in Dove's Guide
+    pub fn dove_code(&self) -> &str {
+        match self {
+            Affiliation::CambridgeUni => "CUG",
+            Affiliation::ManchesterUni => "MUG",
+            Affiliation::OxfordUni => "OUS",
+            Affiliation::OxfordDiocese => "ODG",
+            Affiliation::Surrey => "Surr",
+            Affiliation::Other(s) => s,
+        }
+    }
 }
 
-impl Display for Note {
+impl Display for Affiliation {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.name, self.accidental)
+        write!(f, "{}", self.dove_code())
     }
 }
 
-/// The name of a root [`Note`] (i.e. `A` to `G`)
-#[derive(Debug, Clone)]
-pub enum NoteName {
-    A,
-    B,
-    C,
-    D,
-    E,
-    F,
-    G,
+impl FromStr for Affiliation {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "CUG" => Affiliation::CambridgeUni,
+            "MUG" => Affiliation::ManchesterUni,
+            "OUS" => Affiliation::OxfordUni,
+            "ODG" => Affiliation::OxfordDiocese,
+            "Surr" => Affiliation::Surrey,
+            _ => Affiliation::Other(Symbol::new(s)),
+        })
+    }
 }
 
-impl Display for NoteName {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self) // Display using the note names
+/// Whether or not a [`Ring`] can be rung.  Dove's only marks rings as unringable (`u/r`) in the
+/// `UR` column, but distinguishes between prohibited and restricted ringing in `ExtraInfo`, so these
+/// are combined into one status.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum AccessStatus {
+    /// The `Ring` can be rung normally.
+    Ringable,
+    /// The `Ring` can be rung, but only in a limited way (e.g. only by invitees, or not by visiting
+    /// bands).
+    ///
+    /// **Dove's values:** `Only limited ringing allowed`, `Ringing restricted (invitees only)` or
+    /// `No visiting bands at present` in `ExtraInfo`
+    Restricted,
+    /// The bells are physically ringable, but ringing isn't currently allowed.
+    ///
+    /// **Dove's values:** `Ringing prohibited`, `Ringing currently not permitted` or `Ringing
+    /// temporarily suspended` in `ExtraInfo`
+    Prohibited,
+    /// The bells can't be rung at all.
+    ///
+    /// **Dove's value:** `u/r` in `UR`
+    Unringable,
+}
+
+impl AccessStatus {
+    /// Combines Dove's `UR` flag with any restrictions listed in a `Ring`'s [`RingFeature`]s.
+    /// The most restrictive status wins.
+    pub(crate) fn from_dove(unringable: bool, features: &[RingFeature]) -> Self {
+        if unringable {
+            return AccessStatus::Unringable;
+        }
+        features
+            .iter()
+            .map(|feature| match feature {
+                RingFeature::RingingProhibited
+                | RingFeature::RingingNotPermitted
+                | RingFeature::RingingSuspended => AccessStatus::Prohibited,
+                RingFeature::LimitedRinging
+                | RingFeature::RingingRestricted
+                | RingFeature::NoVisitingBands => AccessStatus::Restricted,
+                _ => AccessStatus::Ringable,
+            })
+            .max()
+            .unwrap_or(AccessStatus::Ringable)
+    }
+
+    /// `true` if the `Ring` can currently be rung, possibly with restrictions
+    pub fn is_ringable(self) -> bool {
+        matches!(self, AccessStatus::Ringable | AccessStatus::Restricted)
     }
 }
 
-/// An accidental to be applied to a [`NoteName`] to create a specific [`Note`].
-#[derive(Debug, Clone)]
-pub enum Accidental {
-    Flat,
-    Natural,
-    Sharp,
+/// How complete Dove's records of the individual bells in a [`Ring`] are.  Rings with complete
+/// details always have an exact tenor weight and frequency; rings with partial details may have
+/// approximate weights or no frequency at all.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Details {
+    /// Dove's has full details (weights, notes, etc.) of every bell in the `Ring`.
+    ///
+    /// **Dove's value:** `C`
+    Complete,
+    /// Dove's only has partial details about the bells in the `Ring`.
+    ///
+    /// **Dove's value:** `P`
+    Partial,
+    /// Any value not covered by the other variants, stored exactly as it appears in Dove's.
+    Other(String),
 }
 
-impl Display for Accidental {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+impl Details {
+    /// `true` if Dove's has complete details of every bell in the [`Ring`]
+    pub fn is_complete(&self) -> bool {
+        matches!(self, Details::Complete)
+    }
+
+    /// `true` if Dove's only has partial details of the bells in the [`Ring`]
+    pub fn is_partial(&self) -> bool {
+        matches!(self, Details::Partial)
+    }
+
+    /// The string used for this `Details` in Dove's Guide
+    pub fn dove_code(&self) -> &str {
         match self {
-            Accidental::Flat => write!(f, "{}", FLAT),
-            Accidental::Natural => Ok(()),
-            Accidental::Sharp => write!(f, "{}", SHARP),
+            Details::Complete => "C",
+            Details::Partial => "P",
+            Details::Other(s) => s,
         }
     }
 }
 
-/////////////////////////////
-// DESERIALIZATION HELPERS //
-/////////////////////////////
-
-/// Serializes as `false` if the next string is empty and `true` otherwise.
-fn deser_not_empty<'de, D>(de: D) -> Result<bool, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    <&str>::deserialize(de).map(str::is_empty)
-}
-
-/// Serializes the next string as a `;`-delimited sequence of [`Affiliation`]s
-fn deser_affiliations<'de, D>(de: D) -> Result<HashSet<Affiliation>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    todo!()
-}
-
-/// Serializes the next string as a `;`-delimited sequence of strings
-fn deser_semicolon_list<'de, D>(de: D) -> Result<Vec<String>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    <&str>::deserialize(de).map(|s| s.split(';').map(str::to_owned).collect())
-}
-
-/// Serializes the next string as a weight in pounds
-fn deser_weight<'de, D>(de: D) -> Result<Weight, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    f64::deserialize(de).map(|lbs| Weight { lbs })
-}
-
-/// Serializes the next string as a note, or `None` if the string is empty
-fn deser_option_note<'de, D>(de: D) -> Result<Option<Note>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let s = <&str>::deserialize(de)?;
-    let mut chars = s.chars();
-    let name = match chars.next() {
-        None => return Ok(None), // Empty strings correspond to `None`
-        Some('A') => NoteName::A,
-        Some('B') => NoteName::B,
-        Some('C') => NoteName::C,
-        Some('D') => NoteName::D,
-        Some('E') => NoteName::E,
-        Some('F') => NoteName::F,
-        Some('G') => NoteName::G,
-        Some(c) => return Err(D::Error::custom(format!("Char {:?} is not a note name", c))),
-    };
-    let accidental = match chars.next() {
-        Some('♭') | Some('b') => Accidental::Flat,
-        Some('♮') | None => Accidental::Natural,
-        Some('♯') | Some('#') => Accidental::Sharp,
-        Some(c) => {
-            return Err(D::Error::custom(format!(
-                "Char {:?} is not an accidental name",
-                c
-            )))
-        }
-    };
-    Ok(Some(Note { name, accidental }))
+impl Display for Details {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.dove_code())
+    }
+}
+
+impl FromStr for Details {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "C" => Details::Complete,
+            "P" => Details::Partial,
+            _ => Details::Other(s.to_owned()),
+        })
+    }
 }
@@ -0,0 +1,248 @@
+//! Checks for likely mistakes in the data of Dove's Guide, for generating lists of corrections.
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+use crate::{
+    duplicate::place_key, CountryCode, Distance, Doves, Postcode, Ring, RingType, SmolStr, TowerId,
+};
+
+/// The range of bell counts which are normal for rings hung for full-circle ringing
+const USUAL_BELLS: std::ops::RangeInclusive<u8> = 3..=16;
+/// How far a `Ring`'s coordinates can be from its grid reference before [`Doves::validate`]
+/// reports a [`LintKind::GridRefMismatch`]
+const MAX_GRID_REF_DISTANCE: Distance = Distance::from_km(1.0);
+
+/// How serious a [`Lint`] is
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    /// Something unusual, which is often correct
+    Info,
+    /// Something which is probably a mistake
+    Warning,
+    /// Something which can't be correct
+    Error,
+}
+
+/// The kinds of problem which can be found by [`Doves::validate`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintKind {
+    /// The `Ring` has no coordinates (mobile rings are exempt)
+    MissingCoordinates,
+    /// The tenor's weight is given as zero
+    ZeroWeight,
+    /// The number of bells is outside the usual range of 3 to 16
+    UnusualBellCount(u8),
+    /// The practice night description doesn't give any regular sessions, either because
+    /// [`Ring::practice_schedule`](crate::Ring::practice_schedule) can't parse it or because it's
+    /// only a note (e.g. `PN: by arrangement`)
+    UnparsedPractice(String),
+    /// Another `Ring` with a different [`TowerId`] has the same place, county and dedication
+    DuplicatePlace(TowerId),
+    /// The `Ring`'s coordinates are this far from the centre of its
+    /// [grid reference](crate::Ring::grid_ref), so at least one of them is probably wrong
+    GridRefMismatch(Distance),
+    /// The [`Postcode`] isn't in the format used by the `Ring`'s
+    /// [ISO 3166 country](crate::Ring::iso_3166_code) (see [`Postcode::is_valid_for`])
+    PostcodeMismatch {
+        postcode: Postcode,
+        country: CountryCode,
+    },
+}
+
+/// A likely mistake in the data of one [`Ring`], found by [`Doves::validate`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint {
+    /// The [`TowerId`] of the `Ring` with the problem
+    pub id: TowerId,
+    /// The place name of the `Ring`, to make reports easier to read
+    pub place: SmolStr,
+    pub severity: Severity,
+    pub kind: LintKind,
+}
+
+impl Doves {
+    /// Checks every [`Ring`] for likely mistakes in the data (see [`LintKind`] for the checks),
+    /// returning the problems in the order the `Ring`s appear in Dove's Guide.  Coordinates are
+    /// reported as mismatching their grid reference if they're more than 1km apart (use
+    /// [`Doves::grid_ref_mismatches`] to choose a different distance).
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn validate(&self) -> Vec<Lint> {
+        let mut lints = Vec::new();
+        let mut places = HashMap::<(String, Option<&str>, Option<&str>, &str), TowerId>::new();
+        for ring in &self.towers {
+            let mut lint = |severity, kind| lints.push(Lint::new(ring, severity, kind));
+
+            if ring.best_coordinates().is_none() && !ring.is_mobile() {
+                lint(Severity::Warning, LintKind::MissingCoordinates);
+            }
+            if ring.weight.is_some_and(|w| w.lbs() == 0.0) {
+                lint(Severity::Error, LintKind::ZeroWeight);
+            }
+            if !USUAL_BELLS.contains(&ring.bells) {
+                // Carillons and chimes often have many more bells than a ring
+                let severity = match ring.ring_type {
+                    RingType::FullCircle => Severity::Warning,
+                    _ => Severity::Info,
+                };
+                lint(severity, LintKind::UnusualBellCount(ring.bells));
+            }
+            if let Some(practice) = &ring.practice {
+                let has_sessions = ring
+                    .practice_schedule()
+                    .is_some_and(|schedule| !schedule.sessions.is_empty());
+                if !practice.trim().is_empty() && !has_sessions {
+                    lint(Severity::Info, LintKind::UnparsedPractice(practice.clone()));
+                }
+            }
+
+            let first_id = *places.entry(place_key(ring)).or_insert(ring.id);
+            if first_id != ring.id {
+                lint(Severity::Warning, LintKind::DuplicatePlace(first_id));
+            }
+            if let Some(mismatch) = grid_ref_mismatch(ring, MAX_GRID_REF_DISTANCE) {
+                lint(Severity::Warning, mismatch);
+            }
+            if let (Some(postcode), Some(country)) = (&ring.postcode, ring.iso_3166_code) {
+                if postcode.is_valid_for(country) == Some(false) {
+                    let kind = LintKind::PostcodeMismatch {
+                        postcode: postcode.clone(),
+                        country,
+                    };
+                    lint(Severity::Warning, kind);
+                }
+            }
+        }
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            rings = self.towers.len(),
+            lints = lints.len(),
+            errors = lints
+                .iter()
+                .filter(|l| l.severity == Severity::Error)
+                .count(),
+            "validated Dove's Guide"
+        );
+        lints
+    }
+
+    /// Finds every [`Ring`] whose [coordinates](Ring::coordinates) are more than `max_distance`
+    /// from the centre of its [grid reference](Ring::grid_ref), as [`LintKind::GridRefMismatch`]
+    /// warnings.  Grid references are usually to the nearest 100m, so `max_distance` should be
+    /// at least 100m.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(max_km = max_distance.km()))
+    )]
+    pub fn grid_ref_mismatches(&self, max_distance: Distance) -> Vec<Lint> {
+        let lints = self
+            .towers
+            .iter()
+            .filter_map(|ring| {
+                let kind = grid_ref_mismatch(ring, max_distance)?;
+                Some(Lint::new(ring, Severity::Warning, kind))
+            })
+            .collect::<Vec<_>>();
+        #[cfg(feature = "tracing")]
+        tracing::info!(mismatches = lints.len(), "checked grid references");
+        lints
+    }
+}
+
+fn grid_ref_mismatch(ring: &Ring, max_distance: Distance) -> Option<LintKind> {
+    let distance = ring.grid_ref_distance()?;
+    (distance > max_distance).then_some(LintKind::GridRefMismatch(distance))
+}
+
+impl Lint {
+    fn new(ring: &Ring, severity: Severity, kind: LintKind) -> Self {
+        Self {
+            id: ring.id,
+            place: ring.place.clone(),
+            severity,
+            kind,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+impl Display for LintKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintKind::MissingCoordinates => write!(f, "no coordinates"),
+            LintKind::ZeroWeight => write!(f, "tenor weight is zero"),
+            LintKind::UnusualBellCount(bells) => write!(f, "unusual number of bells ({})", bells),
+            LintKind::UnparsedPractice(practice) => {
+                write!(f, "no regular practice sessions in {:?}", practice)
+            }
+            LintKind::DuplicatePlace(id) => write!(f, "same place and dedication as tower {}", id),
+            LintKind::GridRefMismatch(distance) => {
+                write!(f, "coordinates are {} from the grid reference", distance)
+            }
+            LintKind::PostcodeMismatch { postcode, country } => {
+                write!(
+                    f,
+                    "postcode {} isn't in the format used in {}",
+                    postcode, country
+                )
+            }
+        }
+    }
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} ({}): {}",
+            self.severity, self.place, self.id, self.kind
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LintKind;
+    use crate::{Doves, RingBuilder, TowerId};
+
+    /// The [`LintKind::UnparsedPractice`] lints of a `Ring` with the given practice description
+    fn practice_lints(practice: &str) -> Vec<LintKind> {
+        let ring = RingBuilder::new(TowerId::new(1), "Anytown", 6)
+            .practice(practice)
+            .build()
+            .unwrap();
+        Doves::from(vec![ring])
+            .validate()
+            .into_iter()
+            .map(|lint| lint.kind)
+            .filter(|kind| matches!(kind, LintKind::UnparsedPractice(_)))
+            .collect()
+    }
+
+    #[test]
+    fn practice_with_sessions() {
+        assert!(practice_lints("Mon 19:30").is_empty());
+        assert!(practice_lints("Thu (1st, 3rd) 19:30 & Sat (alt)").is_empty());
+    }
+
+    #[test]
+    fn practice_without_sessions() {
+        for practice in ["PN: by arrangement", "PN:", "Mondays", "Tue 7.30pm"] {
+            assert_eq!(
+                practice_lints(practice),
+                [LintKind::UnparsedPractice(practice.to_owned())]
+            );
+        }
+    }
+}
@@ -0,0 +1,450 @@
+//! Loading Dove's Guide with a choice of which malformed values are fatal, and which are replaced
+//! with `None` and reported as [`LoadWarning`]s.
+
+use std::{
+    collections::BTreeSet,
+    convert::TryFrom,
+    fmt::{Display, Formatter},
+    fs::File,
+    io::Read,
+    path::Path,
+};
+
+use csv::{ErrorKind, StringRecord};
+
+use crate::{
+    metadata,
+    record::{is_plausible_year, RingRecord},
+    Column, Country, Doves, GridRef, IndexKind, Ring, TowerId,
+};
+
+/// A group of related columns of Dove's CSV file whose values a [`Loader`] can be
+/// [lenient](Loader::lenient) about.  Columns which identify or define a ring (e.g. `TowerID`,
+/// `Bells` or `TowerBase`) aren't in any group, so are always parsed strictly.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum FieldGroup {
+    /// The tenor's weight, note and frequency (`Wt`, `Note` and `Hz`)
+    Tuning,
+    /// The location of the tower (`Lat`, `Long`, `SNLat`, `SNLong` and `NG`).  Grid references
+    /// are only checked if this group is lenient, and never in the Channel Islands: Dove's Guide
+    /// gives UTM references there (e.g. `WV295753`), which aren't [`GridRef`]s.
+    Location,
+    /// The years of the last overhaul and tuning (`OvhaulYr` and `TuneYr`)
+    Years,
+    /// The ISO 3166 country code (`ISO3166code`)
+    Regions,
+}
+
+impl Column {
+    /// The [`FieldGroup`] containing this `Column`, or `None` if it's always parsed strictly
+    pub fn field_group(self) -> Option<FieldGroup> {
+        Some(match self {
+            Column::Weight | Column::Note | Column::Frequency => FieldGroup::Tuning,
+            Column::Lat
+            | Column::Long
+            | Column::SatnavLat
+            | Column::SatnavLong
+            | Column::OsGridRef => FieldGroup::Location,
+            Column::OverhaulYear | Column::TuneYear => FieldGroup::Years,
+            Column::Iso3166Code => FieldGroup::Regions,
+            _ => return None,
+        })
+    }
+}
+
+/// Options for loading a [`Doves`], choosing which [`FieldGroup`]s are parsed leniently.  By
+/// default every field is strict, and (as with [`Doves::from_reader`]) any malformed value fails
+/// the whole load.  A malformed value in a lenient group is instead treated as empty, and
+/// reported as a [`LoadWarning`].
+#[derive(Debug, Clone, Default)]
+pub struct Loader {
+    lenient: BTreeSet<FieldGroup>,
+}
+
+/// A [`Doves`] loaded by a [`Loader`], along with the warnings generated while loading it
+#[derive(Debug, Clone)]
+pub struct Loaded {
+    pub doves: Doves,
+    /// Every malformed value which was ignored, in the order they appear in the file
+    pub warnings: Vec<LoadWarning>,
+}
+
+/// A malformed value which a [`Loader`] ignored, either because its [`FieldGroup`] is lenient
+/// or because it's an implausible overhaul or tuning year (which never fails the load)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadWarning {
+    /// The [`TowerId`] of the row containing the value
+    pub id: TowerId,
+    /// The number of the row containing the value, where the header row is row 0 (as in the
+    /// errors from the `csv` crate)
+    pub row: u64,
+    pub column: Column,
+    /// The value which was ignored, exactly as written in the file
+    pub value: String,
+    /// Why the value couldn't be used
+    pub message: String,
+}
+
+impl Loader {
+    /// Creates a `Loader` which parses every field strictly
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treat malformed values in a [`FieldGroup`] as empty, rather than failing
+    pub fn lenient(mut self, group: FieldGroup) -> Self {
+        self.lenient.insert(group);
+        self
+    }
+
+    /// Reads a [`Doves`] from the contents of Dove's `dove.csv` file
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "load", skip_all))]
+    pub fn from_reader(&self, reader: impl Read) -> Result<Loaded, csv::Error> {
+        let mut reader = csv::Reader::from_reader(reader);
+        let headers = reader.headers()?.clone();
+        let mut towers = Vec::new();
+        let mut warnings = Vec::new();
+        for record in reader.records() {
+            towers.push(self.read_ring(record?, &headers, &mut warnings)?);
+        }
+        let mut doves = Doves::from(towers);
+        doves.metadata.schema_version = metadata::schema_version(&headers);
+        doves.ensure_index(IndexKind::Id); // Build the lookup now, rather than on the first query
+
+        #[cfg(feature = "tracing")]
+        for warning in &warnings {
+            tracing::warn!(%warning, "ignored malformed value");
+        }
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            rings = doves.len(),
+            warnings = warnings.len(),
+            "loaded Dove's Guide"
+        );
+        Ok(Loaded { doves, warnings })
+    }
+
+    /// Reads a [`Doves`] from Dove's `dove.csv` file
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(path = %path.as_ref().display()))
+    )]
+    pub fn from_path(&self, path: impl AsRef<Path>) -> Result<Loaded, csv::Error> {
+        let path = path.as_ref();
+        let mut loaded = self.from_reader(File::open(path)?)?;
+        loaded.doves.metadata.set_path(path);
+        Ok(loaded)
+    }
+
+    fn is_lenient(&self, column: Column) -> bool {
+        column
+            .field_group()
+            .is_some_and(|group| self.lenient.contains(&group))
+    }
+
+    /// Removes `ring`'s grid reference if it isn't a valid [`GridRef`] and
+    /// [`FieldGroup::Location`] is lenient, returning the ignored value
+    fn take_invalid_grid_ref(&self, ring: &mut Ring) -> Option<(Column, String, String)> {
+        if !self.is_lenient(Column::OsGridRef) || ring.country == Some(Country::ChannelIslands) {
+            return None;
+        }
+        let error = ring.os_grid_ref.as_deref()?.parse::<GridRef>().err()?;
+        let value = ring.os_grid_ref.take()?;
+        Some((Column::OsGridRef, value.to_string(), error.to_string()))
+    }
+
+    /// Finds the header of the lenient column which causes `record` to fail to deserialize with
+    /// `message`.  Errors from parsing a column's value don't say which column failed, so this
+    /// blanks each non-empty lenient column in turn until the error changes.
+    fn find_culprit<'h>(
+        &self,
+        record: &StringRecord,
+        headers: &'h StringRecord,
+        message: &str,
+    ) -> Option<&'h str> {
+        headers.iter().enumerate().find_map(|(idx, header)| {
+            let column = header.parse::<Column>().ok()?;
+            if !self.is_lenient(column) || record.get(idx)?.is_empty() {
+                return None;
+            }
+            let blanked = blank_fields(record, &[idx]);
+            let still_fails = match blanked.deserialize::<RingRecord>(Some(headers)) {
+                Err(e) => match e.kind() {
+                    ErrorKind::Deserialize { err, .. } => err.kind().to_string() == message,
+                    _ => false,
+                },
+                Ok(_) => false,
+            };
+            (!still_fails).then_some(header)
+        })
+    }
+
+    /// Reads one [`Ring`], blanking malformed values in lenient columns until it parses
+    fn read_ring(
+        &self,
+        mut record: StringRecord,
+        headers: &StringRecord,
+        warnings: &mut Vec<LoadWarning>,
+    ) -> Result<Ring, csv::Error> {
+        let mut ignored = Vec::new();
+        loop {
+            let (columns, message) = match record.deserialize::<RingRecord>(Some(headers)) {
+                Ok(ring_record) => {
+                    let years = implausible_years(&ring_record, &record, headers);
+                    match Ring::try_from(ring_record) {
+                        Ok(mut ring) => {
+                            ignored.extend(years);
+                            ignored.extend(self.take_invalid_grid_ref(&mut ring));
+                            let row = record.position().map_or(0, |pos| pos.record());
+                            warnings.extend(ignored.into_iter().map(|(column, value, message)| {
+                                LoadWarning {
+                                    id: ring.id,
+                                    row,
+                                    column,
+                                    value,
+                                    message,
+                                }
+                            }));
+                            return Ok(ring);
+                        }
+                        Err(e) => (e.headers(), e.to_string()),
+                    }
+                }
+                Err(e) => match e.kind() {
+                    ErrorKind::Deserialize { err, .. } => {
+                        let message = err.kind().to_string();
+                        let header = match err.field() {
+                            Some(idx) => headers.get(idx as usize),
+                            None => self.find_culprit(&record, headers, &message),
+                        };
+                        (header.into_iter().collect(), message)
+                    }
+                    _ => return Err(e),
+                },
+            };
+
+            // Blank the offending columns if they're all lenient, otherwise fail with the same
+            // error as `Doves::from_reader`
+            let mut indices = Vec::new();
+            for header in columns {
+                let column = header.parse::<Column>().ok();
+                let idx = headers.iter().position(|h| h == header);
+                match (column, idx) {
+                    (Some(column), Some(idx))
+                        if self.is_lenient(column) && !record[idx].is_empty() =>
+                    {
+                        ignored.push((column, record[idx].to_owned(), message.clone()));
+                        indices.push(idx);
+                    }
+                    _ => return Err(record.deserialize::<Ring>(Some(headers)).unwrap_err()),
+                }
+            }
+            if indices.is_empty() {
+                return Err(record.deserialize::<Ring>(Some(headers)).unwrap_err());
+            }
+            record = blank_fields(&record, &indices);
+        }
+    }
+}
+
+/// The year columns of `ring_record` which will be dropped when it's converted into a [`Ring`]
+/// because they're implausible.  These are reported regardless of which groups are lenient, since
+/// they never fail the load.
+fn implausible_years(
+    ring_record: &RingRecord,
+    record: &StringRecord,
+    headers: &StringRecord,
+) -> Vec<(Column, String, String)> {
+    [
+        (Column::OverhaulYear, ring_record.overhaul_year),
+        (Column::TuneYear, ring_record.tune_year),
+    ]
+    .iter()
+    .filter(|(_, year)| year.is_some_and(|y| !is_plausible_year(y)))
+    .map(|&(column, year)| {
+        let idx = headers.iter().position(|h| h == column.dove_header());
+        let value = idx.and_then(|idx| record.get(idx)).unwrap_or_default();
+        let message = format!("implausible year {}", year.unwrap());
+        (column, value.to_owned(), message)
+    })
+    .collect()
+}
+
+/// A copy of `record` with the fields at `indices` made empty
+fn blank_fields(record: &StringRecord, indices: &[usize]) -> StringRecord {
+    let mut blanked = record
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| if indices.contains(&idx) { "" } else { field })
+        .collect::<StringRecord>();
+    blanked.set_position(record.position().cloned());
+    blanked
+}
+
+impl Display for LoadWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "row {} (tower {}): ignored `{}` value {:?}: {}",
+            self.row,
+            self.id,
+            self.column.dove_header(),
+            self.value,
+            self.message
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use csv::{ErrorKind, StringRecord};
+
+    use crate::{record::RingRecord, Column, FieldGroup, Loaded, Loader};
+
+    const HEADERS: &str = "TowerID,RingType,Place,Place2,PlaceCL,Dedicn,AltName,County,Country,\
+        ISO3166code,Diocese,Lat,Long,Bells,UR,Semitones,Wt,App,Note,Hz,Details,GF,Toilet,\
+        Simulator,ExtraInfo,WebPage,Affiliations,NG,Postcode,Practice,OvhaulYr,Contractor,TuneYr,\
+        LGrade,BldgID,ChurchCare,TowerBase,DoveID,SNLat,SNLong";
+    const ROW: &str = "12574,Full circle ring,Ab Kettleby,,,S James,,Leicestershire,England,GB,\
+        Leicester,52.79858,-0.92747,6,,,974,,G,783.0,C,GF,,,,http://www.abkettlebybells.co.uk/,\
+        LeiDG,SK724228,LE14 3HS,Wed,2016,,,II*,1295154,619058,6918,AB KETTLEB,52.79968,-0.92635";
+
+    /// A CSV file containing one row (Ab Kettleby), with some of its values replaced
+    fn csv_with(changes: &[(&str, &str)]) -> String {
+        let mut fields = ROW.split(',').collect::<Vec<_>>();
+        for (header, value) in changes {
+            let idx = HEADERS.split(',').position(|h| h == *header).unwrap();
+            fields[idx] = value;
+        }
+        format!("{}\n{}\n", HEADERS, fields.join(","))
+    }
+
+    /// Loads a CSV file containing one row (see [`csv_with`]) with the given lenient groups
+    fn load(lenient: &[FieldGroup], changes: &[(&str, &str)]) -> Loaded {
+        let loader = lenient
+            .iter()
+            .fold(Loader::new(), |loader, &group| loader.lenient(group));
+        loader.from_reader(csv_with(changes).as_bytes()).unwrap()
+    }
+
+    /// The columns of the loaded `Ring` which differ from the unchanged row
+    fn changed_columns(loaded: &Loaded) -> Vec<Column> {
+        let unchanged = load(&[], &[]);
+        let (old, new) = (&unchanged.doves[0], &loaded.doves[0]);
+        Column::all()
+            .filter(|column| column.value(old) != column.value(new))
+            .collect()
+    }
+
+    fn warnings(loaded: &Loaded) -> Vec<(Column, &str)> {
+        let warnings = loaded.warnings.iter();
+        warnings.map(|w| (w.column, w.value.as_str())).collect()
+    }
+
+    #[test]
+    fn implausible_years_are_dropped() {
+        let csv = csv_with(&[("OvhaulYr", "1066"), ("TuneYr", "9999")]);
+        let loaded = Loader::new().from_reader(csv.as_bytes()).unwrap();
+        let ring = &loaded.doves[0];
+        assert_eq!(ring.overhaul_year, None);
+        assert_eq!(ring.tune_year, None);
+        let warnings = loaded.warnings.iter().map(|w| (w.column, w.value.as_str()));
+        assert_eq!(
+            warnings.collect::<Vec<_>>(),
+            [(Column::OverhaulYear, "1066"), (Column::TuneYear, "9999")]
+        );
+    }
+
+    #[test]
+    fn plausible_years_are_kept() {
+        let csv = csv_with(&[("TuneYr", "27 Oct 2013")]);
+        let loaded = Loader::new()
+            .lenient(FieldGroup::Years)
+            .from_reader(csv.as_bytes())
+            .unwrap();
+        assert_eq!(loaded.doves[0].overhaul_year, Some(2016));
+        assert_eq!(loaded.doves[0].tune_year, Some(2013));
+        assert!(loaded.warnings.is_empty());
+    }
+
+    #[test]
+    fn invalid_grid_ref_is_blanked() {
+        let loaded = load(&[FieldGroup::Location], &[("NG", "SK72422")]);
+        assert_eq!(changed_columns(&loaded), [Column::OsGridRef]);
+        assert_eq!(warnings(&loaded), [(Column::OsGridRef, "SK72422")]);
+        assert!(loaded.doves[0].coordinates.is_some());
+    }
+
+    #[test]
+    fn invalid_grid_ref_is_kept_when_strict() {
+        let loaded = load(&[FieldGroup::Tuning], &[("NG", "WV295753")]);
+        assert_eq!(loaded.doves[0].os_grid_ref.as_deref(), Some("WV295753"));
+        assert!(loaded.warnings.is_empty());
+    }
+
+    #[test]
+    fn channel_islands_grid_ref_is_kept() {
+        let changes = [
+            ("Country", "Channel Islands"),
+            ("ISO3166code", "GG"),
+            ("NG", "WV295753"),
+        ];
+        let loaded = load(&[FieldGroup::Location], &changes);
+        assert_eq!(loaded.doves[0].os_grid_ref.as_deref(), Some("WV295753"));
+        assert!(loaded.warnings.is_empty());
+    }
+
+    #[test]
+    fn malformed_value_blanks_only_its_column() {
+        let loaded = load(&[FieldGroup::Tuning], &[("Hz", "loud")]);
+        assert_eq!(changed_columns(&loaded), [Column::Frequency]);
+        assert_eq!(warnings(&loaded), [(Column::Frequency, "loud")]);
+
+        let loaded = load(&[FieldGroup::Regions], &[("ISO3166code", "??")]);
+        assert_eq!(changed_columns(&loaded), [Column::Iso3166Code]);
+    }
+
+    #[test]
+    fn malformed_coordinate_blanks_its_pair() {
+        let loaded = load(&[FieldGroup::Location], &[("Lat", "north")]);
+        assert_eq!(changed_columns(&loaded), [Column::Lat, Column::Long]);
+        assert_eq!(
+            warnings(&loaded),
+            [(Column::Lat, "north"), (Column::Long, "-0.92747")]
+        );
+        assert!(loaded.doves[0].satnav_coordinates.is_some());
+        assert!(loaded.doves[0].os_grid_ref.is_some());
+    }
+
+    #[test]
+    fn malformed_value_in_strict_group_fails() {
+        let csv = csv_with(&[("Hz", "loud")]);
+        let loader = Loader::new().lenient(FieldGroup::Location);
+        assert!(loader.from_reader(csv.as_bytes()).is_err());
+        assert!(Loader::new().from_reader(csv.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn find_culprit() {
+        let csv = csv_with(&[("Hz", "loud")]);
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        let headers = reader.headers().unwrap().clone();
+        let record: StringRecord = reader.records().next().unwrap().unwrap();
+        let message = match record
+            .deserialize::<RingRecord>(Some(&headers))
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::Deserialize { err, .. } => err.kind().to_string(),
+            kind => panic!("unexpected error {:?}", kind),
+        };
+        let lenient = Loader::new().lenient(FieldGroup::Tuning);
+        assert_eq!(
+            lenient.find_culprit(&record, &headers, &message),
+            Some("Hz")
+        );
+        // Only lenient columns are blanked
+        let strict = Loader::new().lenient(FieldGroup::Years);
+        assert_eq!(strict.find_culprit(&record, &headers, &message), None);
+    }
+}
@@ -0,0 +1,228 @@
+//! Indices for finding [`Ring`]s in a [`Doves`] without scanning every `Ring`.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use crate::{
+    normalize::normalize_county, spatial::SpatialIndex, Affiliation, Doves, Postcode, Resolution,
+    Ring, SmolStr, TowerId,
+};
+
+/// The kinds of index which a [`Doves`] can build over its [`Ring`]s (see
+/// [`Doves::ensure_index`])
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum IndexKind {
+    /// Finds `Ring`s by [`TowerId`], DoveID and TowerBase ID (e.g. for [`Doves::get`])
+    Id,
+    /// Finds `Ring`s by [`Affiliation`] (for [`Doves::with_affiliation`])
+    Affiliation,
+    /// Finds `Ring`s by county (for [`Doves::in_county`])
+    County,
+    /// Finds `Ring`s by postcode area (for [`Doves::in_postcode_area`] and [`Doves::in_outcode`])
+    Postcode,
+    /// Finds `Ring`s by location (for geographic queries such as [`Doves::nearest`])
+    Spatial,
+}
+
+/// Every index over the [`Ring`]s in a [`Doves`].  Each index is built the first time it's needed
+/// (or when it's requested with [`Doves::ensure_index`]), reused by every later query, and thrown
+/// away whenever the `Doves` is mutated.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Indices {
+    lookup: OnceLock<Lookup>,
+    /// The indices of every `Ring` with each [`Affiliation`]
+    by_affiliation: OnceLock<HashMap<Affiliation, Vec<usize>>>,
+    /// The indices of every `Ring` in each (normalized) county
+    by_county: OnceLock<HashMap<SmolStr, Vec<usize>>>,
+    /// The indices of every `Ring` in each (upper-case) postcode area
+    by_postcode_area: OnceLock<HashMap<SmolStr, Vec<usize>>>,
+    spatial: OnceLock<SpatialIndex>,
+}
+
+/// Maps from IDs to the positions of [`Ring`]s in [`Doves::towers`].  This is built when a
+/// `Doves` is loaded, since almost every application looks up `Ring`s by ID.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Lookup {
+    /// The index of the first `Ring` with each [`TowerId`]
+    by_id: HashMap<TowerId, usize>,
+    /// The index of the `Ring` with each (upper-case) DoveID
+    by_dove_id: HashMap<SmolStr, usize>,
+    /// The indices of every `Ring` with each (non-zero) TowerBase ID
+    by_towerbase_id: HashMap<u32, Vec<usize>>,
+}
+
+impl Lookup {
+    #[allow(deprecated)] // Old datasets still need to be looked up by DoveID
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "build_lookup", level = "debug", skip_all)
+    )]
+    fn new(rings: &[Ring]) -> Self {
+        let mut by_id = HashMap::with_capacity(rings.len());
+        let mut by_dove_id = HashMap::with_capacity(rings.len());
+        let mut by_towerbase_id = HashMap::<u32, Vec<usize>>::with_capacity(rings.len());
+        for (idx, ring) in rings.iter().enumerate() {
+            by_id.entry(ring.id).or_insert(idx);
+            if let Some(dove_id) = &ring.dove_id {
+                by_dove_id.entry(normalize_dove_id(dove_id)).or_insert(idx);
+            }
+            if ring.towerbase_id != NO_TOWERBASE_ID {
+                by_towerbase_id
+                    .entry(ring.towerbase_id)
+                    .or_default()
+                    .push(idx);
+            }
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(rings = by_id.len(), "built lookup");
+        Self {
+            by_id,
+            by_dove_id,
+            by_towerbase_id,
+        }
+    }
+}
+
+impl Doves {
+    /// Builds an index over the [`Ring`]s, if it hasn't been built since this `Doves` was last
+    /// mutated.  Every index is built automatically by the first query which needs it, so this is
+    /// only needed to move that work somewhere more convenient (e.g. building every index before
+    /// sharing a `Doves` between threads).
+    pub fn ensure_index(&self, kind: IndexKind) {
+        match kind {
+            IndexKind::Id => {
+                self.lookup();
+            }
+            IndexKind::Affiliation => {
+                self.affiliation_index();
+            }
+            IndexKind::County => {
+                self.county_index();
+            }
+            IndexKind::Postcode => {
+                self.postcode_index();
+            }
+            IndexKind::Spatial => {
+                self.spatial_index();
+            }
+        }
+    }
+
+    /// Gets the [`Lookup`] for this `Doves`, building it if it has been invalidated
+    pub(crate) fn lookup(&self) -> &Lookup {
+        self.indices
+            .lookup
+            .get_or_init(|| Lookup::new(&self.towers))
+    }
+
+    /// Gets the index of `Ring`s by [`Affiliation`], building it if it has been invalidated
+    pub(crate) fn affiliation_index(&self) -> &HashMap<Affiliation, Vec<usize>> {
+        self.indices
+            .by_affiliation
+            .get_or_init(|| group_by(&self.towers, |ring| ring.affiliations.iter().cloned()))
+    }
+
+    /// Gets the index of `Ring`s by normalized county, building it if it has been invalidated
+    pub(crate) fn county_index(&self) -> &HashMap<SmolStr, Vec<usize>> {
+        self.indices.by_county.get_or_init(|| {
+            group_by(&self.towers, |ring| {
+                ring.county.as_deref().map(normalize_county)
+            })
+        })
+    }
+
+    /// Gets the index of `Ring`s by upper-case postcode area, building it if it has been
+    /// invalidated
+    pub(crate) fn postcode_index(&self) -> &HashMap<SmolStr, Vec<usize>> {
+        self.indices.by_postcode_area.get_or_init(|| {
+            group_by(&self.towers, |ring| {
+                let area = ring.postcode.as_ref().and_then(Postcode::area)?;
+                Some(SmolStr::new(area.to_ascii_uppercase()))
+            })
+        })
+    }
+
+    /// Gets the [`SpatialIndex`] for this `Doves`, building it if it has been invalidated
+    pub(crate) fn spatial_index(&self) -> &SpatialIndex {
+        self.indices
+            .spatial
+            .get_or_init(|| SpatialIndex::new(&self.towers))
+    }
+
+    /// Throws away every index, because the [`Ring`]s are about to be modified
+    pub(crate) fn invalidate_indices(&mut self) {
+        self.indices = Indices::default();
+    }
+
+    /// The `Ring`s at some positions in [`Doves::towers`]
+    pub(crate) fn rings_at(&self, idxs: Option<&Vec<usize>>) -> Vec<&Ring> {
+        idxs.map_or_else(Vec::new, |idxs| {
+            idxs.iter().map(|&idx| &self.towers[idx]).collect()
+        })
+    }
+
+    /// Gets the [`Ring`] with a given [`TowerId`] in constant time.  If the tower contains
+    /// several `Ring`s, the first one (usually the main ring) is returned; use
+    /// [`Doves::towers_grouped`] to get all of them.  If the `TowerId` isn't in this `Doves` but
+    /// has been [redirected](Doves::set_redirects), the tower it was merged into is returned
+    /// (use [`Doves::resolve`] to tell these apart).
+    pub fn get(&self, id: TowerId) -> Option<&Ring> {
+        match self.resolve(id)? {
+            Resolution::Found(ring) | Resolution::Redirected(ring) => Some(ring),
+            Resolution::Removed => None,
+        }
+    }
+
+    /// Gets the [`Ring`] with a given [`TowerId`], without following any redirects
+    pub(crate) fn get_exact(&self, id: TowerId) -> Option<&Ring> {
+        let idx = *self.lookup().by_id.get(&id)?;
+        Some(&self.towers[idx])
+    }
+
+    /// Gets the [`Ring`] with a given DoveID (e.g. `"AB KETTLEB"`).  DoveIDs have been deprecated
+    /// by Dove's in favour of [`TowerId`]s, so this is mainly useful for migrating old datasets.
+    /// Case and surrounding whitespace are ignored.
+    pub fn get_by_dove_id(&self, dove_id: &str) -> Option<&Ring> {
+        let idx = *self.lookup().by_dove_id.get(&normalize_dove_id(dove_id))?;
+        Some(&self.towers[idx])
+    }
+
+    /// Gets every [`Ring`] with a given TowerBase ID, in the order they appear in Dove's Guide.
+    /// TowerBase IDs aren't unique, so this may return any number of `Ring`s.  Dove's uses `0` for
+    /// rings which aren't in TowerBase, so `get_by_towerbase_id(0)` always returns nothing.
+    pub fn get_by_towerbase_id(&self, towerbase_id: u32) -> Vec<&Ring> {
+        self.rings_at(self.lookup().by_towerbase_id.get(&towerbase_id))
+    }
+
+    /// Gets every [`Ring`] affiliated to a given [`Affiliation`], in the order they appear in
+    /// Dove's Guide.  This uses a precomputed index, so doesn't scan every `Ring`.
+    pub fn with_affiliation(&self, affiliation: Affiliation) -> Vec<&Ring> {
+        self.rings_at(self.affiliation_index().get(&affiliation))
+    }
+
+    /// `true` if this `Doves` contains a [`Ring`] with the given [`TowerId`]
+    pub fn contains_id(&self, id: TowerId) -> bool {
+        self.lookup().by_id.contains_key(&id)
+    }
+}
+
+/// The TowerBase ID which Dove's gives to rings which aren't in TowerBase
+pub(crate) const NO_TOWERBASE_ID: u32 = 0;
+
+/// Groups the positions of `rings` by any number of keys per `Ring`, keeping each group in order
+fn group_by<'r, K: Eq + std::hash::Hash, I: IntoIterator<Item = K>>(
+    rings: &'r [Ring],
+    keys: impl Fn(&'r Ring) -> I,
+) -> HashMap<K, Vec<usize>> {
+    let mut groups = HashMap::<K, Vec<usize>>::new();
+    for (idx, ring) in rings.iter().enumerate() {
+        for key in keys(ring) {
+            groups.entry(key).or_default().push(idx);
+        }
+    }
+    groups
+}
+
+/// Normalizes a DoveID so that lookups don't depend on case or surrounding whitespace
+fn normalize_dove_id(dove_id: &str) -> SmolStr {
+    SmolStr::new(dove_id.trim().to_ascii_uppercase())
+}
@@ -0,0 +1,76 @@
+//! Queries on when [`Ring`]s were last overhauled or tuned, and by whom.
+
+use std::{cmp::Reverse, collections::HashMap};
+
+use crate::{Contractor, Doves, Ring};
+
+/// The [`Ring`]s last overhauled by one [`Contractor`].  Created by [`Doves::by_contractor`].
+#[derive(Debug, Clone)]
+pub struct ContractorWork<'d> {
+    pub contractor: &'d Contractor,
+    /// The `Ring`s overhauled by `contractor`, in the order they appear in Dove's Guide
+    pub rings: Vec<&'d Ring>,
+    /// The earliest known overhaul year of any of the `rings`
+    pub first_year: Option<u16>,
+    /// The latest known overhaul year of any of the `rings`
+    pub last_year: Option<u16>,
+}
+
+impl Doves {
+    /// Gets every [`Ring`] last overhauled in or after `year`, in the order they appear in Dove's
+    /// Guide.  `Ring`s with no known overhaul year are never returned.
+    pub fn overhauled_since(&self, year: u16) -> Vec<&Ring> {
+        self.overhauled_between(year, u16::MAX)
+    }
+
+    /// Gets every [`Ring`] last overhauled between `start` and `end` (inclusive), in the order
+    /// they appear in Dove's Guide.  `Ring`s with no known overhaul year are never returned.
+    pub fn overhauled_between(&self, start: u16, end: u16) -> Vec<&Ring> {
+        self.iter()
+            .filter(|ring| {
+                ring.overhaul_year
+                    .is_some_and(|y| (start..=end).contains(&y))
+            })
+            .collect()
+    }
+
+    /// Gets every [`Ring`] last tuned in or after `year`, in the order they appear in Dove's
+    /// Guide.  `Ring`s with no known tuning year are never returned.
+    pub fn tuned_since(&self, year: u16) -> Vec<&Ring> {
+        self.tuned_between(year, u16::MAX)
+    }
+
+    /// Gets every [`Ring`] last tuned between `start` and `end` (inclusive), in the order they
+    /// appear in Dove's Guide.  `Ring`s with no known tuning year are never returned.
+    pub fn tuned_between(&self, start: u16, end: u16) -> Vec<&Ring> {
+        self.iter()
+            .filter(|ring| ring.tune_year.is_some_and(|y| (start..=end).contains(&y)))
+            .collect()
+    }
+
+    /// Groups the [`Ring`]s by the [`Contractor`] who last overhauled them, most prolific first
+    /// (ties are broken by name).  `Ring`s with no known contractor are left out.
+    pub fn by_contractor(&self) -> Vec<ContractorWork<'_>> {
+        let mut groups = HashMap::<&Contractor, ContractorWork>::new();
+        for ring in self {
+            let contractor = match &ring.contractor {
+                Some(contractor) => contractor,
+                None => continue,
+            };
+            let work = groups.entry(contractor).or_insert_with(|| ContractorWork {
+                contractor,
+                rings: Vec::new(),
+                first_year: None,
+                last_year: None,
+            });
+            work.rings.push(ring);
+            if let Some(year) = ring.overhaul_year {
+                work.first_year = Some(work.first_year.map_or(year, |y| y.min(year)));
+                work.last_year = Some(work.last_year.map_or(year, |y| y.max(year)));
+            }
+        }
+        let mut groups = groups.into_values().collect::<Vec<_>>();
+        groups.sort_by_key(|work| (Reverse(work.rings.len()), work.contractor.dove_name()));
+        groups
+    }
+}
@@ -0,0 +1,116 @@
+//! Information about where a [`Doves`] was loaded from, so that applications can show how up to
+//! date their data is (and notice when a cached copy is stale).
+
+use std::{
+    fmt::{Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{Column, Doves};
+
+/// The version of Dove's CSV layout read by this crate, i.e. the [`Column`]s returned by
+/// [`Column::all`] in that order.  This is increased whenever Dove's Guide adds, removes or
+/// reorders its columns.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Where a [`Doves`] was loaded from
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Source {
+    /// A file on disk
+    Path(PathBuf),
+    /// A file downloaded from a URL
+    Url(String),
+}
+
+/// Information about the source of a [`Doves`] (see [`Doves::metadata`]).  A `Doves` which wasn't
+/// loaded from a file (e.g. one built from a `Vec` of [`Ring`](crate::Ring)s) only knows its
+/// `record_count`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metadata {
+    /// Where the data was loaded from, or `None` if it was read from some other reader
+    pub source: Option<Source>,
+    /// When the data was exported from Dove's Guide, if known.  Loading from a path sets this to
+    /// the file's modification time; applications which download the file should set it from the
+    /// response's `Last-Modified` header.
+    pub exported: Option<SystemTime>,
+    /// The number of records which were loaded.  Unlike [`Doves::len`], this doesn't change if
+    /// [`Ring`](crate::Ring)s are later added or removed.
+    pub record_count: usize,
+    /// [`SCHEMA_VERSION`] if the header row of the file exactly matched the layout read by this
+    /// crate, or `None` if it didn't (e.g. for older exports with fewer columns)
+    pub schema_version: Option<u32>,
+}
+
+impl Metadata {
+    /// The date that the data was [`exported`](Self::exported), in UTC, formatted as
+    /// `YYYY-MM-DD`
+    pub fn export_date(&self) -> Option<String> {
+        let (year, month, day) = civil_date(self.exported?);
+        Some(format!("{:04}-{:02}-{:02}", year, month, day))
+    }
+
+    /// How long ago the data was [`exported`](Self::exported), according to the system clock
+    pub fn age(&self) -> Option<Duration> {
+        SystemTime::now().duration_since(self.exported?).ok()
+    }
+
+    /// Sets the `source` and `exported` time from a file on disk
+    pub(crate) fn set_path(&mut self, path: &Path) {
+        self.source = Some(Source::Path(path.to_owned()));
+        self.exported = fs::metadata(path).and_then(|m| m.modified()).ok();
+    }
+}
+
+impl Doves {
+    /// Information about where this `Doves` was loaded from
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Mutable access to this `Doves`'s [`Metadata`], e.g. to set the export time of a file which
+    /// was downloaded rather than loaded from a path
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Url(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+/// The [`SCHEMA_VERSION`] of a header row, or `None` if it doesn't match the current layout
+pub(crate) fn schema_version<'h>(headers: impl IntoIterator<Item = &'h str>) -> Option<u32> {
+    let mut headers = headers.into_iter();
+    let matches = Column::all().all(|column| headers.next() == Some(column.dove_header()))
+        && headers.next().is_none();
+    matches.then_some(SCHEMA_VERSION)
+}
+
+/// The `(year, month, day)` of a [`SystemTime`], in UTC
+pub(crate) fn civil_date(time: SystemTime) -> (u16, u8, u8) {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    // Convert days since the epoch into a civil date, using the algorithm from
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = secs / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153; // March is 0
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month_index >= 10 { 1 } else { 0 };
+    (year as u16, month as u8, day as u8)
+}
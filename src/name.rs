@@ -0,0 +1,56 @@
+use crate::{Dedication, Ring};
+
+/// The conventions used by [`Ring::display_name`] to build a human-readable name for a
+/// [`Ring`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum NameStyle {
+    /// The style used by Dove's website: the place, followed by the dedication (e.g. `Oxford, S
+    /// Mary Magd` or `Leeds, Headingley, S Michael & All Angels`).
+    Dove,
+    /// The style used in county lists, where towers are listed under a county so the county is
+    /// omitted.  This uses Dove's own county list name where there is one (e.g. `Bridgnorth, S
+    /// Leonard`), falling back to just the place name.
+    CountyList,
+    /// The style used by BellBoard: the place and county, followed by the dedication in brackets
+    /// (e.g. `Oxford, Oxfordshire (S Mary Magd)`).
+    BellBoard,
+}
+
+impl Ring {
+    /// The conventional human-readable name of this `Ring`, formatted according to a
+    /// [`NameStyle`].  Unknown dedications are left out, as are the counties and dedications of
+    /// mobile `Ring`s (whose `place` is already the name of the `Ring`).
+    pub fn display_name(&self, style: NameStyle) -> String {
+        if style == NameStyle::CountyList {
+            return match &self.place_county_list {
+                Some(county_list_name) => county_list_name.to_string(),
+                None => self.place.to_string(),
+            };
+        }
+
+        let mut name = self.place.to_string();
+        if let Some(place2) = &self.place2 {
+            name.push_str(", ");
+            name.push_str(place2);
+        }
+        if self.is_mobile() {
+            return name;
+        }
+        if style == NameStyle::BellBoard {
+            if let Some(county) = &self.county {
+                name.push_str(", ");
+                name.push_str(county);
+            }
+        }
+        if self.dedication != Dedication::Unknown {
+            let (open, close) = match style {
+                NameStyle::BellBoard => (" (", ")"),
+                _ => (", ", ""),
+            };
+            name.push_str(open);
+            name.push_str(self.dedication.as_str());
+            name.push_str(close);
+        }
+        name
+    }
+}
@@ -0,0 +1,198 @@
+//! Mapping the naming conventions of Dove's Guide to the other ways people write the same names
+//! (e.g. `Yorkshire, West` and `West Yorkshire`, `Caernarvon` and `Caernarfon`, or `S Mary V` and
+//! `St Mary the Virgin`).  These are used by the search and query methods, and are also exposed
+//! for matching names from other sources against Dove's.
+
+use crate::SmolStr;
+
+/// Alternative (normalized) names for counties, along with the normalized name Dove's uses.  Most
+/// of these are the Welsh names of Welsh counties, or the English names of counties which Dove's
+/// gives in Welsh.
+static COUNTY_ALIASES: [(&str, &str); 20] = [
+    ("anglesey", "ynys mon"),
+    ("isle of anglesey", "ynys mon"),
+    ("sir fon", "ynys mon"),
+    ("bro morgannwg", "vale of glamorgan"),
+    ("rhondda cynon taf", "rhondda cynon taff"),
+    ("caerffili", "caerphilly"),
+    ("caerdydd", "cardiff"),
+    ("abertawe", "swansea"),
+    ("casnewydd", "newport"),
+    ("castell nedd port talbot", "neath port talbot"),
+    ("pen y bont ar ogwr", "bridgend"),
+    ("merthyr tudful", "merthyr tydfil"),
+    ("sir fynwy", "monmouthshire"),
+    ("sir benfro", "pembrokeshire"),
+    ("sir gaerfyrddin", "carmarthenshire"),
+    ("sir ddinbych", "denbighshire"),
+    ("sir y fflint", "flintshire"),
+    ("wrecsam", "wrexham"),
+    ("cardiganshire", "ceredigion"),
+    ("conway", "conwy"),
+];
+
+/// Pairs of (folded) names for the same place, which aren't already covered by the `alt_name`s
+/// in Dove's Guide.  These are mostly older anglicised spellings of Welsh place names.
+static PLACE_VARIANTS: [(&str, &str); 14] = [
+    ("caernarvon", "caernarfon"),
+    ("carnarvon", "caernarfon"),
+    ("conway", "conwy"),
+    ("dolgelley", "dolgellau"),
+    ("portmadoc", "porthmadog"),
+    ("barmouth", "abermaw"),
+    ("towyn", "tywyn"),
+    ("aberdovey", "aberdyfi"),
+    ("dinas powis", "dinas powys"),
+    ("llandaff", "llandaf"),
+    ("merthyr tydfil", "merthyr tudful"),
+    ("flint", "y fflint"),
+    ("caldicot", "cil y coed"),
+    ("hay on wye", "y gelli gandryll"),
+];
+
+/// The abbreviations used in the dedications in Dove's Guide, along with their expansions
+static DEDICATION_ABBREVIATIONS: [(&str, &str); 24] = [
+    ("S", "St"),
+    ("SS", "Saints"),
+    ("SMV", "St Mary the Virgin"),
+    ("BVM", "the Blessed Virgin Mary"),
+    ("BV", "the Blessed Virgin"),
+    ("V", "the Virgin"),
+    ("Bapt", "the Baptist"),
+    ("Ev", "the Evangelist"),
+    ("Div", "the Divine"),
+    ("Magd", "Magdalene"),
+    ("Gt", "the Great"),
+    ("Less", "the Less"),
+    ("Ap", "the Apostle"),
+    ("M", "the Martyr"),
+    ("K", "King"),
+    ("Q", "Queen"),
+    ("K&M", "King and Martyr"),
+    ("A&M", "Apostle and Martyr"),
+    ("Conf", "the Confessor"),
+    ("Abp", "Archbishop"),
+    ("Bp", "Bishop"),
+    ("Cath", "Cathedral"),
+    ("Ch", "Church"),
+    ("Univ", "University"),
+];
+
+/// Normalizes the name of a county into the lower-case form used for matching.  This ignores
+/// case, accents, punctuation and extra whitespace, drops `County`/`Co.` prefixes (so `Co. Durham`
+/// matches `Durham`) and a leading `The`, and reorders names like `Yorkshire, North` into `north
+/// yorkshire`.  The Welsh and English names of Welsh counties normalize to the same name, so
+/// `Anglesey` matches `Ynys Môn`.
+pub fn normalize_county(name: &str) -> SmolStr {
+    let name = name
+        .trim()
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(strip_accent)
+        .collect::<String>();
+    // Reorder `Yorkshire, North` into `North Yorkshire`
+    let name = match name.split_once(',') {
+        Some((county, direction)) => format!("{} {}", direction, county),
+        None => name,
+    };
+    let words = name
+        .split(|c: char| c.is_whitespace() || c == '.' || c == '-')
+        .filter(|w| !w.is_empty())
+        .map(|w| if w == "&" { "and" } else { w })
+        .collect::<Vec<_>>();
+    let words = match words.as_slice() {
+        ["county" | "co", rest @ ..] if !rest.is_empty() => rest,
+        [rest @ .., "county"] if !rest.is_empty() => rest,
+        ["the", rest @ ..] if !rest.is_empty() => rest,
+        words => words,
+    };
+    let name = words.join(" ");
+    match COUNTY_ALIASES.iter().find(|(alias, _)| *alias == name) {
+        Some((_, dove_name)) => SmolStr::new(dove_name),
+        None => SmolStr::new(name),
+    }
+}
+
+/// `true` if two county names refer to the same county (i.e. they're equal after
+/// [`normalize_county`])
+pub fn counties_match(a: &str, b: &str) -> bool {
+    normalize_county(a) == normalize_county(b)
+}
+
+/// The different ways of writing a place name, folded for matching (i.e. lower case, without
+/// accents, and with punctuation replaced by spaces).  The first is always `name` itself,
+/// followed by any alternative spellings (e.g. `Caernarvon` gives `caernarvon` and `caernarfon`).
+/// Welsh names given in a `Ring`'s `alt_name` aren't repeated here.
+pub fn place_variants(name: &str) -> Vec<String> {
+    let folded = fold(name);
+    let mut variants = vec![folded.clone()];
+    let padded = format!(" {} ", folded);
+    for (a, b) in &PLACE_VARIANTS {
+        for (from, to) in [(a, b), (b, a)] {
+            let from = format!(" {} ", from);
+            if padded.contains(&from) {
+                let variant = padded.replace(&from, &format!(" {} ", to));
+                variants.push(variant.trim().to_owned());
+            }
+        }
+    }
+    variants.dedup();
+    variants
+}
+
+/// Expands the abbreviations used in the dedications of Dove's Guide, so e.g. `S Mary V` becomes
+/// `St Mary the Virgin` and `Cath Ch of S Peter & S Paul` becomes `Cathedral Church of St Peter &
+/// St Paul`.  Unlike [`Dedication::normalize_name`](crate::Dedication::normalize_name), this keeps
+/// every word, so is suitable for display and full-text search.
+pub fn expand_dedication(dedication: &str) -> String {
+    dedication
+        .split_whitespace()
+        .map(|word| {
+            // Keep any trailing comma (e.g. in `S Mary V, S Nicholas`)
+            let (word, suffix) = match word.strip_suffix(',') {
+                Some(word) => (word, ","),
+                None => (word, ""),
+            };
+            let expansion = DEDICATION_ABBREVIATIONS
+                .iter()
+                .find(|(abbr, _)| *abbr == word)
+                .map_or(word, |(_, expansion)| expansion);
+            format!("{}{}", expansion, suffix)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Folds a string into a form suitable for fuzzy matching: lower case, without accents, and with
+/// any punctuation replaced by single spaces
+pub(crate) fn fold(s: &str) -> String {
+    let mut folded = String::with_capacity(s.len());
+    for c in s.chars().flat_map(char::to_lowercase) {
+        let c = strip_accent(c);
+        if c.is_alphanumeric() {
+            folded.push(c);
+        } else if !folded.is_empty() && !folded.ends_with(' ') {
+            folded.push(' ');
+        }
+    }
+    let trimmed_len = folded.trim_end().len();
+    folded.truncate(trimmed_len);
+    folded
+}
+
+/// Removes the accent from a lower-case Latin letter (e.g. `é` becomes `e`)
+fn strip_accent(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' => 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => 'i',
+        'ñ' | 'ń' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' => 'u',
+        'ý' | 'ÿ' | 'ŷ' => 'y',
+        'ŵ' => 'w',
+        'ß' => 's',
+        _ => c,
+    }
+}
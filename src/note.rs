@@ -0,0 +1,489 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+use crate::{SmolStr, TowerId};
+
+const FLAT: char = '♭';
+const NATURAL: char = '♮';
+const SHARP: char = '♯';
+
+/// The number of semitones in an octave
+const OCTAVE: i16 = 12;
+/// The standard concert pitch of `A`, in Hz
+pub const CONCERT_A: f64 = 440.0;
+
+/// The musical `Note` of the heaviest bell in a [`Ring`](crate::Ring).
+///
+/// `Note`s don't store an octave, so comparisons are made within a single octave starting at `A`.
+/// Enharmonic notes (e.g. `A♯` and `B♭`) refer to the same pitch and so are considered equal.
+#[derive(Debug, Clone, Copy)]
+pub struct Note {
+    pub name: NoteName,
+    pub accidental: Accidental,
+}
+
+impl Note {
+    /// Creates a new `Note`
+    pub fn new(name: NoteName, accidental: Accidental) -> Self {
+        Self { name, accidental }
+    }
+
+    /// The number of semitones from the `A` below (or at) this `Note` up to this `Note`.  This is
+    /// always in the range `0..12`.
+    pub fn semitones_from_a(self) -> u8 {
+        let name_semitones = self.name.semitones_from_a() as i16;
+        (name_semitones + self.accidental.offset() as i16).rem_euclid(OCTAVE) as u8
+    }
+
+    /// Creates the `Note` which is `semitones` above `A`.  Notes which need an accidental are
+    /// spelled using `accidental` (or sharps if `accidental` is [`Accidental::Natural`]).
+    pub fn from_semitones_from_a(semitones: i16, accidental: Accidental) -> Self {
+        let semitones = semitones.rem_euclid(OCTAVE) as u8;
+        // Use a natural note if one exists
+        if let Some(name) = NoteName::from_semitones_from_a(semitones) {
+            return Self::new(name, Accidental::Natural);
+        }
+        // Otherwise, the note must be a semitone off a natural note
+        let accidental = match accidental {
+            Accidental::Flat => Accidental::Flat,
+            Accidental::Natural | Accidental::Sharp => Accidental::Sharp,
+        };
+        let natural = (semitones as i16 - accidental.offset() as i16).rem_euclid(OCTAVE) as u8;
+        let name = NoteName::from_semitones_from_a(natural)
+            .expect("Every note next to an accidental should be natural");
+        Self::new(name, accidental)
+    }
+
+    /// Creates the `Note` which is `semitones` above `A`, spelling black notes the way Dove's
+    /// Guide usually does (i.e. `B♭`, `C♯`, `E♭`, `F♯` and `A♭`)
+    pub(crate) fn with_dove_spelling(semitones: i16) -> Self {
+        let spelling = match semitones.rem_euclid(OCTAVE) {
+            1 | 6 | 11 => Accidental::Flat,
+            _ => Accidental::Sharp,
+        };
+        Self::from_semitones_from_a(semitones, spelling)
+    }
+
+    /// Transposes this `Note` by some number of semitones (positive values transpose upwards).
+    /// The result keeps the same style of accidental as `self` where possible.
+    pub fn transpose(self, semitones: i8) -> Self {
+        Self::from_semitones_from_a(
+            self.semitones_from_a() as i16 + semitones as i16,
+            self.accidental,
+        )
+    }
+
+    /// The number of semitones from `self` up to the next occurrence of `other`.  This is always
+    /// in the range `0..12`.
+    pub fn semitones_up_to(self, other: Note) -> u8 {
+        (other.semitones_from_a() as i16 - self.semitones_from_a() as i16).rem_euclid(OCTAVE) as u8
+    }
+
+    /// Returns a wrapper which [`Display`]s this `Note` using only ASCII characters (i.e. `b` and
+    /// `#` instead of `♭` and `♯`).
+    pub fn ascii(self) -> AsciiNote {
+        AsciiNote(self)
+    }
+
+    /// The shortest number of semitones between `self` and `other`, which is positive if `other`
+    /// is above `self`.  This is always in the range `-5..=6`.
+    pub fn interval_to(self, other: Note) -> i8 {
+        let up = self.semitones_up_to(other) as i8;
+        if up > 6 {
+            up - OCTAVE as i8
+        } else {
+            up
+        }
+    }
+}
+
+impl PartialEq for Note {
+    fn eq(&self, other: &Self) -> bool {
+        self.semitones_from_a() == other.semitones_from_a()
+    }
+}
+
+impl Eq for Note {}
+
+impl Hash for Note {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.semitones_from_a().hash(state);
+    }
+}
+
+impl PartialOrd for Note {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Note {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.semitones_from_a().cmp(&other.semitones_from_a())
+    }
+}
+
+impl Display for Note {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.name, self.accidental)
+    }
+}
+
+impl FromStr for Note {
+    type Err = ParseNoteError;
+
+    /// Parses a `Note` from a note name followed by an optional accidental.  Accidentals can be
+    /// written either in Unicode (`♭`, `♮`, `♯`) or ASCII (`b`, `#`), so `"F#"`, `"F♯"`, `"Bb"` and
+    /// `"B♭"` are all valid.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let name = match chars.next() {
+            Some('A') => NoteName::A,
+            Some('B') => NoteName::B,
+            Some('C') => NoteName::C,
+            Some('D') => NoteName::D,
+            Some('E') => NoteName::E,
+            Some('F') => NoteName::F,
+            Some('G') => NoteName::G,
+            Some(c) => return Err(ParseNoteError::InvalidName(c)),
+            None => return Err(ParseNoteError::Empty),
+        };
+        let accidental = match chars.next() {
+            Some(FLAT) | Some('b') => Accidental::Flat,
+            Some(NATURAL) | None => Accidental::Natural,
+            Some(SHARP) | Some('#') => Accidental::Sharp,
+            Some(c) => return Err(ParseNoteError::InvalidAccidental(c)),
+        };
+        if let Some(c) = chars.next() {
+            return Err(ParseNoteError::TrailingChar(c));
+        }
+        Ok(Note { name, accidental })
+    }
+}
+
+/// A wrapper which [`Display`]s a [`Note`] using only ASCII characters.  Created by
+/// [`Note::ascii`].
+#[derive(Debug, Clone, Copy)]
+pub struct AsciiNote(Note);
+
+impl Display for AsciiNote {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.0.name, self.0.accidental.ascii_str())
+    }
+}
+
+/// The error generated when parsing an invalid [`Note`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseNoteError {
+    Empty,
+    InvalidName(char),
+    InvalidAccidental(char),
+    TrailingChar(char),
+}
+
+impl Display for ParseNoteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseNoteError::Empty => write!(f, "Empty string is not a note"),
+            ParseNoteError::InvalidName(c) => write!(f, "Char {:?} is not a note name", c),
+            ParseNoteError::InvalidAccidental(c) => {
+                write!(f, "Char {:?} is not an accidental name", c)
+            }
+            ParseNoteError::TrailingChar(c) => write!(f, "Unexpected char {:?} after note", c),
+        }
+    }
+}
+
+impl std::error::Error for ParseNoteError {}
+
+/// The name of a root [`Note`] (i.e. `A` to `G`)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum NoteName {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+}
+
+impl NoteName {
+    /// The number of semitones from `A` up to this `NoteName`
+    pub fn semitones_from_a(self) -> u8 {
+        match self {
+            NoteName::A => 0,
+            NoteName::B => 2,
+            NoteName::C => 3,
+            NoteName::D => 5,
+            NoteName::E => 7,
+            NoteName::F => 8,
+            NoteName::G => 10,
+        }
+    }
+
+    /// The `NoteName` which is `semitones` above `A`, or `None` if that isn't a natural note
+    fn from_semitones_from_a(semitones: u8) -> Option<Self> {
+        Some(match semitones {
+            0 => NoteName::A,
+            2 => NoteName::B,
+            3 => NoteName::C,
+            5 => NoteName::D,
+            7 => NoteName::E,
+            8 => NoteName::F,
+            10 => NoteName::G,
+            _ => return None,
+        })
+    }
+}
+
+impl Display for NoteName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self) // Display using the note names
+    }
+}
+
+/// An accidental to be applied to a [`NoteName`] to create a specific [`Note`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Accidental {
+    Flat,
+    Natural,
+    Sharp,
+}
+
+impl Accidental {
+    /// The number of semitones by which this `Accidental` changes a note
+    pub fn offset(self) -> i8 {
+        match self {
+            Accidental::Flat => -1,
+            Accidental::Natural => 0,
+            Accidental::Sharp => 1,
+        }
+    }
+}
+
+impl Accidental {
+    /// The ASCII representation of this `Accidental`
+    fn ascii_str(self) -> &'static str {
+        match self {
+            Accidental::Flat => "b",
+            Accidental::Natural => "",
+            Accidental::Sharp => "#",
+        }
+    }
+}
+
+impl Display for Accidental {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Accidental::Flat => write!(f, "{}", FLAT),
+            Accidental::Natural => Ok(()),
+            Accidental::Sharp => write!(f, "{}", SHARP),
+        }
+    }
+}
+
+///////////////
+// FREQUENCY //
+///////////////
+
+/// A `Frequency`, measured in Hz.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(transparent))]
+pub struct Frequency(f64);
+
+impl Frequency {
+    /// Creates a `Frequency` from a number of Hz
+    pub fn from_hz(hz: f64) -> Self {
+        Self(hz)
+    }
+
+    /// The value of this `Frequency` in Hz
+    pub fn hz(self) -> f64 {
+        self.0
+    }
+
+    /// Finds the [`Note`] closest to this `Frequency`, along with how far this `Frequency` is from
+    /// that `Note` in cents (positive if this `Frequency` is sharp of the `Note`).  This assumes
+    /// equal temperament tuned to [`CONCERT_A`]; use [`Frequency::nearest_note_with_a`] to use a
+    /// different reference pitch.
+    pub fn nearest_note(self) -> (Note, f64) {
+        self.nearest_note_with_a(CONCERT_A)
+    }
+
+    /// Finds the [`Note`] closest to this `Frequency`, along with how far this `Frequency` is from
+    /// that `Note` in cents, assuming equal temperament with `A` tuned to `a_hz`.  Black notes are
+    /// spelled the way Dove's Guide usually spells them (i.e. `B♭`, `C♯`, `E♭`, `F♯` and `A♭`).
+    pub fn nearest_note_with_a(self, a_hz: f64) -> (Note, f64) {
+        let semitones = self.semitones_above(a_hz);
+        let nearest = semitones.round();
+        let note = Note::with_dove_spelling(nearest as i16);
+        (note, (semitones - nearest) * 100.0)
+    }
+
+    /// How far this `Frequency` is from the closest occurrence of `note`, in cents (positive if
+    /// this `Frequency` is sharp of `note`).  This is always in the range `-600.0..=600.0`.  This
+    /// assumes equal temperament tuned to [`CONCERT_A`]; use [`Frequency::cents_from_with_a`] to
+    /// use a different reference pitch.
+    pub fn cents_from(self, note: Note) -> f64 {
+        self.cents_from_with_a(note, CONCERT_A)
+    }
+
+    /// How far this `Frequency` is from the closest occurrence of `note`, in cents, assuming equal
+    /// temperament with `A` tuned to `a_hz`.
+    pub fn cents_from_with_a(self, note: Note, a_hz: f64) -> f64 {
+        let semitones = self.semitones_above(a_hz) - note.semitones_from_a() as f64;
+        let octave = OCTAVE as f64;
+        // Wrap into the octave centred on `note`
+        let wrapped = (semitones + octave / 2.0).rem_euclid(octave) - octave / 2.0;
+        wrapped * 100.0
+    }
+
+    /// The (fractional) number of semitones from `a_hz` up to this `Frequency`
+    fn semitones_above(self, a_hz: f64) -> f64 {
+        (self.0 / a_hz).log2() * OCTAVE as f64
+    }
+}
+
+impl Display for Frequency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}Hz", self.0)
+    }
+}
+
+/// A [`Ring`](crate::Ring) whose measured [`Frequency`] doesn't match its stated [`Note`].
+/// Created by [`Ring::note_mismatch`](crate::Ring::note_mismatch).
+#[derive(Debug, Clone)]
+pub struct NoteMismatch {
+    /// The [`TowerId`] of the mismatching `Ring`
+    pub id: TowerId,
+    /// The place name of the mismatching `Ring`, to make reports easier to read
+    pub place: SmolStr,
+    /// The [`Note`] stated by Dove's Guide
+    pub note: Note,
+    /// The [`Frequency`] stated by Dove's Guide
+    pub freq: Frequency,
+    /// How far `freq` is from `note`, in cents (positive if `freq` is sharp)
+    pub cents: f64,
+    /// The [`Note`] which is actually closest to `freq`
+    pub nearest_note: Note,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Accidental, Frequency, Note, NoteName, ParseNoteError};
+
+    fn note(s: &str) -> Note {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_naturals() {
+        for (s, name) in [
+            ("A", NoteName::A),
+            ("B", NoteName::B),
+            ("C", NoteName::C),
+            ("D", NoteName::D),
+            ("E", NoteName::E),
+            ("F", NoteName::F),
+            ("G", NoteName::G),
+        ] {
+            let parsed = note(s);
+            assert_eq!(parsed.name, name);
+            assert_eq!(parsed.accidental, Accidental::Natural);
+        }
+        assert_eq!(note("C♮").accidental, Accidental::Natural);
+    }
+
+    #[test]
+    fn parse_accidentals() {
+        for s in ["F#", "F♯"] {
+            assert_eq!(note(s).name, NoteName::F);
+            assert_eq!(note(s).accidental, Accidental::Sharp);
+        }
+        for s in ["Bb", "B♭"] {
+            assert_eq!(note(s).name, NoteName::B);
+            assert_eq!(note(s).accidental, Accidental::Flat);
+        }
+        // Enharmonic notes are equal
+        assert_eq!(note("A#"), note("Bb"));
+        assert_eq!(note("E#"), note("F"));
+        assert_eq!(note("Cb"), note("B"));
+    }
+
+    #[test]
+    fn octaves_wrap() {
+        // `A♭` is just below `A`, so wraps to the top of the octave
+        assert_eq!(note("A").semitones_from_a(), 0);
+        assert_eq!(note("Ab").semitones_from_a(), 11);
+        assert_eq!(note("G#").semitones_from_a(), 11);
+        assert_eq!(note("B#").semitones_from_a(), 3);
+        assert_eq!(note("C").transpose(12), note("C"));
+        assert_eq!(note("C").transpose(-13), note("B"));
+        assert_eq!(
+            Note::from_semitones_from_a(-1, Accidental::Flat),
+            note("Ab")
+        );
+        assert_eq!(note("G").interval_to(note("A")), 2);
+        assert_eq!(note("A").interval_to(note("G")), -2);
+    }
+
+    #[test]
+    fn octave_numbers_are_rejected() {
+        // `Note`s don't store an octave, so scientific pitch notation isn't accepted
+        assert_eq!(
+            "A4".parse::<Note>(),
+            Err(ParseNoteError::InvalidAccidental('4'))
+        );
+        assert_eq!(
+            "C#5".parse::<Note>(),
+            Err(ParseNoteError::TrailingChar('5'))
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("".parse::<Note>(), Err(ParseNoteError::Empty));
+        assert_eq!("H".parse::<Note>(), Err(ParseNoteError::InvalidName('H')));
+        assert_eq!("a".parse::<Note>(), Err(ParseNoteError::InvalidName('a')));
+        assert_eq!(
+            "Bx".parse::<Note>(),
+            Err(ParseNoteError::InvalidAccidental('x'))
+        );
+        assert_eq!(
+            "F##".parse::<Note>(),
+            Err(ParseNoteError::TrailingChar('#'))
+        );
+        assert_eq!(" F".parse::<Note>(), Err(ParseNoteError::InvalidName(' ')));
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in ["A", "B♭", "C♯", "E♭", "F♯", "A♭"] {
+            let parsed = note(s);
+            assert_eq!(parsed.to_string(), s);
+            assert_eq!(note(&parsed.ascii().to_string()), parsed);
+        }
+        assert_eq!(note("Bb").ascii().to_string(), "Bb");
+    }
+
+    #[test]
+    fn frequencies_in_any_octave() {
+        for hz in [110.0, 220.0, 440.0, 880.0] {
+            let (nearest, cents) = Frequency::from_hz(hz).nearest_note();
+            assert_eq!(nearest, note("A"));
+            assert!(cents.abs() < 1e-9);
+        }
+        let (nearest, _) = Frequency::from_hz(783.0).nearest_note();
+        assert_eq!(nearest, note("G"));
+        let cents = Frequency::from_hz(880.0).cents_from(note("Ab"));
+        assert!((cents - 100.0).abs() < 1e-9);
+    }
+}
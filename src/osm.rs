@@ -0,0 +1,258 @@
+//! Reconciling Dove's Guide with OpenStreetMap.  This generates Overpass API queries for the
+//! places of worship around each tower, and matches the results back to [`Ring`]s by location and
+//! dedication.  Actually sending the queries (e.g. to <https://overpass-api.de/api/interpreter>)
+//! is left to the caller.
+
+#[cfg(feature = "serde")]
+use std::io::Read;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Write},
+};
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+use crate::{normalize::fold, Coordinates, Dedication, Distance, Doves, Ring, RingFilter};
+
+/// The tags which Overpass queries return for each element, after its type, ID and location
+const OVERPASS_TAGS: [&str; 2] = ["name", "alt_name"];
+
+/// The type of an element in OpenStreetMap
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum OsmType {
+    Node,
+    Way,
+    Relation,
+}
+
+/// The ID of an element in OpenStreetMap.  IDs are only unique within each [`OsmType`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct OsmId {
+    pub osm_type: OsmType,
+    pub id: u64,
+}
+
+/// A place of worship in OpenStreetMap, as returned by the queries from [`Ring::overpass_query`]
+/// or [`Doves::overpass_query`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OsmElement {
+    pub id: OsmId,
+    /// The location of the element, or the centre of its outline if it's a way or relation
+    pub coordinates: Coordinates,
+    pub name: Option<String>,
+    pub alt_name: Option<String>,
+}
+
+/// The OpenStreetMap elements which might be the church containing a [`Ring`], returned by
+/// [`Doves::match_osm`]
+#[derive(Debug, Clone)]
+pub struct OsmMatch<'d> {
+    pub ring: &'d Ring,
+    /// The candidate elements, best first.  This is empty if nothing was found near the `Ring`.
+    pub candidates: Vec<OsmCandidate>,
+}
+
+/// An OpenStreetMap element near a [`Ring`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OsmCandidate {
+    pub id: OsmId,
+    /// The distance from the `Ring`'s [best coordinates](Ring::best_coordinates) to the element
+    pub distance: Distance,
+    /// `true` if the element's name mentions a saint or feast from the `Ring`'s [`Dedication`]
+    pub dedication_matches: bool,
+}
+
+impl OsmType {
+    /// The name of this type in OpenStreetMap (e.g. `way`)
+    pub fn osm_name(self) -> &'static str {
+        match self {
+            OsmType::Node => "node",
+            OsmType::Way => "way",
+            OsmType::Relation => "relation",
+        }
+    }
+}
+
+impl Display for OsmType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.osm_name())
+    }
+}
+
+impl OsmId {
+    /// The URL of this element on <https://www.openstreetmap.org>
+    pub fn url(self) -> String {
+        format!("https://www.openstreetmap.org/{}", self)
+    }
+}
+
+impl Display for OsmId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.osm_type, self.id)
+    }
+}
+
+/// A row of the CSV returned by an Overpass query
+#[cfg(feature = "serde")]
+#[derive(Debug, Deserialize)]
+struct OverpassRow {
+    #[serde(rename = "@type")]
+    osm_type: OsmType,
+    #[serde(rename = "@id")]
+    id: u64,
+    #[serde(rename = "@lat")]
+    lat: Option<f64>,
+    #[serde(rename = "@lon")]
+    long: Option<f64>,
+    name: Option<String>,
+    alt_name: Option<String>,
+}
+
+impl OsmElement {
+    /// Reads the results of an Overpass query generated by this crate.  Elements without valid
+    /// coordinates are skipped.
+    #[cfg(feature = "serde")]
+    pub fn from_overpass_csv(reader: impl Read) -> Result<Vec<Self>, csv::Error> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .quoting(false)
+            .from_reader(reader);
+        let mut elements = Vec::new();
+        for row in reader.deserialize() {
+            let row: OverpassRow = row?;
+            let coordinates = match (row.lat, row.long) {
+                (Some(lat), Some(long)) => match Coordinates::new(lat, long) {
+                    Ok(coords) => coords,
+                    Err(_) => continue,
+                },
+                _ => continue,
+            };
+            elements.push(OsmElement {
+                id: OsmId {
+                    osm_type: row.osm_type,
+                    id: row.id,
+                },
+                coordinates,
+                name: row.name.filter(|s| !s.is_empty()),
+                alt_name: row.alt_name.filter(|s| !s.is_empty()),
+            });
+        }
+        Ok(elements)
+    }
+
+    /// `true` if this element's name (or alternative name) mentions a saint or feast from a
+    /// [`Dedication`] (e.g. `St Mary's Church` and `S Mary V`)
+    pub fn mentions_dedication(&self, dedication: &Dedication) -> bool {
+        let words = [&self.name, &self.alt_name]
+            .iter()
+            .filter_map(|name| name.as_deref())
+            .flat_map(|name| fold(name).split(' ').map(str::to_owned).collect::<Vec<_>>())
+            // Normalize the spelling of saints' names (e.g. `Nicolas` to `Nicholas`)
+            .flat_map(|word| {
+                let normalized = Dedication::normalize_name(&word).map(|n| fold(&n));
+                std::iter::once(word).chain(normalized)
+            })
+            .collect::<Vec<_>>();
+        dedication.names().iter().any(|saint| {
+            fold(saint)
+                .split(' ')
+                .all(|word| words.iter().any(|w| w == word))
+        })
+    }
+}
+
+impl Ring {
+    /// An Overpass API query for every place of worship within `radius` of this `Ring`'s
+    /// [best coordinates](Ring::best_coordinates), or `None` if it doesn't have coordinates.  The
+    /// results can be read with [`OsmElement::from_overpass_csv`].
+    pub fn overpass_query(&self, radius: Distance) -> Option<String> {
+        let (coords, _) = self.best_coordinates()?;
+        Some(overpass_query(&[coords], radius))
+    }
+}
+
+impl Doves {
+    /// A single Overpass API query for every place of worship within `radius` of any [`Ring`]
+    /// which passes a [`RingFilter`].  The results can be read with
+    /// [`OsmElement::from_overpass_csv`] and passed to [`Doves::match_osm`].  Overpass servers
+    /// limit the size of queries, so large sets of `Ring`s should be split up (e.g. by county).
+    pub fn overpass_query(&self, radius: Distance, filter: impl RingFilter) -> String {
+        let points = self
+            .filter(filter)
+            .filter_map(|ring| Some(ring.best_coordinates()?.0))
+            .collect::<Vec<_>>();
+        overpass_query(&points, radius)
+    }
+
+    /// Matches OpenStreetMap elements back to the [`Ring`]s which pass a [`RingFilter`].  Every
+    /// element within `radius` of a `Ring` is a candidate for that `Ring`; candidates whose names
+    /// mention the `Ring`'s [`Dedication`] come first, then the rest by distance.  Every `Ring`
+    /// with coordinates is returned (in the order they appear in Dove's Guide), so `Ring`s with
+    /// no candidates show where OpenStreetMap is missing a church.
+    pub fn match_osm(
+        &self,
+        elements: &[OsmElement],
+        radius: Distance,
+        filter: impl RingFilter,
+    ) -> Vec<OsmMatch<'_>> {
+        let mut candidates = HashMap::<usize, Vec<OsmCandidate>>::new();
+        for element in elements {
+            for idx in self.spatial_index().by_distance(element.coordinates) {
+                let ring = &self.towers[idx];
+                let distance = ring.distance_to(element.coordinates).unwrap();
+                if distance > radius {
+                    break;
+                }
+                candidates.entry(idx).or_default().push(OsmCandidate {
+                    id: element.id,
+                    distance,
+                    dedication_matches: element.mentions_dedication(&ring.dedication),
+                });
+            }
+        }
+
+        self.towers
+            .iter()
+            .enumerate()
+            .filter(|(_, ring)| ring.best_coordinates().is_some() && filter.matches(ring))
+            .map(|(idx, ring)| {
+                let mut candidates = candidates.remove(&idx).unwrap_or_default();
+                candidates.sort_by(|a, b| {
+                    b.dedication_matches
+                        .cmp(&a.dedication_matches)
+                        .then_with(|| a.distance.km().total_cmp(&b.distance.km()))
+                        .then_with(|| a.id.cmp(&b.id))
+                });
+                OsmMatch { ring, candidates }
+            })
+            .collect()
+    }
+}
+
+/// An Overpass QL query for the places of worship within `radius` of any of `points`, returning
+/// CSV in the format read by [`OsmElement::from_overpass_csv`]
+fn overpass_query(points: &[Coordinates], radius: Distance) -> String {
+    let metres = (radius.km() * 1000.0).round();
+    let mut query = format!(
+        "[out:csv(::type,::id,::lat,::lon,{};true)];\n(\n",
+        OVERPASS_TAGS.join(",")
+    );
+    for coords in points {
+        writeln!(
+            query,
+            "  nwr[\"amenity\"=\"place_of_worship\"](around:{},{:.5},{:.5});",
+            metres,
+            coords.lat(),
+            coords.long()
+        )
+        .unwrap();
+    }
+    query.push_str(");\nout center;\n");
+    query
+}
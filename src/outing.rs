@@ -0,0 +1,162 @@
+//! Planning ringing outings: choosing a set of nearby towers and an order in which to visit them.
+
+use std::collections::HashSet;
+
+use crate::{Coordinates, Distance, Doves, Ring, TowerId};
+
+/// The constraints for planning an [`Outing`], created with [`Doves::plan_outing`]
+#[derive(Debug, Clone)]
+pub struct OutingPlanner<'d> {
+    doves: &'d Doves,
+    start: Coordinates,
+    radius: Distance,
+    num_towers: usize,
+    min_bells: Option<u8>,
+    ringable_only: bool,
+    excluded: HashSet<TowerId>,
+}
+
+/// A planned outing: a sequence of towers to visit, created by [`OutingPlanner::plan`]
+#[derive(Debug, Clone)]
+pub struct Outing<'d> {
+    /// The towers to visit, in order
+    pub stops: Vec<OutingStop<'d>>,
+}
+
+/// One tower visited on an [`Outing`]
+#[derive(Debug, Clone, Copy)]
+pub struct OutingStop<'d> {
+    pub ring: &'d Ring,
+    /// The straight-line distance from the previous stop (or from the start, for the first stop)
+    pub distance_from_previous: Distance,
+}
+
+impl Doves {
+    /// Starts planning an [`Outing`] to `num_towers` towers, all within `radius` of `start`.  Add
+    /// any other constraints to the [`OutingPlanner`], then call [`OutingPlanner::plan`].
+    pub fn plan_outing(
+        &self,
+        start: Coordinates,
+        radius: Distance,
+        num_towers: usize,
+    ) -> OutingPlanner<'_> {
+        OutingPlanner {
+            doves: self,
+            start,
+            radius,
+            num_towers,
+            min_bells: None,
+            ringable_only: false,
+            excluded: HashSet::new(),
+        }
+    }
+}
+
+impl<'d> OutingPlanner<'d> {
+    /// Only visit [`Ring`]s with at least `bells` bells
+    pub fn min_bells(mut self, bells: u8) -> Self {
+        self.min_bells = Some(bells);
+        self
+    }
+
+    /// Only visit [`Ring`]s which can currently be rung (see [`Ring::is_ringable`])
+    pub fn ringable_only(mut self) -> Self {
+        self.ringable_only = true;
+        self
+    }
+
+    /// Don't visit any of the given towers (e.g. those visited on recent outings)
+    pub fn exclude(mut self, ids: impl IntoIterator<Item = TowerId>) -> Self {
+        self.excluded.extend(ids);
+        self
+    }
+
+    /// `true` if `ring` can be visited on this outing (ignoring the distance from the start)
+    fn is_allowed(&self, ring: &Ring) -> bool {
+        self.min_bells.is_none_or(|min| ring.bells >= min)
+            && (!self.ringable_only || ring.is_ringable())
+            && !self.excluded.contains(&ring.id)
+    }
+
+    /// Chooses the towers and the order in which to visit them, or returns `None` if not enough
+    /// towers satisfy the constraints.  Towers are chosen close to the start and close to each
+    /// other, and the route is then shortened by reordering the stops.  Each tower is visited at
+    /// most once, even if it contains several [`Ring`]s.
+    pub fn plan(&self) -> Option<Outing<'d>> {
+        let mut seen_towers = HashSet::new();
+        let mut candidates = self
+            .doves
+            .within_radius(self.start, self.radius, |ring: &Ring| self.is_allowed(ring))
+            .into_iter()
+            .map(|nearby| nearby.ring)
+            .filter(|ring| seen_towers.insert(ring.id))
+            .collect::<Vec<_>>();
+        if candidates.len() < self.num_towers {
+            return None;
+        }
+
+        // Greedily choose the towers, always moving to the closest unvisited one
+        let coords = |ring: &Ring| ring.best_coordinates().unwrap().0;
+        let mut route = Vec::<&Ring>::with_capacity(self.num_towers);
+        let mut position = self.start;
+        while route.len() < self.num_towers {
+            let (idx, _) = candidates
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| {
+                    let dist_a = coords(a).distance_to(position).km();
+                    dist_a.total_cmp(&coords(b).distance_to(position).km())
+                })
+                .expect("There should be enough candidates");
+            let ring = candidates.swap_remove(idx);
+            position = coords(ring);
+            route.push(ring);
+        }
+
+        // Shorten the route using 2-opt: reverse any section of the route which makes it shorter,
+        // until no more improvements can be made
+        let points = std::iter::once(self.start)
+            .chain(route.iter().map(|r| coords(r)))
+            .collect::<Vec<_>>();
+        let mut order = (0..points.len()).collect::<Vec<_>>();
+        let dist = |a: usize, b: usize| points[a].distance_to(points[b]).km();
+        let mut improved = true;
+        while improved {
+            improved = false;
+            // Reverse `order[i..=j]`, never moving the start (at index 0)
+            for i in 1..order.len() {
+                for j in i + 1..order.len() {
+                    let before = dist(order[i - 1], order[i])
+                        + order.get(j + 1).map_or(0.0, |&next| dist(order[j], next));
+                    let after = dist(order[i - 1], order[j])
+                        + order.get(j + 1).map_or(0.0, |&next| dist(order[i], next));
+                    if after < before - 1e-9 {
+                        order[i..=j].reverse();
+                        improved = true;
+                    }
+                }
+            }
+        }
+
+        let stops = order
+            .windows(2)
+            .map(|w| OutingStop {
+                ring: route[w[1] - 1],
+                distance_from_previous: points[w[0]].distance_to(points[w[1]]),
+            })
+            .collect();
+        Some(Outing { stops })
+    }
+}
+
+impl Outing<'_> {
+    /// The total straight-line distance travelled from the start to the last stop
+    pub fn total_distance(&self) -> Distance {
+        Distance::from_km(
+            self.stops
+                .iter()
+                .map(|stop| stop.distance_from_previous.km())
+                .sum(),
+        )
+    }
+}
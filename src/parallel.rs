@@ -0,0 +1,36 @@
+//! Parallel iteration over the [`Ring`]s in a [`Doves`] using [`rayon`], for heavy per-`Ring`
+//! computations (e.g. validation or distance matrices).  Alongside these impls, rayon provides
+//! [`par_iter`](rayon::iter::IntoParallelRefIterator::par_iter) and
+//! [`par_iter_mut`](rayon::iter::IntoParallelRefMutIterator::par_iter_mut) on `Doves`.
+
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator};
+
+use crate::{Doves, Ring};
+
+impl IntoParallelIterator for Doves {
+    type Item = Ring;
+    type Iter = rayon::vec::IntoIter<Ring>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.towers.into_par_iter()
+    }
+}
+
+impl<'d> IntoParallelIterator for &'d Doves {
+    type Item = &'d Ring;
+    type Iter = rayon::slice::Iter<'d, Ring>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.towers.par_iter()
+    }
+}
+
+impl<'d> IntoParallelIterator for &'d mut Doves {
+    type Item = &'d mut Ring;
+    type Iter = rayon::slice::IterMut<'d, Ring>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.invalidate_indices();
+        self.towers.par_iter_mut()
+    }
+}
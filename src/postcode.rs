@@ -0,0 +1,205 @@
+use std::{
+    convert::Infallible,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{Coordinates, CountryCode, Doves, Ring, SmolStr};
+
+/// The postcode of a [`Ring`].  Most `Ring`s have UK-style postcodes (e.g. `OX4 1EZ`), which are
+/// split into an [outcode](Self::outcode) and [incode](Self::incode).  Postcodes from other
+/// countries (ZIP codes, Eircodes, etc.) are kept verbatim, but aren't split.
+///
+/// **CSV Header**: `Postcode`
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Postcode {
+    /// The postcode, in upper case
+    code: SmolStr,
+    /// The length of the outcode, if this is a UK-style postcode
+    outcode_len: Option<u8>,
+}
+
+impl Postcode {
+    /// The postcode, as written in Dove's Guide (but in upper case)
+    pub fn as_str(&self) -> &str {
+        &self.code
+    }
+
+    /// `true` if this is a UK-style postcode.  This includes the Crown Dependencies, which use the
+    /// same format (e.g. `JE2 4UE`).
+    pub fn is_uk_style(&self) -> bool {
+        self.outcode_len.is_some()
+    }
+
+    /// The outward part of a UK-style postcode (e.g. `OX4` for `OX4 1EZ`)
+    pub fn outcode(&self) -> Option<&str> {
+        Some(&self.code[..usize::from(self.outcode_len?)])
+    }
+
+    /// The inward part of a UK-style postcode (e.g. `1EZ` for `OX4 1EZ`)
+    pub fn incode(&self) -> Option<&str> {
+        Some(&self.code[usize::from(self.outcode_len?) + 1..])
+    }
+
+    /// The postcode area of a UK-style postcode (e.g. `OX` for `OX4 1EZ`)
+    pub fn area(&self) -> Option<&str> {
+        let outcode = self.outcode()?;
+        let area_len = outcode
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(outcode.len());
+        Some(&outcode[..area_len])
+    }
+
+    /// `true` if this postcode is in the format used by the country with the given ISO code,
+    /// or `None` if this crate doesn't know that country's format.  UK-style postcodes are only
+    /// valid for the UK if they aren't in one of the Crown Dependencies' areas (`GY`, `JE` and
+    /// `IM`), which are only valid for [`CountryCode::GG`], [`CountryCode::JE`] and
+    /// [`CountryCode::IM`] respectively.
+    pub fn is_valid_for(&self, country: CountryCode) -> Option<bool> {
+        let area = self.area();
+        Some(match country {
+            CountryCode::GB => area.is_some_and(|a| !CROWN_DEPENDENCY_AREAS.contains(&a)),
+            CountryCode::GG => area == Some("GY"),
+            CountryCode::JE => area == Some("JE"),
+            CountryCode::IM => area == Some("IM"),
+            _ => {
+                let (_, formats) = FORMATS.iter().find(|(code, _)| *code == country)?;
+                formats.iter().any(|f| matches_format(&self.code, f))
+            }
+        })
+    }
+}
+
+/// The UK-style postcode areas used by the Crown Dependencies
+const CROWN_DEPENDENCY_AREAS: [&str; 3] = ["GY", "JE", "IM"];
+
+/// The postcode formats of countries which don't use UK-style postcodes, where `9` stands for a
+/// digit, `A` for a letter and `X` for either
+static FORMATS: [(CountryCode, &[&str]); 12] = [
+    (CountryCode::AU, &["9999"]),
+    (CountryCode::BE, &["9999"]),
+    (CountryCode::CA, &["A9A 9A9"]),
+    (CountryCode::ES, &["99999"]),
+    (CountryCode::FR, &["99999"]),
+    // Eircodes, where the routing key is a letter and two digits (except `D6W`)
+    (CountryCode::IE, &["A99 XXXX", "A9A XXXX"]),
+    (CountryCode::IN, &["999999"]),
+    (CountryCode::NL, &["9999 AA"]),
+    (CountryCode::NZ, &["9999"]),
+    (CountryCode::SG, &["999999"]),
+    (CountryCode::US, &["99999", "99999-9999"]),
+    (CountryCode::ZA, &["9999"]),
+];
+
+/// `true` if a (normalized) postcode matches a format from [`FORMATS`]
+fn matches_format(code: &str, format: &str) -> bool {
+    code.len() == format.len()
+        && code.bytes().zip(format.bytes()).all(|(c, f)| match f {
+            b'9' => c.is_ascii_digit(),
+            b'A' => c.is_ascii_uppercase(),
+            b'X' => c.is_ascii_digit() || c.is_ascii_uppercase(),
+            _ => c == f,
+        })
+}
+
+/// Returns the length of the outcode if `code` is a (normalized) UK-style postcode, which have
+/// the form `A9 9AA`, `A99 9AA`, `A9A 9AA`, `AA9 9AA`, `AA99 9AA` or `AA9A 9AA`.
+fn uk_outcode_len(code: &str) -> Option<u8> {
+    let (outcode, incode) = code.split_once(' ')?;
+    let out = outcode.as_bytes();
+    let area_len = out.iter().take_while(|b| b.is_ascii_uppercase()).count();
+    let district = &out[area_len..];
+    let valid_outcode = (1..=2).contains(&area_len)
+        && matches!(district, [d] | [d, _] if d.is_ascii_digit())
+        && district.iter().all(u8::is_ascii_alphanumeric);
+    let valid_incode = matches!(
+        incode.as_bytes(),
+        [d, a, b] if d.is_ascii_digit() && a.is_ascii_uppercase() && b.is_ascii_uppercase()
+    );
+    // Valid outcodes have at most 4 characters
+    (valid_outcode && valid_incode).then_some(outcode.len() as u8)
+}
+
+impl Display for Postcode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl FromStr for Postcode {
+    type Err = Infallible;
+
+    /// Parses a `Postcode`, normalizing case and whitespace.  UK-style postcodes written without
+    /// a space (e.g. `OX41EZ`) have one added.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut code = s
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_ascii_uppercase();
+        if !code.contains(' ') && code.len() > 3 {
+            let with_space = format!("{} {}", &code[..code.len() - 3], &code[code.len() - 3..]);
+            if uk_outcode_len(&with_space).is_some() {
+                code = with_space;
+            }
+        }
+        Ok(Self {
+            outcode_len: uk_outcode_len(&code),
+            code: SmolStr::new(code),
+        })
+    }
+}
+
+impl Doves {
+    /// Gets every [`Ring`] whose postcode is in a given UK postcode area (e.g. `"OX"`), in the
+    /// order they appear in Dove's Guide.  Case is ignored.
+    pub fn in_postcode_area(&self, area: &str) -> Vec<&Ring> {
+        let area = area.trim().to_ascii_uppercase();
+        self.rings_at(self.postcode_index().get(area.as_str()))
+    }
+
+    /// Gets every [`Ring`] whose postcode has a given UK outcode (e.g. `"OX4"`), in the order
+    /// they appear in Dove's Guide.  Case is ignored.
+    pub fn in_outcode(&self, outcode: &str) -> Vec<&Ring> {
+        let outcode = outcode.trim();
+        let area_len = outcode
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(outcode.len());
+        let area = outcode[..area_len].to_ascii_uppercase();
+        self.rings_at(self.postcode_index().get(area.as_str()))
+            .into_iter()
+            .filter(|ring| {
+                let ring_outcode = ring.postcode.as_ref().and_then(Postcode::outcode);
+                ring_outcode.is_some_and(|o| o.eq_ignore_ascii_case(outcode))
+            })
+            .collect()
+    }
+
+    /// Estimates the [`Coordinates`] of a postcode from the `Ring`s in Dove's Guide, without
+    /// needing a geocoding service.  This averages the coordinates of every [`Ring`] with exactly
+    /// that postcode or, if there are none, every `Ring` with the same outcode (so is only
+    /// accurate to a few miles).  Returns `None` if no `Ring`s with coordinates share either.
+    pub fn locate_postcode(&self, postcode: &Postcode) -> Option<Coordinates> {
+        let same_code = self.mean_coordinates(|p| p == postcode);
+        same_code.or_else(|| {
+            let outcode = postcode.outcode()?;
+            self.mean_coordinates(|p| p.outcode() == Some(outcode))
+        })
+    }
+
+    /// The mean [`Coordinates`] of every `Ring` whose postcode passes `pred`
+    fn mean_coordinates(&self, pred: impl Fn(&Postcode) -> bool) -> Option<Coordinates> {
+        let (mut lat, mut long, mut count) = (0.0, 0.0, 0);
+        for ring in self.iter() {
+            if let (Some(postcode), Some(coords)) = (&ring.postcode, ring.coordinates) {
+                if pred(postcode) {
+                    lat += coords.lat();
+                    long += coords.long();
+                    count += 1;
+                }
+            }
+        }
+        let count = f64::from(count);
+        (count > 0.0).then(|| Coordinates::new(lat / count, long / count).unwrap())
+    }
+}
@@ -0,0 +1,283 @@
+//! Parsing Dove's descriptions of practice nights (e.g. `Thu (1st, 3rd) 19:30`) into structured
+//! schedules.
+
+use std::fmt::{Display, Formatter};
+
+use crate::Ring;
+
+/// When a tower holds its practices, parsed from [`Ring::practice`] by [`Ring::practice_schedule`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PracticeSchedule {
+    /// The regular practice sessions, which is empty if the practice is by arrangement
+    pub sessions: Vec<PracticeSession>,
+    /// Any description which applies to the whole schedule (e.g. `by arrangement`)
+    pub note: Option<String>,
+}
+
+/// A regular practice on one day of the week
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PracticeSession {
+    pub day: Weekday,
+    /// Which weeks the practice is held
+    pub weeks: Weeks,
+    /// The start time as `(hour, minute)`, if Dove's gives one
+    pub time: Option<(u8, u8)>,
+    /// Any further details (e.g. `not always: check` or `term only`)
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+/// The weeks of the month in which a [`PracticeSession`] is held
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Weeks {
+    /// Every week
+    Every,
+    /// Only the given weeks of the month (e.g. the 1st and 3rd Thursdays)
+    Only(Vec<WeekOfMonth>),
+    /// Every week except the given weeks of the month
+    Except(Vec<WeekOfMonth>),
+    /// Every other week
+    Alternate,
+}
+
+/// Which occurrence of a day within a month (e.g. the 2nd Tuesday, or the last Friday)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum WeekOfMonth {
+    /// The `n`th occurrence, where `n` is from 1 to 5
+    Nth(u8),
+    Last,
+}
+
+/// The days of the week, with their abbreviations in Dove's Guide
+static WEEKDAYS: [(Weekday, &str); 7] = [
+    (Weekday::Monday, "Mon"),
+    (Weekday::Tuesday, "Tue"),
+    (Weekday::Wednesday, "Wed"),
+    (Weekday::Thursday, "Thu"),
+    (Weekday::Friday, "Fri"),
+    (Weekday::Saturday, "Sat"),
+    (Weekday::Sunday, "Sun"),
+];
+
+impl Weekday {
+    /// The abbreviation used for this `Weekday` in Dove's Guide (e.g. `Tue`)
+    pub fn dove_abbreviation(self) -> &'static str {
+        WEEKDAYS[self as usize].1
+    }
+}
+
+impl Display for Weekday {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.dove_abbreviation())
+    }
+}
+
+impl Ring {
+    /// Parses this `Ring`'s [practice night description](Ring::practice) into a
+    /// [`PracticeSchedule`].  Returns `None` if there's no description, or if it isn't in
+    /// Dove's usual format (e.g. `Mon`, `Thu (1st, 3rd) 19:30`, `Tue (alt) & Sat 10:00` or
+    /// `PN: by arrangement`).
+    pub fn practice_schedule(&self) -> Option<PracticeSchedule> {
+        self.practice.as_deref().and_then(parse_schedule)
+    }
+}
+
+pub(crate) fn parse_schedule(s: &str) -> Option<PracticeSchedule> {
+    let s = s.trim();
+    if let Some(note) = s.strip_prefix("PN:") {
+        return Some(PracticeSchedule {
+            sessions: Vec::new(),
+            note: Some(note.trim().to_owned()),
+        });
+    }
+    let sessions = s
+        .split(" & ")
+        .map(parse_session)
+        .collect::<Option<Vec<_>>>()?;
+    Some(PracticeSchedule {
+        sessions,
+        note: None,
+    })
+}
+
+/// Parses one session of a practice description, e.g. `Thu (1st, 3rd: check) 19:30`
+fn parse_session(s: &str) -> Option<PracticeSession> {
+    let s = s.trim();
+    let (day, rest) = s.split_at(s.find(' ').unwrap_or(s.len()));
+    let day = WEEKDAYS.iter().find(|(_, abbr)| *abbr == day)?.0;
+    let mut rest = rest.trim();
+
+    let mut weeks = Weeks::Every;
+    let mut note = None;
+    if let Some(after_open) = rest.strip_prefix('(') {
+        let (qualifier, after_close) = after_open.split_once(')')?;
+        rest = after_close.trim();
+        let (head, tail) = match qualifier.split_once(':') {
+            Some((head, tail)) => (head.trim(), Some(tail.trim())),
+            None => (qualifier.trim(), None),
+        };
+        match parse_weeks(head) {
+            Some(w) => {
+                weeks = w;
+                note = tail.map(str::to_owned);
+            }
+            None => note = Some(qualifier.trim().to_owned()),
+        }
+    }
+
+    let time = match rest {
+        "" => None,
+        _ => Some(parse_time(rest)?),
+    };
+    Some(PracticeSession {
+        day,
+        weeks,
+        time,
+        note,
+    })
+}
+
+/// Parses the part of a qualifier which says which weeks a practice is held (e.g. `1st, 3rd`,
+/// `exc last` or `alt`)
+fn parse_weeks(s: &str) -> Option<Weeks> {
+    if s == "alt" {
+        return Some(Weeks::Alternate);
+    }
+    let (list, is_exception) = match s.strip_prefix("exc ") {
+        Some(list) => (list, true),
+        None => (s, false),
+    };
+    let weeks = list
+        .split(',')
+        .map(|w| match w.trim() {
+            "1st" => Some(WeekOfMonth::Nth(1)),
+            "2nd" => Some(WeekOfMonth::Nth(2)),
+            "3rd" => Some(WeekOfMonth::Nth(3)),
+            "4th" => Some(WeekOfMonth::Nth(4)),
+            "5th" => Some(WeekOfMonth::Nth(5)),
+            "last" => Some(WeekOfMonth::Last),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(if is_exception {
+        Weeks::Except(weeks)
+    } else {
+        Weeks::Only(weeks)
+    })
+}
+
+/// Parses a 24-hour time like `19:30`
+fn parse_time(s: &str) -> Option<(u8, u8)> {
+    let (hour, minute) = s.split_once(':')?;
+    let (hour, minute) = (hour.parse::<u8>().ok()?, minute.parse::<u8>().ok()?);
+    (hour < 24 && minute < 60).then_some((hour, minute))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_schedule, PracticeSession, WeekOfMonth, Weekday, Weeks};
+
+    fn session(day: Weekday, weeks: Weeks, time: Option<(u8, u8)>) -> PracticeSession {
+        PracticeSession {
+            day,
+            weeks,
+            time,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn every_week() {
+        let schedule = parse_schedule("Mon 19:30").unwrap();
+        assert_eq!(
+            schedule.sessions,
+            [session(Weekday::Monday, Weeks::Every, Some((19, 30)))]
+        );
+        assert_eq!(schedule.note, None);
+        assert_eq!(
+            parse_schedule("Sun").unwrap().sessions,
+            [session(Weekday::Sunday, Weeks::Every, None)]
+        );
+    }
+
+    #[test]
+    fn weeks_of_month() {
+        assert_eq!(
+            parse_schedule("Thu (1st, 3rd) 19:30").unwrap().sessions,
+            [session(
+                Weekday::Thursday,
+                Weeks::Only(vec![WeekOfMonth::Nth(1), WeekOfMonth::Nth(3)]),
+                Some((19, 30))
+            )]
+        );
+        assert_eq!(
+            parse_schedule("Wed (exc 1st) 20:00").unwrap().sessions,
+            [session(
+                Weekday::Wednesday,
+                Weeks::Except(vec![WeekOfMonth::Nth(1)]),
+                Some((20, 0))
+            )]
+        );
+        assert_eq!(
+            parse_schedule("Fri (exc 2nd, last)").unwrap().sessions,
+            [session(
+                Weekday::Friday,
+                Weeks::Except(vec![WeekOfMonth::Nth(2), WeekOfMonth::Last]),
+                None
+            )]
+        );
+    }
+
+    #[test]
+    fn alternate_and_multiple_sessions() {
+        assert_eq!(
+            parse_schedule("Tue (alt) & Sat 10:00").unwrap().sessions,
+            [
+                session(Weekday::Tuesday, Weeks::Alternate, None),
+                session(Weekday::Saturday, Weeks::Every, Some((10, 0))),
+            ]
+        );
+    }
+
+    #[test]
+    fn notes() {
+        let schedule = parse_schedule("PN: by arrangement").unwrap();
+        assert!(schedule.sessions.is_empty());
+        assert_eq!(schedule.note.as_deref(), Some("by arrangement"));
+
+        let sessions = parse_schedule("Thu (1st: check) 19:30").unwrap().sessions;
+        assert_eq!(sessions[0].weeks, Weeks::Only(vec![WeekOfMonth::Nth(1)]));
+        assert_eq!(sessions[0].note.as_deref(), Some("check"));
+
+        // Qualifiers which aren't weeks are kept as notes
+        let sessions = parse_schedule("Mon (term only) 19:00").unwrap().sessions;
+        assert_eq!(sessions[0].weeks, Weeks::Every);
+        assert_eq!(sessions[0].note.as_deref(), Some("term only"));
+    }
+
+    #[test]
+    fn unparseable() {
+        for s in [
+            "",
+            "Monday",
+            "Mon 25:00",
+            "Mon 19:60",
+            "Mon 7pm",
+            "Thu (1st 19:30",
+            "Mon & ",
+            "occasional",
+        ] {
+            assert_eq!(parse_schedule(s), None, "{:?}", s);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{normalize_county, Affiliation, Doves, Ring, SmolStr, Weight, WeightClass};
+
+/// A set of constraints on [`Ring`]s, created with [`Doves::query`].  Constraints are combined
+/// with 'and', so a `Ring` has to satisfy every constraint to match.
+#[derive(Debug, Clone)]
+pub struct Query<'d> {
+    doves: &'d Doves,
+    min_bells: Option<u8>,
+    max_bells: Option<u8>,
+    ringable_only: bool,
+    ground_floor: bool,
+    toilet: bool,
+    simulator: bool,
+    affiliation: Option<Affiliation>,
+    county: Option<SmolStr>,
+    min_weight: Option<Weight>,
+    max_weight: Option<Weight>,
+    weight_class: Option<WeightClass>,
+}
+
+impl Doves {
+    /// Starts a [`Query`] which matches every [`Ring`] in this `Doves`
+    pub fn query(&self) -> Query<'_> {
+        Query {
+            doves: self,
+            min_bells: None,
+            max_bells: None,
+            ringable_only: false,
+            ground_floor: false,
+            toilet: false,
+            simulator: false,
+            affiliation: None,
+            county: None,
+            min_weight: None,
+            max_weight: None,
+            weight_class: None,
+        }
+    }
+}
+
+impl<'d> Query<'d> {
+    /// Only match [`Ring`]s with at least `bells` bells
+    pub fn min_bells(mut self, bells: u8) -> Self {
+        self.min_bells = Some(bells);
+        self
+    }
+
+    /// Only match [`Ring`]s with at most `bells` bells
+    pub fn max_bells(mut self, bells: u8) -> Self {
+        self.max_bells = Some(bells);
+        self
+    }
+
+    /// Only match [`Ring`]s which can currently be rung (see [`Ring::is_ringable`])
+    pub fn ringable_only(mut self) -> Self {
+        self.ringable_only = true;
+        self
+    }
+
+    /// Only match [`Ring`]s which are rung from the ground floor
+    pub fn ground_floor(mut self) -> Self {
+        self.ground_floor = true;
+        self
+    }
+
+    /// Only match [`Ring`]s in churches with a toilet
+    pub fn toilet(mut self) -> Self {
+        self.toilet = true;
+        self
+    }
+
+    /// Only match [`Ring`]s with a simulator
+    pub fn simulator(mut self) -> Self {
+        self.simulator = true;
+        self
+    }
+
+    /// Only match [`Ring`]s affiliated to `affiliation`
+    pub fn affiliation(mut self, affiliation: Affiliation) -> Self {
+        self.affiliation = Some(affiliation);
+        self
+    }
+
+    /// Only match [`Ring`]s in `county`, which is normalized with [`normalize_county`] (so
+    /// `yorkshire, west` matches `West Yorkshire`)
+    pub fn county(mut self, county: impl Into<SmolStr>) -> Self {
+        self.county = Some(normalize_county(&county.into()));
+        self
+    }
+
+    /// Only match [`Ring`]s whose tenor is at least as heavy as `weight`.  `Ring`s with no known
+    /// weight never match.
+    pub fn min_weight(mut self, weight: Weight) -> Self {
+        self.min_weight = Some(weight);
+        self
+    }
+
+    /// Only match [`Ring`]s whose tenor is no heavier than `weight`.  `Ring`s with no known weight
+    /// never match.
+    pub fn max_weight(mut self, weight: Weight) -> Self {
+        self.max_weight = Some(weight);
+        self
+    }
+
+    /// Only match [`Ring`]s whose tenor is in `class`.  `Ring`s with no known weight never match.
+    pub fn weight_class(mut self, class: WeightClass) -> Self {
+        self.weight_class = Some(class);
+        self
+    }
+
+    /// `true` if `ring` satisfies every constraint in this `Query`
+    pub fn matches(&self, ring: &Ring) -> bool {
+        let weight_lbs = ring.weight.map(Weight::lbs);
+        self.min_bells.is_none_or(|min| ring.bells >= min)
+            && self.max_bells.is_none_or(|max| ring.bells <= max)
+            && (!self.ringable_only || ring.is_ringable())
+            && (!self.ground_floor || ring.ground_floor())
+            && (!self.toilet || ring.toilet())
+            && (!self.simulator || ring.simulator())
+            && self
+                .affiliation
+                .as_ref()
+                .is_none_or(|a| ring.affiliations.contains(a))
+            && self.county.as_ref().is_none_or(|county| {
+                ring.county
+                    .as_ref()
+                    .is_some_and(|c| normalize_county(c) == *county)
+            })
+            && self
+                .min_weight
+                .is_none_or(|min| weight_lbs.is_some_and(|lbs| lbs >= min.lbs()))
+            && self
+                .max_weight
+                .is_none_or(|max| weight_lbs.is_some_and(|lbs| lbs <= max.lbs()))
+            && self
+                .weight_class
+                .is_none_or(|class| ring.weight_class() == Some(class))
+    }
+
+    /// Returns an iterator over every matching [`Ring`], in the order they appear in Dove's Guide
+    pub fn iter(&self) -> impl Iterator<Item = &'d Ring> + '_ {
+        self.doves.iter().filter(move |ring| self.matches(ring))
+    }
+
+    /// The number of matching [`Ring`]s
+    pub fn count(&self) -> usize {
+        self.iter().count()
+    }
+}
+
+impl<'d> IntoIterator for Query<'d> {
+    type Item = &'d Ring;
+    type IntoIter = QueryIter<'d>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        QueryIter {
+            rings: self.doves.iter(),
+            query: self,
+        }
+    }
+}
+
+/// An iterator over the [`Ring`]s matching a [`Query`]
+#[derive(Debug, Clone)]
+pub struct QueryIter<'d> {
+    query: Query<'d>,
+    rings: std::slice::Iter<'d, Ring>,
+}
+
+impl<'d> Iterator for QueryIter<'d> {
+    type Item = &'d Ring;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let query = &self.query;
+        self.rings.find(|ring| query.matches(ring))
+    }
+}
@@ -0,0 +1,19 @@
+use rand::{Rng, RngExt};
+
+use crate::{Doves, Ring, RingFilter};
+
+impl Doves {
+    /// Picks a uniformly random [`Ring`] out of those which pass a [`RingFilter`] (e.g. a
+    /// [`Query`](crate::Query)), or `None` if no `Ring`s pass.  This makes one pass over the
+    /// `Ring`s without collecting the matches, using reservoir sampling.
+    pub fn random<R: Rng + ?Sized>(&self, rng: &mut R, filter: impl RingFilter) -> Option<&Ring> {
+        let mut chosen = None;
+        for (idx, ring) in self.filter(filter).enumerate() {
+            // Replace the chosen `Ring` with probability `1 / (number of matches so far)`
+            if rng.random_range(0..=idx) == 0 {
+                chosen = Some(ring);
+            }
+        }
+        chosen
+    }
+}
@@ -0,0 +1,426 @@
+//! The raw layout of Dove's CSV file, before any fields are combined into the types exposed by
+//! this crate.
+
+use std::{
+    collections::BTreeSet,
+    convert::TryFrom,
+    fmt::{Display, Formatter},
+    num::ParseIntError,
+    time::SystemTime,
+};
+
+#[cfg(feature = "serde")]
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer};
+use smol_str::SmolStr;
+
+use crate::{
+    metadata::civil_date, AccessStatus, Affiliation, ChurchCareId, Contractor, Coordinates,
+    Country, CountryCode, Dedication, Details, Diocese, Frequency, InvalidCoordinates, Note,
+    Postcode, Ring, RingFeature, RingFlags, RingType, Symbol, TowerId, Weight,
+};
+
+/// A single row of Dove's CSV file.  Each field corresponds to exactly one column; fields made from
+/// several columns are combined when converting into a [`Ring`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(deny_unknown_fields))]
+pub(crate) struct RingRecord {
+    #[cfg_attr(feature = "serde", serde(rename = "TowerID"))]
+    pub(crate) id: TowerId,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "RingType", deserialize_with = "deser_from_str")
+    )]
+    pub(crate) ring_type: RingType,
+    #[cfg_attr(feature = "serde", serde(rename = "Bells"))]
+    pub(crate) bells: u8,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "UR", deserialize_with = "deser_not_empty")
+    )]
+    pub(crate) unringable: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "GF", deserialize_with = "deser_not_empty")
+    )]
+    pub(crate) ground_floor: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "Toilet", deserialize_with = "deser_not_empty")
+    )]
+    pub(crate) toilet: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "Simulator", deserialize_with = "deser_not_empty")
+    )]
+    pub(crate) simulator: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "Affiliations", deserialize_with = "deser_affiliations")
+    )]
+    pub(crate) affiliations: BTreeSet<Affiliation>,
+    #[cfg_attr(feature = "serde", serde(rename = "Practice"))]
+    pub(crate) practice: Option<String>,
+
+    #[cfg_attr(feature = "serde", serde(rename = "TowerBase"))]
+    pub(crate) towerbase_id: u32,
+    #[cfg_attr(feature = "serde", serde(rename = "DoveID"))]
+    pub(crate) dove_id: Option<SmolStr>,
+
+    #[cfg_attr(feature = "serde", serde(rename = "Wt"))]
+    pub(crate) weight_lbs: Option<f64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "Note", deserialize_with = "deser_option_from_str")
+    )]
+    pub(crate) note: Option<Note>,
+    #[cfg_attr(feature = "serde", serde(rename = "Hz"))]
+    pub(crate) freq: Option<Frequency>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "Details", deserialize_with = "deser_from_str")
+    )]
+    pub(crate) details: Details,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "ExtraInfo", deserialize_with = "deser_ring_features")
+    )]
+    pub(crate) extra_info: Vec<RingFeature>,
+    #[cfg_attr(feature = "serde", serde(rename = "WebPage"))]
+    pub(crate) url: Option<String>,
+
+    #[cfg_attr(feature = "serde", serde(rename = "Semitones"))]
+    pub(crate) semitones: Option<Box<str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "App", deserialize_with = "deser_not_empty")
+    )]
+    pub(crate) app: bool,
+
+    #[cfg_attr(feature = "serde", serde(rename = "Place"))]
+    pub(crate) place: SmolStr,
+    #[cfg_attr(feature = "serde", serde(rename = "Place2"))]
+    pub(crate) place2: Option<Box<str>>,
+    #[cfg_attr(feature = "serde", serde(rename = "PlaceCL"))]
+    pub(crate) place_county_list: Option<Box<str>>,
+    #[cfg_attr(feature = "serde", serde(rename = "County"))]
+    pub(crate) county: Option<SmolStr>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "Country", deserialize_with = "deser_option_from_str")
+    )]
+    pub(crate) country: Option<Country>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "ISO3166code", deserialize_with = "deser_option_from_str")
+    )]
+    pub(crate) iso_3166_code: Option<CountryCode>,
+    #[cfg_attr(feature = "serde", serde(rename = "NG"))]
+    pub(crate) os_grid_ref: Option<SmolStr>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "Postcode", deserialize_with = "deser_option_from_str")
+    )]
+    pub(crate) postcode: Option<Postcode>,
+
+    #[cfg_attr(feature = "serde", serde(rename = "Long"))]
+    pub(crate) long: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(rename = "Lat"))]
+    pub(crate) lat: Option<f64>,
+
+    #[cfg_attr(feature = "serde", serde(rename = "SNLong"))]
+    pub(crate) satnav_long: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(rename = "SNLat"))]
+    pub(crate) satnav_lat: Option<f64>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "OvhaulYr", deserialize_with = "deser_year")
+    )]
+    pub(crate) overhaul_year: Option<u16>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "Contractor", deserialize_with = "deser_option_from_str")
+    )]
+    pub(crate) contractor: Option<Contractor>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "TuneYr", deserialize_with = "deser_year")
+    )]
+    pub(crate) tune_year: Option<u16>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "BldgID", deserialize_with = "deser_option_not_none")
+    )]
+    pub(crate) building_id: Option<SmolStr>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "LGrade", deserialize_with = "deser_option_not_none")
+    )]
+    pub(crate) building_grade: Option<SmolStr>,
+
+    #[cfg_attr(feature = "serde", serde(rename = "ChurchCare"))]
+    pub(crate) church_care: Option<u32>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "Dedicn", deserialize_with = "deser_from_str")
+    )]
+    pub(crate) dedication: Dedication,
+    #[cfg_attr(feature = "serde", serde(rename = "AltName"))]
+    pub(crate) alt_name: Option<Box<str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "Diocese", deserialize_with = "deser_option_from_str")
+    )]
+    pub(crate) diocese: Option<Diocese>,
+}
+
+impl TryFrom<RingRecord> for Ring {
+    type Error = RecordError;
+
+    #[allow(deprecated)] // We still need to populate `dove_id`
+    fn try_from(r: RingRecord) -> Result<Self, Self::Error> {
+        let coordinates = combine_coordinates(r.lat, r.long, "Lat", "Long")?;
+        let satnav_coordinates =
+            combine_coordinates(r.satnav_lat, r.satnav_long, "SNLat", "SNLong")?;
+        let weight = r
+            .weight_lbs
+            .map(|lbs| Weight::from_lbs(lbs).with_approximate(r.app));
+        let church_care = r
+            .church_care
+            .map(|id| ChurchCareId::from_dove(id, r.diocese.as_ref(), r.country.as_ref()));
+        let mut flags = RingFlags::empty();
+        flags.set(RingFlags::GROUND_FLOOR, r.ground_floor);
+        flags.set(RingFlags::TOILET, r.toilet);
+        flags.set(RingFlags::SIMULATOR, r.simulator);
+        Ok(Ring {
+            id: r.id,
+            ring_type: r.ring_type,
+            bells: r.bells,
+            access: AccessStatus::from_dove(r.unringable, &r.extra_info),
+            flags,
+            affiliations: r.affiliations,
+            practice: r.practice,
+            towerbase_id: r.towerbase_id,
+            felstead_id: None,
+            dove_id: r.dove_id,
+            weight,
+            note: r.note,
+            freq: r.freq,
+            details: r.details,
+            extra_info: r.extra_info,
+            url: r.url,
+            semitones: r.semitones,
+            place: r.place,
+            place2: r.place2,
+            place_county_list: r.place_county_list,
+            county: r.county.map(Symbol::from),
+            country: r.country,
+            iso_3166_code: r.iso_3166_code,
+            os_grid_ref: r.os_grid_ref,
+            postcode: r.postcode,
+            coordinates,
+            satnav_coordinates,
+            overhaul_year: r.overhaul_year.filter(|&y| is_plausible_year(y)),
+            contractor: r.contractor,
+            tune_year: r.tune_year.filter(|&y| is_plausible_year(y)),
+            building_id: r.building_id,
+            building_grade: r.building_grade,
+            church_care,
+            dedication: r.dedication,
+            alt_name: r.alt_name,
+            diocese: r.diocese,
+        })
+    }
+}
+
+/// Combines a pair of latitude/longitude columns into [`Coordinates`], requiring that either both
+/// or neither are present.
+pub(crate) fn combine_coordinates(
+    lat: Option<f64>,
+    long: Option<f64>,
+    lat_header: &'static str,
+    long_header: &'static str,
+) -> Result<Option<Coordinates>, RecordError> {
+    match (lat, long) {
+        (Some(lat), Some(long)) => {
+            Coordinates::new(lat, long)
+                .map(Some)
+                .map_err(|error| RecordError::InvalidCoordinates {
+                    lat_header,
+                    long_header,
+                    error,
+                })
+        }
+        (None, None) => Ok(None),
+        (Some(_), None) => Err(RecordError::MissingColumn {
+            present: lat_header,
+            missing: long_header,
+        }),
+        (None, Some(_)) => Err(RecordError::MissingColumn {
+            present: long_header,
+            missing: lat_header,
+        }),
+    }
+}
+
+/// The earliest year which we accept for an overhaul or tuning.  Nothing earlier than this can
+/// sensibly describe work on a ring which is still hung today.
+const MIN_YEAR: u16 = 1500;
+
+/// `true` if `year` could be the year of an overhaul or tuning: no earlier than [`MIN_YEAR`] and
+/// not in the future.  Implausible years are dropped when loading, rather than failing the load,
+/// since whether a year is in the future depends on the system clock.
+pub(crate) fn is_plausible_year(year: u16) -> bool {
+    (MIN_YEAR..=current_year()).contains(&year)
+}
+
+/// The current year (in UTC), according to the system clock
+fn current_year() -> u16 {
+    civil_date(SystemTime::now()).0
+}
+
+/// The error generated when a [`RingRecord`] can't be converted into a [`Ring`].
+#[derive(Debug, Clone)]
+pub(crate) enum RecordError {
+    /// One column was set but another column, which must be set alongside it, was empty
+    MissingColumn {
+        present: &'static str,
+        missing: &'static str,
+    },
+    // The headers are only used by `Loader`, to find which columns to blank
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    InvalidCoordinates {
+        lat_header: &'static str,
+        long_header: &'static str,
+        error: InvalidCoordinates,
+    },
+}
+
+impl RecordError {
+    /// The headers of the columns which caused this error
+    #[cfg(feature = "serde")]
+    pub(crate) fn headers(&self) -> Vec<&'static str> {
+        match self {
+            RecordError::MissingColumn { present, .. } => vec![present],
+            RecordError::InvalidCoordinates {
+                lat_header,
+                long_header,
+                ..
+            } => vec![lat_header, long_header],
+        }
+    }
+}
+
+impl Display for RecordError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordError::MissingColumn { present, missing } => {
+                write!(f, "`{}` is set but `{}` is empty", present, missing)
+            }
+            RecordError::InvalidCoordinates { error, .. } => write!(f, "{}", error),
+        }
+    }
+}
+
+/////////////////////////////
+// DESERIALIZATION HELPERS //
+/////////////////////////////
+
+/// Serializes as `false` if the next string is empty and `true` otherwise.
+#[cfg(feature = "serde")]
+pub(crate) fn deser_not_empty<'de, D>(de: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    <&str>::deserialize(de).map(|s| !s.is_empty())
+}
+
+/// Serializes the next string as a `;`-delimited sequence of [`Affiliation`]s
+#[cfg(feature = "serde")]
+fn deser_affiliations<'de, D>(de: D) -> Result<BTreeSet<Affiliation>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    <&str>::deserialize(de).map(parse_affiliations)
+}
+
+/// Parses a `;`-delimited sequence of [`Affiliation`]s
+pub(crate) fn parse_affiliations(s: &str) -> BTreeSet<Affiliation> {
+    s.split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().unwrap())
+        .collect()
+}
+
+/// Serializes the next string as a [`SmolStr`], or `None` if the string is empty or `"None"`
+#[cfg(feature = "serde")]
+pub(crate) fn deser_option_not_none<'de, D>(de: D) -> Result<Option<SmolStr>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    <&str>::deserialize(de).map(parse_not_none)
+}
+
+/// Parses a string as a [`SmolStr`], or `None` if the string is empty or `"None"`
+pub(crate) fn parse_not_none(s: &str) -> Option<SmolStr> {
+    (!s.is_empty() && s != "None").then(|| SmolStr::new(s))
+}
+
+/// Serializes the next string using its [`FromStr`] implementation
+#[cfg(feature = "serde")]
+pub(crate) fn deser_from_str<'de, D, T>(de: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    <&str>::deserialize(de)?.parse().map_err(D::Error::custom)
+}
+
+/// Serializes the next string using its [`FromStr`] implementation, or `None` if the string is
+/// empty
+#[cfg(feature = "serde")]
+pub(crate) fn deser_option_from_str<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let s = <&str>::deserialize(de)?;
+    if s.is_empty() {
+        return Ok(None);
+    }
+    s.parse().map(Some).map_err(D::Error::custom)
+}
+
+/// Serializes the next string as a year, or `None` if the string is empty.  Dove's sometimes gives
+/// full dates (e.g. `27 Oct 2013`) rather than years, in which case only the year is kept.
+#[cfg(feature = "serde")]
+fn deser_year<'de, D>(de: D) -> Result<Option<u16>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    parse_year(<&str>::deserialize(de)?).map_err(D::Error::custom)
+}
+
+/// Parses a string as a year, or `None` if the string is empty.  Only the year of full dates (e.g.
+/// `27 Oct 2013`) is kept.
+pub(crate) fn parse_year(s: &str) -> Result<Option<u16>, ParseIntError> {
+    s.split_whitespace().last().map(str::parse).transpose()
+}
+
+/// Serializes the next string as a `;`-delimited sequence of [`RingFeature`]s
+#[cfg(feature = "serde")]
+fn deser_ring_features<'de, D>(de: D) -> Result<Vec<RingFeature>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    <&str>::deserialize(de).map(RingFeature::parse_list)
+}
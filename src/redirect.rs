@@ -0,0 +1,196 @@
+//! Resolving [`TowerId`]s which are no longer in Dove's Guide, because their towers were removed
+//! or merged into other entries.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Write,
+};
+#[cfg(feature = "serde")]
+use std::{fs::File, io::Read, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+use crate::{duplicate::place_key, Doves, Ring, TowerId};
+
+/// A table of [`TowerId`]s which have disappeared from Dove's Guide, saying what happened to
+/// each.  This can be loaded from a CSV file with the headers `TowerID` and `NewTowerID` (where
+/// an empty `NewTowerID` means the tower was removed), or built by comparing two versions of
+/// Dove's Guide with [`Redirects::from_diff`].  It's then applied to a [`Doves`] with
+/// [`Doves::set_redirects`], after which [`Doves::get`] and [`Doves::resolve`] follow it.
+#[derive(Debug, Clone, Default)]
+pub struct Redirects {
+    redirects: BTreeMap<TowerId, Redirect>,
+}
+
+/// What happened to a [`TowerId`] which is no longer in Dove's Guide
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Redirect {
+    /// The tower was merged into the tower with another `TowerId`
+    MergedInto(TowerId),
+    /// The tower was removed from Dove's Guide
+    Removed,
+}
+
+/// The result of looking up a (possibly stale) [`TowerId`] with [`Doves::resolve`]
+#[derive(Debug, Clone, Copy)]
+pub enum Resolution<'d> {
+    /// The `TowerId` is in Dove's Guide
+    Found(&'d Ring),
+    /// The `TowerId` is no longer in Dove's Guide, but was merged into this [`Ring`]'s tower
+    Redirected(&'d Ring),
+    /// The tower was definitely removed from Dove's Guide
+    Removed,
+}
+
+/// A row of a redirects CSV file
+#[cfg(feature = "serde")]
+#[derive(Debug, Deserialize)]
+struct RedirectRecord {
+    #[serde(rename = "TowerID")]
+    tower_id: TowerId,
+    #[serde(rename = "NewTowerID")]
+    new_tower_id: Option<TowerId>,
+}
+
+impl Redirects {
+    /// Creates an empty `Redirects` table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a `Redirects` table from the contents of a CSV file
+    #[cfg(feature = "serde")]
+    pub fn from_reader(reader: impl Read) -> Result<Self, csv::Error> {
+        let mut redirects = Self::new();
+        for record in csv::Reader::from_reader(reader).deserialize() {
+            let record: RedirectRecord = record?;
+            let redirect = match record.new_tower_id {
+                Some(id) => Redirect::MergedInto(id),
+                None => Redirect::Removed,
+            };
+            redirects.insert(record.tower_id, redirect);
+        }
+        Ok(redirects)
+    }
+
+    /// Reads a `Redirects` table from a CSV file
+    #[cfg(feature = "serde")]
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, csv::Error> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Builds the `Redirects` for every tower in `old` which isn't in `new`.  A tower is assumed to
+    /// have been merged into a tower in `new` with the same coordinates, or with the same place,
+    /// county and dedication; otherwise it's assumed to have been removed.
+    pub fn from_diff(old: &Doves, new: &Doves) -> Self {
+        let mut by_coords = HashMap::new();
+        let mut by_place = HashMap::new();
+        for ring in new {
+            if let Some(coords) = ring.coordinates {
+                let key = (coords.lat().to_bits(), coords.long().to_bits());
+                by_coords.entry(key).or_insert(ring.id);
+            }
+            by_place.entry(place_key(ring)).or_insert(ring.id);
+        }
+
+        let mut redirects = Self::new();
+        for ring in old {
+            if new.contains_id(ring.id) || redirects.get(ring.id).is_some() {
+                continue; // Only the first `Ring` of each tower is used
+            }
+            let by_coords = ring.coordinates.and_then(|coords| {
+                let key = (coords.lat().to_bits(), coords.long().to_bits());
+                by_coords.get(&key)
+            });
+            let redirect = match by_coords.or_else(|| by_place.get(&place_key(ring))) {
+                Some(&id) => Redirect::MergedInto(id),
+                None => Redirect::Removed,
+            };
+            redirects.insert(ring.id, redirect);
+        }
+        redirects
+    }
+
+    /// Writes this table as CSV, in the format read by [`Redirects::from_reader`]
+    pub fn write_csv(&self, writer: impl Write) -> Result<(), csv::Error> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(["TowerID", "NewTowerID"])?;
+        for (id, redirect) in self.iter() {
+            let new_id = match redirect {
+                Redirect::MergedInto(new_id) => new_id.to_string(),
+                Redirect::Removed => String::new(),
+            };
+            writer.write_record([id.to_string(), new_id])?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Gets the [`Redirect`] for a [`TowerId`], without following chains of redirects
+    pub fn get(&self, id: TowerId) -> Option<Redirect> {
+        self.redirects.get(&id).copied()
+    }
+
+    /// Adds (or replaces) the [`Redirect`] for a [`TowerId`]
+    pub fn insert(&mut self, id: TowerId, redirect: Redirect) {
+        self.redirects.insert(id, redirect);
+    }
+
+    /// The number of redirected [`TowerId`]s
+    pub fn len(&self) -> usize {
+        self.redirects.len()
+    }
+
+    /// `true` if there are no redirects
+    pub fn is_empty(&self) -> bool {
+        self.redirects.is_empty()
+    }
+
+    /// Iterates over every redirected [`TowerId`], in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = (TowerId, Redirect)> + '_ {
+        self.redirects.iter().map(|(&id, &redirect)| (id, redirect))
+    }
+}
+
+impl Extend<(TowerId, Redirect)> for Redirects {
+    fn extend<T: IntoIterator<Item = (TowerId, Redirect)>>(&mut self, iter: T) {
+        self.redirects.extend(iter);
+    }
+}
+
+impl Doves {
+    /// Sets the [`Redirects`] which [`Doves::get`] and [`Doves::resolve`] follow for
+    /// [`TowerId`]s which aren't in this `Doves`
+    pub fn set_redirects(&mut self, redirects: Redirects) {
+        self.redirects = redirects;
+    }
+
+    /// The [`Redirects`] followed for [`TowerId`]s which aren't in this `Doves`
+    pub fn redirects(&self) -> &Redirects {
+        &self.redirects
+    }
+
+    /// Looks up a [`TowerId`] which may have come from an older version of Dove's Guide.  If it
+    /// isn't in this `Doves`, the [`Redirects`] are followed (including chains of merges) to find
+    /// the current tower, or to find that the tower was removed.  Returns `None` if the
+    /// `TowerId` is unknown.
+    pub fn resolve(&self, id: TowerId) -> Option<Resolution<'_>> {
+        let mut current = id;
+        // Every step follows a different redirect, unless they form a cycle
+        for _ in 0..=self.redirects.len() {
+            if let Some(ring) = self.get_exact(current) {
+                return Some(if current == id {
+                    Resolution::Found(ring)
+                } else {
+                    Resolution::Redirected(ring)
+                });
+            }
+            match self.redirects.get(current)? {
+                Redirect::MergedInto(next) => current = next,
+                Redirect::Removed => return Some(Resolution::Removed),
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,143 @@
+//! Queries on the counties and countries containing [`Ring`]s, tolerant of the different ways
+//! people write their names.
+
+use crate::{normalize_county, Country, Doves, Ring};
+
+/// Historic or former counties which aren't used by Dove's, along with the (lower-case) counties
+/// which Dove's uses for the same area.  These are deliberately generous, so e.g. every `Ring` in
+/// historic Yorkshire is found even if that includes a few towers which were never in it.
+static HISTORIC_COUNTIES: [(&str, &[&str]); 14] = [
+    (
+        "yorkshire",
+        &[
+            "north yorkshire",
+            "south yorkshire",
+            "west yorkshire",
+            "east riding of yorkshire",
+        ],
+    ),
+    ("sussex", &["east sussex", "west sussex"]),
+    ("london", &["greater london", "city of london"]),
+    ("middlesex", &["greater london"]),
+    ("huntingdonshire", &["cambridgeshire"]),
+    ("cumberland", &["cumbria"]),
+    ("westmorland", &["cumbria"]),
+    ("avon", &["city of bristol", "somerset", "gloucestershire"]),
+    ("humberside", &["east riding of yorkshire", "lincolnshire"]),
+    ("cleveland", &["north yorkshire", "durham"]),
+    (
+        "hereford and worcester",
+        &["herefordshire", "worcestershire"],
+    ),
+    ("bristol", &["city of bristol"]),
+    ("londonderry", &["derry"]),
+    (
+        "northern ireland",
+        &[
+            "antrim",
+            "armagh",
+            "derry",
+            "down",
+            "fermanagh",
+            "tyrone",
+            "northern ireland",
+        ],
+    ),
+];
+
+/// Alternative (lower-case) names for [`Country`]s
+static COUNTRY_ALIASES: [(&str, Country); 10] = [
+    ("ireland", Country::IslandOfIreland),
+    ("republic of ireland", Country::IslandOfIreland),
+    ("eire", Country::IslandOfIreland),
+    ("usa", Country::UnitedStates),
+    ("us", Country::UnitedStates),
+    ("united states", Country::UnitedStates),
+    ("america", Country::UnitedStates),
+    ("holland", Country::Netherlands),
+    ("the netherlands", Country::Netherlands),
+    ("west indies", Country::Caribbean),
+];
+
+/// (Lower-case) names which refer to the whole of the United Kingdom
+const UK_NAMES: [&str; 5] = ["uk", "united kingdom", "great britain", "britain", "gb"];
+
+/// Parses a country name into a [`Country`], ignoring case and accepting common alternatives
+/// (e.g. `USA` or `Ireland`).  Returns `None` for unknown countries.
+fn normalize_country(name: &str) -> Option<Country> {
+    let name = name.trim();
+    Country::from_dove_name_ignore_case(name).or_else(|| {
+        COUNTRY_ALIASES
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+            .map(|(_, country)| country.clone())
+    })
+}
+
+impl Doves {
+    /// Gets every [`Ring`] in a given county, in the order they appear in Dove's Guide.  Case,
+    /// punctuation and `County`/`Co.` prefixes are ignored, and names like `Yorkshire, North` are
+    /// reordered (so `yorkshire, north`, `Co. Durham` and `Tyne & Wear` all work).  Historic
+    /// counties which Dove's doesn't use (e.g. `Yorkshire` or `Middlesex`) match all of the
+    /// counties that now cover them.
+    pub fn in_county(&self, county: &str) -> Vec<&Ring> {
+        let county = normalize_county(county);
+        let targets = HISTORIC_COUNTIES
+            .iter()
+            .find(|(historic, _)| *historic == county)
+            .map_or(&[][..], |(_, modern)| *modern);
+        let index = self.county_index();
+        let mut idxs = std::iter::once(county.as_str())
+            .chain(targets.iter().copied())
+            .filter_map(|c| index.get(c))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        idxs.sort_unstable();
+        idxs.dedup();
+        self.rings_at(Some(&idxs))
+    }
+
+    /// Gets every [`Ring`] in a given country, in the order they appear in Dove's Guide.  Case is
+    /// ignored, and common alternative names are accepted (e.g. `USA` or `Ireland`).  Names for the
+    /// whole UK (e.g. `UK` or `Great Britain`) match every `Ring` in the UK (see
+    /// [`Ring::is_uk`]).
+    pub fn in_country(&self, country: &str) -> Vec<&Ring> {
+        if UK_NAMES
+            .iter()
+            .any(|n| n.eq_ignore_ascii_case(country.trim()))
+        {
+            return self.iter().filter(|ring| ring.is_uk()).collect();
+        }
+        let country = match normalize_country(country) {
+            Some(country) => country,
+            None => return Vec::new(),
+        };
+        self.iter()
+            .filter(|ring| ring.country.as_ref() == Some(&country))
+            .collect()
+    }
+
+    /// Every distinct county in this `Doves`, in alphabetical order
+    pub fn counties(&self) -> Vec<&str> {
+        let mut counties = self
+            .iter()
+            .filter_map(|ring| ring.county.as_deref())
+            .collect::<Vec<_>>();
+        counties.sort_unstable();
+        counties.dedup();
+        counties
+    }
+
+    /// Every distinct [`Country`] in this `Doves`, in alphabetical order of their names in Dove's
+    /// Guide
+    pub fn countries(&self) -> Vec<&Country> {
+        let mut countries = self
+            .iter()
+            .filter_map(|ring| ring.country.as_ref())
+            .collect::<Vec<_>>();
+        countries.sort_unstable_by_key(|c| c.dove_name());
+        countries.dedup();
+        countries
+    }
+}
@@ -0,0 +1,321 @@
+//! Rendering county lists, tower lists, statistics and changes between versions of Dove's Guide
+//! as plain text, Markdown or HTML, e.g. for newsletters, annual reports and association websites.
+
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use crate::{AccessStatus, CountyGroup, Dedication, Doves, Ring, Stats};
+
+/// The format in which to render a report (see [`Table::render`])
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ReportFormat {
+    /// Plain text, with columns aligned using spaces (so best viewed in a monospaced font)
+    PlainText,
+    /// A Markdown heading followed by a table
+    Markdown,
+    /// An HTML heading followed by a `<table>`, with no styling (so it can be pasted into an
+    /// existing page)
+    Html,
+}
+
+/// A titled table of text, which is the building block of every report.  Reports are built as
+/// `Table`s and then [rendered](Table::render) in any [`ReportFormat`], so applications can also
+/// build their own `Table`s to publish alongside the generated ones.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Table {
+    pub title: String,
+    pub headings: Vec<String>,
+    /// The cells of each row, which should have the same length as `headings`
+    pub rows: Vec<Vec<String>>,
+    /// Lines of text printed after the table (e.g. cross-references or footnotes)
+    pub notes: Vec<String>,
+}
+
+/// The column headings of a county list
+const HEADINGS: [&str; 5] = ["Place", "Dedication", "Bells", "Tenor", "Practice"];
+
+impl Table {
+    /// Creates an empty `Table` with the given title and column headings
+    pub fn new(title: impl Into<String>, headings: &[&str]) -> Self {
+        Self {
+            title: title.into(),
+            headings: headings.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    /// A list of [`Ring`]s, with one row per `Ring` giving its place, county, dedication, number
+    /// of bells (marked `UR` if the bells are unringable), tenor weight and note, and practice
+    /// night.  The `Ring`s are listed in the order given.
+    pub fn from_rings<'d>(
+        title: impl Into<String>,
+        rings: impl IntoIterator<Item = &'d Ring>,
+    ) -> Self {
+        let mut table = Self::new(
+            title,
+            &[
+                "Place",
+                "County",
+                "Dedication",
+                "Bells",
+                "Tenor",
+                "Practice",
+            ],
+        );
+        for ring in rings {
+            let [place, dedication, bells, tenor, practice] = row(ring);
+            let county = ring.county.as_deref().unwrap_or_default().to_owned();
+            table
+                .rows
+                .push(vec![place, county, dedication, bells, tenor, practice]);
+        }
+        table
+    }
+
+    /// Renders this `Table` as a string in the given [`ReportFormat`]
+    pub fn render(&self, format: ReportFormat) -> String {
+        let mut report = String::new();
+        match format {
+            ReportFormat::PlainText => {
+                writeln!(report, "{}", self.title).unwrap();
+                writeln!(report, "{}", "=".repeat(self.title.chars().count())).unwrap();
+                writeln!(report).unwrap();
+                // Pad every column (except the last) to the width of its longest cell
+                let mut widths = self
+                    .headings
+                    .iter()
+                    .map(|h| h.chars().count())
+                    .collect::<Vec<_>>();
+                for row in &self.rows {
+                    for (width, cell) in widths.iter_mut().zip(row) {
+                        *width = (*width).max(cell.chars().count());
+                    }
+                }
+                for row in std::iter::once(&self.headings).chain(&self.rows) {
+                    let mut line = String::new();
+                    for ((cell, width), idx) in row.iter().zip(&widths).zip(0..) {
+                        if idx > 0 {
+                            line.push_str("  ");
+                        }
+                        write!(line, "{:width$}", cell, width = width).unwrap();
+                    }
+                    writeln!(report, "{}", line.trim_end()).unwrap();
+                }
+            }
+            ReportFormat::Markdown => {
+                writeln!(report, "## {}", self.title).unwrap();
+                writeln!(report).unwrap();
+                let md_row = |cells: &[String]| {
+                    let cells = cells.iter().map(|c| c.replace('|', "\\|"));
+                    format!("| {} |", cells.collect::<Vec<_>>().join(" | "))
+                };
+                writeln!(report, "{}", md_row(&self.headings)).unwrap();
+                writeln!(report, "|{}", "---|".repeat(self.headings.len())).unwrap();
+                for row in &self.rows {
+                    writeln!(report, "{}", md_row(row)).unwrap();
+                }
+            }
+            ReportFormat::Html => {
+                writeln!(report, "<h2>{}</h2>", escape_html(&self.title)).unwrap();
+                writeln!(report, "<table>").unwrap();
+                let html_row = |cells: &[String], tag: &str| {
+                    let cells = cells
+                        .iter()
+                        .map(|c| format!("<{0}>{1}</{0}>", tag, escape_html(c)));
+                    format!("  <tr>{}</tr>", cells.collect::<String>())
+                };
+                writeln!(report, "{}", html_row(&self.headings, "th")).unwrap();
+                for row in &self.rows {
+                    writeln!(report, "{}", html_row(row, "td")).unwrap();
+                }
+                writeln!(report, "</table>").unwrap();
+            }
+        }
+
+        if !self.notes.is_empty() {
+            writeln!(report).unwrap();
+            for note in &self.notes {
+                match format {
+                    ReportFormat::PlainText => writeln!(report, "{}", note),
+                    ReportFormat::Markdown => writeln!(report, "*{}*", note),
+                    ReportFormat::Html => writeln!(report, "<p><em>{}</em></p>", escape_html(note)),
+                }
+                .unwrap();
+            }
+        }
+        report
+    }
+}
+
+impl CountyGroup<'_> {
+    /// Renders the [`Ring`]s in this county in the traditional format of the printed guide (see
+    /// [`CountyGroup::table`])
+    pub fn report(&self, format: ReportFormat) -> String {
+        self.table().render(format)
+    }
+
+    /// The [`Ring`]s in this county in the traditional format of the printed guide: one row per
+    /// `Ring`, giving the place, dedication, number of bells (marked `UR` if the bells are
+    /// unringable), tenor weight and note, and practice night.  Any cross-references to `Ring`s
+    /// listed under other counties (see [`CountyGroup::see_also`]) are given as notes.
+    pub fn table(&self) -> Table {
+        let title = match (self.county, self.country) {
+            (Some(county), _) => county,
+            (None, Some(country)) => country.dove_name(),
+            (None, None) => "Other",
+        };
+        let mut table = Table::new(title, &HEADINGS);
+        table.rows = self.rings.iter().map(|r| row(r).to_vec()).collect();
+        table.notes = self
+            .see_also
+            .iter()
+            .map(|ring| {
+                let county = ring.county.as_deref().unwrap_or_default();
+                format!("See also {} ({})", ring, county)
+            })
+            .collect();
+        table
+    }
+}
+
+impl Stats<'_> {
+    /// Renders these `Stats` as a series of [`Table`]s (see [`Stats::tables`])
+    pub fn report(&self, format: ReportFormat) -> String {
+        let tables = self
+            .tables()
+            .iter()
+            .map(|t| t.render(format))
+            .collect::<Vec<_>>();
+        tables.join("\n")
+    }
+
+    /// Summarises these `Stats` as [`Table`]s: an overview, followed by the number of `Ring`s with
+    /// each number of bells, in each country and in each county
+    pub fn tables(&self) -> Vec<Table> {
+        let mut overview = Table::new("Summary", &["Statistic", "Value"]);
+        let describe = |ring: Option<&Ring>| ring.map_or_else(String::new, |r| r.to_string());
+        let median = self
+            .median_tenor_weight
+            .map_or_else(String::new, |w| w.to_string());
+        for (name, value) in [
+            ("Rings", self.num_rings.to_string()),
+            ("Heaviest", describe(self.heaviest)),
+            ("Lightest", describe(self.lightest)),
+            ("Median tenor", median),
+            ("Ground floor", format!("{:.1}%", self.ground_floor_percent)),
+            ("Unringable", format!("{:.1}%", self.unringable_percent)),
+            ("Ringable rings", self.num_ringable.to_string()),
+            ("Bells", self.total_bells.to_string()),
+            ("Ringable bells", self.total_ringable_bells.to_string()),
+        ] {
+            overview.rows.push(vec![name.to_owned(), value]);
+        }
+
+        let mut by_bells = Table::new("By number of bells", &["Bells", "Rings"]);
+        for (bells, count) in &self.by_bells {
+            by_bells
+                .rows
+                .push(vec![bells.to_string(), count.to_string()]);
+        }
+        let mut by_country = Table::new("By country", &["Country", "Rings"]);
+        let mut countries = self.by_country.iter().collect::<Vec<_>>();
+        countries.sort_by_key(|(country, count)| (Reverse(**count), country.dove_name()));
+        for (country, count) in countries {
+            by_country
+                .rows
+                .push(vec![country.to_string(), count.to_string()]);
+        }
+        let mut by_county = Table::new("By county", &["County", "Rings"]);
+        for (county, count) in &self.by_county {
+            by_county
+                .rows
+                .push(vec![county.to_string(), count.to_string()]);
+        }
+        vec![overview, by_bells, by_country, by_county]
+    }
+}
+
+impl Doves {
+    /// Compares this `Doves` to a `newer` version, returning a [`Table`] of every [`Ring`] which
+    /// was added, removed or changed (matched by [`RingKey`](crate::RingKey)).  Changes are
+    /// described by the columns of Dove's CSV file whose values differ (see [`Ring::diff`]).
+    pub fn diff_table(&self, newer: &Doves) -> Table {
+        let mut table = Table::new("Changes", &["Change", "Ring", "Details"]);
+        let old_rings = self.iter().map(|r| (r.key(), r)).collect::<HashMap<_, _>>();
+        let new_keys = newer.iter().map(Ring::key).collect::<HashSet<_>>();
+        for ring in newer {
+            let old_ring = match old_rings.get(&ring.key()) {
+                Some(old_ring) => old_ring,
+                None => {
+                    table
+                        .rows
+                        .push(vec!["Added".to_owned(), ring.to_string(), String::new()]);
+                    continue;
+                }
+            };
+            let changes = old_ring
+                .diff(ring)
+                .into_iter()
+                .map(|change| format!("{}: {:?} → {:?}", change.column, change.old, change.new))
+                .collect::<Vec<_>>();
+            if !changes.is_empty() {
+                table.rows.push(vec![
+                    "Changed".to_owned(),
+                    ring.to_string(),
+                    changes.join("; "),
+                ]);
+            }
+        }
+        for ring in self.iter().filter(|r| !new_keys.contains(&r.key())) {
+            table
+                .rows
+                .push(vec!["Removed".to_owned(), ring.to_string(), String::new()]);
+        }
+        table
+    }
+}
+
+/// Escapes the characters with special meanings in HTML text
+pub(crate) fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The cells of the line for one [`Ring`] in a county list
+fn row(ring: &Ring) -> [String; 5] {
+    let mut place = ring.place.to_string();
+    if let Some(place2) = &ring.place2 {
+        place.push_str(", ");
+        place.push_str(place2);
+    }
+    let dedication = if ring.dedication == Dedication::Unknown || ring.is_mobile() {
+        String::new()
+    } else {
+        ring.dedication.as_str().to_owned()
+    };
+    let mut bells = ring.bells.to_string();
+    if ring.access == AccessStatus::Unringable {
+        bells.push_str(" UR");
+    }
+    let tenor = match (ring.weight, ring.note) {
+        (Some(weight), Some(note)) => format!("{} in {}", weight, note),
+        (Some(weight), None) => weight.to_string(),
+        (None, Some(note)) => note.to_string(),
+        (None, None) => String::new(),
+    };
+    let practice = ring.practice.clone().unwrap_or_default();
+    [place, dedication, bells, tenor, practice]
+}
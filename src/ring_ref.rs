@@ -0,0 +1,255 @@
+//! A borrowed view of one row of Dove's CSV file, for scanning the whole guide with almost no
+//! allocations.
+
+use std::{fs::File, io::Read, path::Path};
+
+use csv::StringRecord;
+use serde::Deserialize;
+
+use crate::{
+    record::{deser_from_str, deser_not_empty, deser_option_from_str},
+    Coordinates, Frequency, Note, Ring, RingType, TowerId, Weight,
+};
+
+/// A [`Ring`] whose string fields borrow from the CSV record it was read from, created by a
+/// [`RingRefReader`].  Only the commonly used columns are decoded; everything else (and the full
+/// validation done when loading a [`Doves`](crate::Doves)) is available by converting into a
+/// `Ring` with [`RingRef::to_ring`].  Fields have the same meanings as the fields of `Ring`
+/// with the same names.
+#[derive(Debug, Clone)]
+pub struct RingRef<'a> {
+    pub id: TowerId,
+    pub ring_type: RingType,
+    pub bells: u8,
+    /// `true` if Dove's marks this `Ring` as unringable (`u/r` in the `UR` column)
+    pub unringable: bool,
+    pub ground_floor: bool,
+    pub toilet: bool,
+    pub simulator: bool,
+    pub towerbase_id: u32,
+
+    weight_lbs: Option<f64>,
+    weight_approximate: bool,
+    pub note: Option<Note>,
+    pub freq: Option<Frequency>,
+    lat: Option<f64>,
+    long: Option<f64>,
+
+    pub place: &'a str,
+    pub place2: Option<&'a str>,
+    pub place_county_list: Option<&'a str>,
+    /// The dedication, exactly as written in Dove's
+    pub dedication: &'a str,
+    pub alt_name: Option<&'a str>,
+    pub county: Option<&'a str>,
+    /// The country, exactly as named in Dove's
+    pub country: Option<&'a str>,
+    /// The diocese, exactly as written in Dove's
+    pub diocese: Option<&'a str>,
+    pub practice: Option<&'a str>,
+    pub url: Option<&'a str>,
+    pub os_grid_ref: Option<&'a str>,
+    pub postcode: Option<&'a str>,
+    /// The `;`-delimited list of affiliation codes, exactly as written in Dove's
+    pub affiliations: &'a str,
+
+    /// The record this `RingRef` was read from, and its headers
+    source: (&'a StringRecord, &'a StringRecord),
+}
+
+impl RingRef<'_> {
+    /// The [`Weight`] of the tenor, if Dove's knows it
+    pub fn weight(&self) -> Option<Weight> {
+        self.weight_lbs
+            .map(|lbs| Weight::from_lbs(lbs).with_approximate(self.weight_approximate))
+    }
+
+    /// The [`Coordinates`] of the tower, if Dove's gives valid ones
+    pub fn coordinates(&self) -> Option<Coordinates> {
+        Coordinates::new(self.lat?, self.long?).ok()
+    }
+
+    /// Decodes the full CSV record into an owned [`Ring`], with the same validation as when
+    /// loading a [`Doves`](crate::Doves)
+    pub fn to_ring(&self) -> Result<Ring, csv::Error> {
+        let (record, headers) = self.source;
+        record.deserialize(Some(headers))
+    }
+}
+
+/// The columns of a [`RingRef`], as deserialized from a CSV record
+#[derive(Debug, Deserialize)]
+struct RingRefRecord<'a> {
+    #[serde(rename = "TowerID")]
+    id: TowerId,
+    #[serde(rename = "RingType", deserialize_with = "deser_from_str")]
+    ring_type: RingType,
+    #[serde(rename = "Bells")]
+    bells: u8,
+    #[serde(rename = "UR", deserialize_with = "deser_not_empty")]
+    unringable: bool,
+    #[serde(rename = "GF", deserialize_with = "deser_not_empty")]
+    ground_floor: bool,
+    #[serde(rename = "Toilet", deserialize_with = "deser_not_empty")]
+    toilet: bool,
+    #[serde(rename = "Simulator", deserialize_with = "deser_not_empty")]
+    simulator: bool,
+    #[serde(rename = "TowerBase")]
+    towerbase_id: u32,
+
+    #[serde(rename = "Wt")]
+    weight_lbs: Option<f64>,
+    #[serde(rename = "App", deserialize_with = "deser_not_empty")]
+    weight_approximate: bool,
+    #[serde(rename = "Note", deserialize_with = "deser_option_from_str")]
+    note: Option<Note>,
+    #[serde(rename = "Hz")]
+    freq: Option<Frequency>,
+    #[serde(rename = "Lat")]
+    lat: Option<f64>,
+    #[serde(rename = "Long")]
+    long: Option<f64>,
+
+    #[serde(rename = "Place")]
+    place: &'a str,
+    #[serde(rename = "Place2")]
+    place2: Option<&'a str>,
+    #[serde(rename = "PlaceCL")]
+    place_county_list: Option<&'a str>,
+    #[serde(rename = "Dedicn")]
+    dedication: &'a str,
+    #[serde(rename = "AltName")]
+    alt_name: Option<&'a str>,
+    #[serde(rename = "County")]
+    county: Option<&'a str>,
+    #[serde(rename = "Country")]
+    country: Option<&'a str>,
+    #[serde(rename = "Diocese")]
+    diocese: Option<&'a str>,
+    #[serde(rename = "Practice")]
+    practice: Option<&'a str>,
+    #[serde(rename = "WebPage")]
+    url: Option<&'a str>,
+    #[serde(rename = "NG")]
+    os_grid_ref: Option<&'a str>,
+    #[serde(rename = "Postcode")]
+    postcode: Option<&'a str>,
+    #[serde(rename = "Affiliations")]
+    affiliations: &'a str,
+}
+
+impl<'a> RingRefRecord<'a> {
+    fn into_ring_ref(self, source: (&'a StringRecord, &'a StringRecord)) -> RingRef<'a> {
+        RingRef {
+            id: self.id,
+            ring_type: self.ring_type,
+            bells: self.bells,
+            unringable: self.unringable,
+            ground_floor: self.ground_floor,
+            toilet: self.toilet,
+            simulator: self.simulator,
+            towerbase_id: self.towerbase_id,
+            weight_lbs: self.weight_lbs,
+            weight_approximate: self.weight_approximate,
+            note: self.note,
+            freq: self.freq,
+            lat: self.lat,
+            long: self.long,
+            place: self.place,
+            place2: self.place2,
+            place_county_list: self.place_county_list,
+            dedication: self.dedication,
+            alt_name: self.alt_name,
+            county: self.county,
+            country: self.country,
+            diocese: self.diocese,
+            practice: self.practice,
+            url: self.url,
+            os_grid_ref: self.os_grid_ref,
+            postcode: self.postcode,
+            affiliations: self.affiliations,
+            source,
+        }
+    }
+}
+
+/// Reads [`RingRef`]s from Dove's CSV file one at a time.  This is a 'lending' reader: each
+/// `RingRef` borrows from the reader's buffer, so must be dropped before reading the next
+/// one (with [`RingRefReader::next_ring`]).
+#[derive(Debug)]
+pub struct RingRefReader<R> {
+    reader: csv::Reader<R>,
+    headers: StringRecord,
+    record: StringRecord,
+}
+
+impl<R: Read> RingRefReader<R> {
+    /// Creates a `RingRefReader` over the contents of Dove's `dove.csv` file
+    pub fn from_reader(reader: R) -> Result<Self, csv::Error> {
+        let mut reader = csv::Reader::from_reader(reader);
+        let headers = reader.headers()?.clone();
+        Ok(Self {
+            reader,
+            headers,
+            record: StringRecord::new(),
+        })
+    }
+
+    /// Reads the next [`RingRef`], returning `None` at the end of the file
+    pub fn next_ring(&mut self) -> Option<Result<RingRef<'_>, csv::Error>> {
+        match self.reader.read_record(&mut self.record) {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(e) => return Some(Err(e)),
+        }
+        let (record, headers) = (&self.record, &self.headers);
+        Some(
+            record
+                .deserialize::<RingRefRecord>(Some(headers))
+                .map(|ring| ring.into_ring_ref((record, headers))),
+        )
+    }
+}
+
+impl RingRefReader<File> {
+    /// Creates a `RingRefReader` over Dove's `dove.csv` file
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, csv::Error> {
+        Self::from_reader(File::open(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RingRefReader;
+    use crate::{Column, Doves, Ring};
+
+    /// A row with quoted fields, one of which contains escaped quotes
+    const ROW: &str =
+        "12574,Full circle ring,\"Ab Kettleby, \"\"Upper\"\"\",,,\"S James, Great\",,\
+        Leicestershire,England,GB,Leicester,52.79858,-0.92747,6,,,974,,G,783.0,C,GF,,,,\
+        http://www.abkettlebybells.co.uk/,LeiDG,SK724228,LE14 3HS,Wed,27 Oct 2013,,,None,None,\
+        619058,6918,AB KETTLEB,52.79968,-0.92635";
+
+    fn values(ring: &Ring) -> Vec<String> {
+        Column::all().map(|column| column.value(ring)).collect()
+    }
+
+    #[test]
+    fn quoted_fields_are_borrowed() {
+        let headers = Column::all().map(Column::dove_header).collect::<Vec<_>>();
+        let csv = format!("{}\n{}\n", headers.join(","), ROW);
+        let mut reader = RingRefReader::from_reader(csv.as_bytes()).unwrap();
+
+        let ring = reader.next_ring().unwrap().unwrap();
+        assert_eq!(ring.place, "Ab Kettleby, \"Upper\"");
+        assert_eq!(ring.dedication, "S James, Great");
+        assert_eq!(ring.county, Some("Leicestershire"));
+        assert_eq!(ring.affiliations, "LeiDG");
+        assert_eq!(ring.bells, 6);
+
+        let doves = Doves::from_reader(csv.as_bytes()).unwrap();
+        assert_eq!(values(&ring.to_ring().unwrap()), values(&doves[0]));
+        drop(ring);
+        assert!(reader.next_ring().is_none());
+    }
+}
@@ -0,0 +1,138 @@
+//! Finding the [`Ring`]s along a route, e.g. for planning a grab on a long journey.
+
+use std::collections::HashMap;
+
+use crate::{
+    spatial::{from_vector, unit_vector},
+    Coordinates, Distance, Doves, Ring, RingFilter,
+};
+
+/// A [`Ring`] returned by [`Doves::along_route`]
+#[derive(Debug, Clone, Copy)]
+pub struct RouteMatch<'d> {
+    pub ring: &'d Ring,
+    /// How far the `Ring` is from the closest point on the route
+    pub distance_from_route: Distance,
+    /// How far along the route (from its start) the closest point to the `Ring` is
+    pub distance_along_route: Distance,
+}
+
+impl Doves {
+    /// Finds every [`Ring`] which passes a [`RingFilter`] and is within `width` of a route,
+    /// ordered by how far along the route they are.  The route is a polyline through the given
+    /// [`Coordinates`], where each leg follows a great circle (so a route made of points every few
+    /// miles along a road will closely follow that road).  `Ring`s without coordinates are never
+    /// returned.
+    pub fn along_route(
+        &self,
+        route: &[Coordinates],
+        width: Distance,
+        filter: impl RingFilter,
+    ) -> Vec<RouteMatch<'_>> {
+        let legs = match route {
+            [] => return Vec::new(),
+            [point] => vec![(*point, *point)],
+            _ => route.windows(2).map(|w| (w[0], w[1])).collect(),
+        };
+
+        // Maps ring indices to their (distance from route, distance along route)
+        let mut closest = HashMap::<usize, (f64, f64)>::new();
+        let mut leg_start_km = 0.0;
+        for (start, end) in legs {
+            let leg_km = start.distance_to(end).km();
+            // Every point within `width` of the leg is within this distance of its midpoint
+            let search_km = leg_km / 2.0 + width.km();
+            let midpoint = midpoint(start, end);
+            for idx in self.spatial_index().by_distance(midpoint) {
+                let coords = self.towers[idx].best_coordinates().unwrap().0;
+                if coords.distance_to(midpoint).km() > search_km {
+                    break;
+                }
+                let (from_leg, along_leg) = distance_to_leg(coords, start, end);
+                if from_leg.km() > width.km() {
+                    continue;
+                }
+                let candidate = (from_leg.km(), leg_start_km + along_leg.km());
+                let best = closest.entry(idx).or_insert(candidate);
+                if candidate.0 < best.0 {
+                    *best = candidate;
+                }
+            }
+            leg_start_km += leg_km;
+        }
+
+        let mut matches = closest
+            .into_iter()
+            .map(|(idx, (from_km, along_km))| RouteMatch {
+                ring: &self.towers[idx],
+                distance_from_route: Distance::from_km(from_km),
+                distance_along_route: Distance::from_km(along_km),
+            })
+            .filter(|m| filter.matches(m.ring))
+            .collect::<Vec<_>>();
+        matches.sort_by(|a, b| {
+            a.distance_along_route
+                .km()
+                .total_cmp(&b.distance_along_route.km())
+                .then_with(|| a.ring.key().cmp(&b.ring.key()))
+        });
+        matches
+    }
+}
+
+/// Returns the distance from `point` to the great-circle arc from `start` to `end`, along with
+/// the distance from `start` to the closest point on that arc
+fn distance_to_leg(
+    point: Coordinates,
+    start: Coordinates,
+    end: Coordinates,
+) -> (Distance, Distance) {
+    let (p, a, b) = (unit_vector(point), unit_vector(start), unit_vector(end));
+    let normal = cross(a, b);
+    let normal_len = dot(normal, normal).sqrt();
+    if normal_len > f64::EPSILON {
+        let normal = normal.map(|x| x / normal_len);
+        // Project `p` onto the plane of the great circle, and check if that falls within the leg
+        let height = dot(p, normal);
+        let projected = [0, 1, 2].map(|i| p[i] - height * normal[i]);
+        if dot(cross(a, projected), normal) >= 0.0 && dot(cross(projected, b), normal) >= 0.0 {
+            return (
+                Distance::from_radians(height.abs().min(1.0).asin()),
+                Distance::from_radians(angle_between(a, projected)),
+            );
+        }
+    }
+    // The closest point is one of the ends of the leg
+    let to_start = point.distance_to(start);
+    let to_end = point.distance_to(end);
+    if to_start <= to_end {
+        (to_start, Distance::default())
+    } else {
+        (to_end, start.distance_to(end))
+    }
+}
+
+/// The point half-way along the great circle between two [`Coordinates`]
+fn midpoint(a: Coordinates, b: Coordinates) -> Coordinates {
+    let (a_vec, b_vec) = (unit_vector(a), unit_vector(b));
+    // Antipodal points have no unique midpoint
+    from_vector([0, 1, 2].map(|i| a_vec[i] + b_vec[i])).unwrap_or(a)
+}
+
+/// The angle (in radians) between two vectors
+fn angle_between(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let cross = cross(a, b);
+    dot(cross, cross).sqrt().atan2(dot(a, b))
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a.iter().zip(&b).map(|(x, y)| x * y).sum()
+}
@@ -0,0 +1,275 @@
+//! Fuzzy searching of place names, and full-text searching of every text field.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Bound,
+};
+
+use crate::{expand_dedication, normalize::fold, place_variants, Doves, Ring};
+
+/// How well a [`Ring`]'s place names matched a query in [`Doves::search_place`].  Better matches
+/// compare as smaller, so sorting puts the best matches first.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum MatchQuality {
+    /// A place name is exactly the query
+    Exact,
+    /// A place name starts with the query
+    Prefix,
+    /// A word in the middle of a place name starts with the query
+    WordPrefix,
+    /// The query appears somewhere inside a place name
+    Substring,
+    /// A place name (or the start of one) is within a small number of typos of the query.  The
+    /// value is the number of typos.
+    Typo(usize),
+}
+
+/// A [`Ring`] returned by [`Doves::search_place`], along with how well it matched
+#[derive(Debug, Clone, Copy)]
+pub struct PlaceMatch<'d> {
+    pub ring: &'d Ring,
+    pub quality: MatchQuality,
+}
+
+impl Doves {
+    /// Searches the place names of every [`Ring`] (`place`, `place2`, `place_county_list` and
+    /// `alt_name`), returning the matches ranked from best to worst.  Matching ignores case,
+    /// accents and punctuation, accepts alternative spellings of Welsh place names (see
+    /// [`place_variants`]), and tolerates a few typos (one for queries of 4-7 characters, two for
+    /// anything longer), so this works well for autocompletion.
+    pub fn search_place(&self, query: &str) -> Vec<PlaceMatch<'_>> {
+        let queries = place_variants(query);
+        if queries[0].is_empty() {
+            return Vec::new();
+        }
+        let mut matches = self
+            .iter()
+            .filter_map(|ring| {
+                let names = [
+                    Some(ring.place.as_str()),
+                    ring.place2.as_deref(),
+                    ring.place_county_list.as_deref(),
+                    ring.alt_name.as_deref(),
+                ];
+                let quality = names
+                    .iter()
+                    .flatten()
+                    .flat_map(|name| {
+                        let name = fold(name);
+                        queries.iter().filter_map(move |q| match_quality(q, &name))
+                    })
+                    .min()?;
+                Some(PlaceMatch { ring, quality })
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by(|a, b| {
+            (a.quality, &a.ring.place, &a.ring.place2).cmp(&(
+                b.quality,
+                &b.ring.place,
+                &b.ring.place2,
+            ))
+        });
+        matches
+    }
+}
+
+/// Determines how well a folded `name` matches a folded `query`
+fn match_quality(query: &str, name: &str) -> Option<MatchQuality> {
+    if name == query {
+        return Some(MatchQuality::Exact);
+    }
+    if name.starts_with(query) {
+        return Some(MatchQuality::Prefix);
+    }
+    if let Some(idx) = name.find(query) {
+        let at_word_start = name[..idx].ends_with(' ');
+        return Some(if at_word_start {
+            MatchQuality::WordPrefix
+        } else {
+            MatchQuality::Substring
+        });
+    }
+
+    let max_typos = match query.chars().count() {
+        0..=3 => return None,
+        4..=7 => 1,
+        _ => 2,
+    };
+    // Compare against both the whole name, and a prefix of the same length as the query (so that
+    // partially typed names still match)
+    let prefix = name.chars().take(query.chars().count()).collect::<String>();
+    let typos = edit_distance(query, name).min(edit_distance(query, &prefix));
+    (typos <= max_typos).then_some(MatchQuality::Typo(typos))
+}
+
+/// The number of single-character insertions, deletions, substitutions or transpositions needed
+/// to turn `a` into `b` (i.e. the optimal string alignment distance)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    // `rows[i][j]` is the distance between `a[..i]` and `b[..j]`
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut dist = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                dist = dist.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = dist;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+/// An inverted index over the text fields of every [`Ring`] in a [`Doves`], created with
+/// [`Doves::build_search_index`].  This covers place names, dedications, counties, dioceses,
+/// extra information and practice nights, so can back a single 'search anything' box.
+#[derive(Debug, Clone)]
+pub struct SearchIndex<'d> {
+    doves: &'d Doves,
+    /// Maps each folded token to the `Ring`s containing it, along with the weight of the most
+    /// important field which contains it
+    postings: BTreeMap<String, Vec<(usize, f64)>>,
+}
+
+/// A [`Ring`] returned by [`SearchIndex::search`], along with its relevance score (higher is
+/// better)
+#[derive(Debug, Clone, Copy)]
+pub struct SearchHit<'d> {
+    pub ring: &'d Ring,
+    pub score: f64,
+}
+
+/// How much more a match in a place name counts for than a match in an unimportant field
+const PLACE_WEIGHT: f64 = 4.0;
+const DEDICATION_WEIGHT: f64 = 3.0;
+const REGION_WEIGHT: f64 = 2.0;
+const OTHER_WEIGHT: f64 = 1.0;
+/// How much a token which only starts with a query token counts for, relative to an exact match
+const PREFIX_FACTOR: f64 = 0.5;
+
+impl Doves {
+    /// Builds a [`SearchIndex`] over the text fields of every [`Ring`].  This isn't built when
+    /// loading, since most applications don't need it.  The index borrows this `Doves`, so it
+    /// can't go out of date.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    pub fn build_search_index(&self) -> SearchIndex<'_> {
+        let mut postings = BTreeMap::<String, Vec<(usize, f64)>>::new();
+        for (idx, ring) in self.iter().enumerate() {
+            let mut add_field = |text: &str, weight: f64| {
+                for token in fold(text).split(' ').filter(|t| !t.is_empty()) {
+                    let ring_postings = postings.entry(token.to_owned()).or_default();
+                    match ring_postings.last_mut() {
+                        Some((last_idx, w)) if *last_idx == idx => *w = w.max(weight),
+                        _ => ring_postings.push((idx, weight)),
+                    }
+                }
+            };
+            for name in [
+                Some(ring.place.as_str()),
+                ring.place2.as_deref(),
+                ring.place_county_list.as_deref(),
+                ring.alt_name.as_deref(),
+            ]
+            .iter()
+            .flatten()
+            {
+                for variant in place_variants(name) {
+                    add_field(&variant, PLACE_WEIGHT);
+                }
+            }
+            add_field(
+                &expand_dedication(ring.dedication.as_str()),
+                DEDICATION_WEIGHT,
+            );
+            for name in ring.dedication.names() {
+                add_field(name, DEDICATION_WEIGHT);
+            }
+            if let Some(county) = &ring.county {
+                add_field(county, REGION_WEIGHT);
+            }
+            if let Some(diocese) = &ring.diocese {
+                add_field(&diocese.to_string(), REGION_WEIGHT);
+            }
+            for feature in &ring.extra_info {
+                add_field(&feature.to_string(), OTHER_WEIGHT);
+            }
+            if let Some(practice) = &ring.practice {
+                add_field(practice, OTHER_WEIGHT);
+            }
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(tokens = postings.len(), "built search index");
+        SearchIndex {
+            doves: self,
+            postings,
+        }
+    }
+}
+
+impl<'d> SearchIndex<'d> {
+    /// Finds every [`Ring`] matching all the words in `query`, ranked from most to least relevant.
+    /// Words match case- and accent-insensitively, and a word also matches any longer word which
+    /// starts with it (at a lower score).  Rarer words and matches in more important fields (e.g.
+    /// place names) count for more.
+    pub fn search(&self, query: &str) -> Vec<SearchHit<'d>> {
+        let query = fold(query);
+        let tokens = query
+            .split(' ')
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>();
+        if tokens.is_empty() {
+            return Vec::new();
+        }
+
+        let num_rings = self.doves.len() as f64;
+        // Maps ring indices to their scores and the number of query tokens they've matched
+        let mut scores = HashMap::<usize, (f64, usize)>::new();
+        for token in &tokens {
+            let mut token_scores = HashMap::<usize, f64>::new();
+            let matches = self
+                .postings
+                .range::<str, _>((Bound::Included(*token), Bound::Unbounded))
+                .take_while(|(t, _)| t.starts_with(token));
+            for (indexed_token, postings) in matches {
+                let idf = (num_rings / postings.len() as f64).ln() + 1.0;
+                let factor = if indexed_token == token {
+                    1.0
+                } else {
+                    PREFIX_FACTOR
+                };
+                for &(idx, weight) in postings {
+                    let score = token_scores.entry(idx).or_default();
+                    *score = score.max(weight * idf * factor);
+                }
+            }
+            for (idx, score) in token_scores {
+                let entry = scores.entry(idx).or_default();
+                entry.0 += score;
+                entry.1 += 1;
+            }
+        }
+
+        let mut hits = scores
+            .into_iter()
+            .filter(|(_, (_, num_matched))| *num_matched == tokens.len())
+            .map(|(idx, (score, _))| SearchHit {
+                ring: &self.doves.towers[idx],
+                score,
+            })
+            .collect::<Vec<_>>();
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.ring.place.cmp(&b.ring.place))
+        });
+        hits
+    }
+}
@@ -0,0 +1,177 @@
+//! The semitone bells which some [`Ring`]s have in addition to their diatonic bells, and the
+//! tuning layouts they make.
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{simulator::MAJOR_SCALE, Accidental, Ring};
+
+/// The entry in Dove's `Semitones` column for an extra treble
+const EXTRA_TREBLE: &str = "0extra";
+
+/// The semitone bells in a [`Ring`], parsed from Dove's `Semitones` column (e.g. `0extra+6b`) by
+/// [`Ring::semitone_bells`].  These are in addition to the [`Ring::bells`] of the diatonic ring.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Semitones {
+    /// `true` if the ring has an extra treble (a diatonic bell above the treble, which Dove's
+    /// lists alongside the semitones)
+    pub extra_treble: bool,
+    /// The semitone bells, in the order Dove's lists them
+    pub bells: Vec<SemitoneBell>,
+}
+
+/// One semitone bell, named after the diatonic bell which it's a semitone above or below (e.g.
+/// the 'flat 6th' is a semitone below the 6th).  Bells are numbered from the treble.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct SemitoneBell {
+    pub bell: u8,
+    /// Either [`Accidental::Sharp`] or [`Accidental::Flat`]
+    pub accidental: Accidental,
+}
+
+/// How a [`Ring`]'s bells are tuned, as returned by [`Ring::tuning_layout`].  An
+/// [extra treble](Semitones::extra_treble) is diatonic, so doesn't change the layout.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TuningLayout {
+    /// A major scale with no semitone bells
+    Diatonic,
+    /// A major scale plus a flat 6th (`6b`)
+    FlatSixth,
+    /// A major scale plus a sharp 2nd (`2#`)
+    SharpSecond,
+    /// A major scale plus both a sharp 2nd and a flat 6th
+    SharpSecondAndFlatSixth,
+    /// Semitone bells fill every whole tone between the treble and the tenor
+    Chromatic,
+    /// Any other semitone bells, including any which Dove's lists in a form this crate can't
+    /// parse
+    Other,
+}
+
+impl SemitoneBell {
+    /// The diatonic bells between which this semitone bell sits, as the number of the lighter
+    /// bell (e.g. a `2#` is between the treble and the 2nd, so this is `1`)
+    pub fn gap(self) -> u8 {
+        match self.accidental {
+            Accidental::Sharp => self.bell.saturating_sub(1),
+            Accidental::Flat | Accidental::Natural => self.bell,
+        }
+    }
+}
+
+impl Ring {
+    /// The [`Semitones`] of this `Ring`, or `None` if it has no semitone bells (or Dove's lists
+    /// them in a form which can't be parsed).
+    pub fn semitone_bells(&self) -> Option<Semitones> {
+        self.semitones.as_deref()?.parse().ok()
+    }
+
+    /// Classifies how this `Ring` is tuned, using its [semitone bells](Ring::semitone_bells)
+    pub fn tuning_layout(&self) -> TuningLayout {
+        let semitones = match (&self.semitones, self.semitone_bells()) {
+            (None, _) => return TuningLayout::Diatonic,
+            (Some(_), None) => return TuningLayout::Other,
+            (Some(_), Some(semitones)) => semitones,
+        };
+        let mut bells = semitones
+            .bells
+            .iter()
+            .map(|b| (b.bell, b.accidental))
+            .collect::<Vec<_>>();
+        bells.sort_by_key(|&(bell, _)| bell);
+        bells.dedup();
+        const SHARP_SECOND: (u8, Accidental) = (2, Accidental::Sharp);
+        const FLAT_SIXTH: (u8, Accidental) = (6, Accidental::Flat);
+        match bells.as_slice() {
+            [] => TuningLayout::Diatonic,
+            [SHARP_SECOND] => TuningLayout::SharpSecond,
+            [FLAT_SIXTH] => TuningLayout::FlatSixth,
+            [SHARP_SECOND, FLAT_SIXTH] => TuningLayout::SharpSecondAndFlatSixth,
+            _ if self.is_chromatic(&semitones) => TuningLayout::Chromatic,
+            _ => TuningLayout::Other,
+        }
+    }
+
+    /// `true` if `semitones` fill every gap of a whole tone between this `Ring`'s diatonic bells
+    fn is_chromatic(&self, semitones: &Semitones) -> bool {
+        let bells = usize::from(self.bells);
+        (1..bells)
+            .filter(|&bell| {
+                // The degrees of the scale (where the tenor is `0`) of the bells on either side
+                let (upper, lower) = (bells - bell, bells - bell - 1);
+                pitch(upper) - pitch(lower) == 2
+            })
+            .all(|gap| semitones.bells.iter().any(|b| usize::from(b.gap()) == gap))
+    }
+}
+
+/// The number of semitones above the tenor of a bell `degree` steps up the major scale
+pub(crate) fn pitch(degree: usize) -> u32 {
+    12 * (degree / 7) as u32 + u32::from(MAJOR_SCALE[degree % 7])
+}
+
+impl FromStr for Semitones {
+    type Err = ParseSemitonesError;
+
+    /// Parses Dove's `Semitones` column, a `+`-delimited list of an optional extra treble
+    /// (`0extra`) and semitone bells (e.g. `6b` or `2#`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut semitones = Semitones::default();
+        for entry in s.split('+').map(str::trim) {
+            if entry == EXTRA_TREBLE {
+                semitones.extra_treble = true;
+                continue;
+            }
+            let parsed = entry.char_indices().last().and_then(|(idx, c)| {
+                let accidental = match c {
+                    'b' | '♭' => Accidental::Flat,
+                    '#' | '♯' => Accidental::Sharp,
+                    _ => return None,
+                };
+                let bell = entry[..idx].parse::<u8>().ok().filter(|&b| b > 0)?;
+                Some(SemitoneBell { bell, accidental })
+            });
+            semitones
+                .bells
+                .push(parsed.ok_or_else(|| ParseSemitonesError(s.to_owned()))?);
+        }
+        Ok(semitones)
+    }
+}
+
+impl Display for SemitoneBell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let accidental = match self.accidental {
+            Accidental::Flat => "b",
+            Accidental::Natural => "",
+            Accidental::Sharp => "#",
+        };
+        write!(f, "{}{}", self.bell, accidental)
+    }
+}
+
+/// `Semitones` are displayed in the same format as Dove's `Semitones` column (e.g. `0extra+6b`)
+impl Display for Semitones {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut entries = Vec::new();
+        if self.extra_treble {
+            entries.push(EXTRA_TREBLE.to_owned());
+        }
+        entries.extend(self.bells.iter().map(ToString::to_string));
+        write!(f, "{}", entries.join("+"))
+    }
+}
+
+/// The error generated when parsing invalid [`Semitones`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseSemitonesError(String);
+
+impl Display for ParseSemitonesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a list of semitone bells", self.0)
+    }
+}
+
+impl std::error::Error for ParseSemitonesError {}
@@ -0,0 +1,126 @@
+//! Generating tower definitions for ringing simulators from the tuning data in Dove's Guide.
+
+use std::fmt::Write;
+
+use crate::{Frequency, NameStyle, Note, Ring, Weight, CONCERT_A};
+
+/// The semitones above the tonic of each degree of the major scale
+pub(crate) const MAJOR_SCALE: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+/// Roughly the nominal (in Hz) of a tenor weighing one hundredweight.  Nominals scale with the
+/// inverse cube root of weight, which is enough to pick the right octave when Dove's gives a
+/// tenor's note but not its frequency.
+const ONE_CWT_NOMINAL: f64 = 1800.0;
+/// The assumed nominal of a tenor whose frequency and weight are both unknown
+const DEFAULT_TENOR_NOMINAL: f64 = 800.0;
+
+/// The estimated tuning of one bell in a [`Ring`], returned by [`Ring::estimated_tuning`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BellTuning {
+    /// The number of the bell, where the treble is `1`
+    pub number: usize,
+    pub note: Note,
+    /// The frequency of the bell's nominal
+    pub nominal: Frequency,
+}
+
+/// The file formats of ringing simulators which can be generated by
+/// [`Ring::simulator_tower_file`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SimulatorFormat {
+    /// An INI-style file in the style of Abel's tower definitions, with a `[Tower]` section
+    /// followed by a `[Bells]` section giving each bell's note and nominal
+    Abel,
+    /// A plain-text table in the style of Beltower's tower definitions, with one line per bell
+    Beltower,
+}
+
+impl Ring {
+    /// Estimates the tuning of every bell in this `Ring`, treble first.  Dove's Guide only records
+    /// the tenor, so the other bells are assumed to form a major scale above it in equal
+    /// temperament.  The tenor's measured [`Frequency`] is used if Dove's has it; otherwise its
+    /// [`Note`] is placed in the octave suggested by its [`Weight`].
+    ///
+    /// Returns `None` if Dove's gives neither the tenor's frequency nor its note, or if this `Ring`
+    /// has [semitone bells](Ring::semitones) (whose positions in the scale can't be inferred).
+    pub fn estimated_tuning(&self) -> Option<Vec<BellTuning>> {
+        if self.semitones.is_some() || self.bells == 0 {
+            return None;
+        }
+        let (tenor_note, tenor_hz) = match (self.freq, self.note) {
+            (Some(freq), note) => (note.unwrap_or_else(|| freq.nearest_note().0), freq.hz()),
+            (None, Some(note)) => (note, nominal_of(note, self.weight)),
+            (None, None) => return None,
+        };
+        let bells = usize::from(self.bells);
+        let tunings = (1..=bells)
+            .map(|number| {
+                let degree = bells - number;
+                let semitones = 12 * (degree / 7) as u32 + MAJOR_SCALE[degree % 7] as u32;
+                BellTuning {
+                    number,
+                    note: Note::with_dove_spelling(
+                        (tenor_note.semitones_from_a() as u32 + semitones) as i16,
+                    ),
+                    nominal: Frequency::from_hz(tenor_hz * 2f64.powf(semitones as f64 / 12.0)),
+                }
+            })
+            .collect();
+        Some(tunings)
+    }
+
+    /// Generates a tower definition file for a ringing simulator, using the
+    /// [estimated tuning](Ring::estimated_tuning) of this `Ring`.  Returns `None` if the tuning
+    /// can't be estimated.
+    pub fn simulator_tower_file(&self, format: SimulatorFormat) -> Option<String> {
+        let tunings = self.estimated_tuning()?;
+        let name = self.display_name(NameStyle::Dove);
+        let mut file = String::new();
+        match format {
+            SimulatorFormat::Abel => {
+                writeln!(file, "[Tower]").unwrap();
+                writeln!(file, "Name={}", name).unwrap();
+                writeln!(file, "Bells={}", self.bells).unwrap();
+                if let Some(weight) = self.weight {
+                    writeln!(file, "Tenor={}", weight).unwrap();
+                }
+                writeln!(file).unwrap();
+                writeln!(file, "[Bells]").unwrap();
+                for bell in &tunings {
+                    writeln!(
+                        file,
+                        "Bell{}={},{:.1}",
+                        bell.number,
+                        bell.note.ascii(),
+                        bell.nominal.hz()
+                    )
+                    .unwrap();
+                }
+            }
+            SimulatorFormat::Beltower => {
+                writeln!(file, "Tower: {}", name).unwrap();
+                writeln!(file, "Bells: {}", self.bells).unwrap();
+                writeln!(file, "Bell  Note  Nominal").unwrap();
+                for bell in &tunings {
+                    writeln!(
+                        file,
+                        "{:<4}  {:<4}  {:.1}",
+                        bell.number,
+                        bell.note.ascii().to_string(),
+                        bell.nominal.hz()
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        Some(file)
+    }
+}
+
+/// The frequency of the occurrence of `note` closest to the expected nominal of a tenor of the
+/// given [`Weight`]
+fn nominal_of(note: Note, weight: Option<Weight>) -> f64 {
+    let expected = weight.map_or(DEFAULT_TENOR_NOMINAL, |w| ONE_CWT_NOMINAL / w.cwt().cbrt());
+    let a_based = CONCERT_A * 2f64.powf(note.semitones_from_a() as f64 / 12.0);
+    let octaves = (expected / a_based).log2().round();
+    a_based * 2f64.powf(octaves)
+}
@@ -0,0 +1,92 @@
+//! Predicates and [`RingFilter`]s on the number of bells in a [`Ring`], for matching towers to the
+//! stages that a band wants to ring.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Ring, RingFilter, RingType};
+
+/// The standard sizes of full-circle [`Ring`]s, which are what most method ringing is designed
+/// for.  Each `StandardRing` is also a [`RingFilter`], matching the `Ring`s of exactly that size.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StandardRing {
+    Six,
+    Eight,
+    Ten,
+    Twelve,
+}
+
+impl StandardRing {
+    /// The number of bells in this `StandardRing`
+    pub fn bells(self) -> usize {
+        match self {
+            StandardRing::Six => 6,
+            StandardRing::Eight => 8,
+            StandardRing::Ten => 10,
+            StandardRing::Twelve => 12,
+        }
+    }
+
+    /// The `StandardRing` with a given number of bells, if there is one
+    pub fn from_bells(bells: u8) -> Option<Self> {
+        Some(match bells {
+            6 => StandardRing::Six,
+            8 => StandardRing::Eight,
+            10 => StandardRing::Ten,
+            12 => StandardRing::Twelve,
+            _ => return None,
+        })
+    }
+}
+
+impl Ring {
+    /// `true` if this `Ring` has at least `n` bells
+    pub fn is_at_least(&self, n: u8) -> bool {
+        self.bells >= n
+    }
+
+    /// `true` if this `Ring` has exactly `n` bells
+    pub fn is_ring_of(&self, n: u8) -> bool {
+        self.bells == n
+    }
+
+    /// The [`StandardRing`] which this `Ring` is, or `None` if it has a non-standard number of
+    /// bells or isn't hung for full-circle ringing
+    pub fn standard_ring(&self) -> Option<StandardRing> {
+        match self.ring_type {
+            RingType::FullCircle => StandardRing::from_bells(self.bells),
+            _ => None,
+        }
+    }
+}
+
+/// A [`RingFilter`] which matches [`Ring`]s with at least this many bells (see
+/// [`Ring::is_at_least`])
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AtLeast(pub u8);
+
+/// A [`RingFilter`] which matches [`Ring`]s with exactly this many bells (see
+/// [`Ring::is_ring_of`])
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RingOf(pub u8);
+
+impl RingFilter for AtLeast {
+    fn matches(&self, ring: &Ring) -> bool {
+        ring.is_at_least(self.0)
+    }
+}
+
+impl RingFilter for RingOf {
+    fn matches(&self, ring: &Ring) -> bool {
+        ring.is_ring_of(self.0)
+    }
+}
+
+impl RingFilter for StandardRing {
+    fn matches(&self, ring: &Ring) -> bool {
+        ring.standard_ring() == Some(*self)
+    }
+}
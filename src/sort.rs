@@ -0,0 +1,98 @@
+use std::cmp::Ordering;
+
+use crate::{Doves, Ring};
+
+/// The common orderings of [`Ring`]s, for use with [`Doves::sorted_by`] or (via
+/// [`SortKey::compare`]) with [`Doves::sort_by`].  Every ordering is ascending; reverse the result
+/// for e.g. heaviest first.  Ties are broken by place name, then by [`RingKey`](crate::RingKey),
+/// so the order is always deterministic.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SortKey {
+    /// By the weight of the tenor, lightest first.  `Ring`s with no known weight come last.
+    TenorWeight,
+    /// By the number of bells, fewest first
+    Bells,
+    /// Alphabetically by place name (then `place2`)
+    Place,
+    /// Alphabetically by county, then by place name.  `Ring`s with no county come last.
+    CountyThenPlace,
+}
+
+impl SortKey {
+    /// Compares two [`Ring`]s according to this `SortKey`
+    pub fn compare(self, a: &Ring, b: &Ring) -> Ordering {
+        let primary = match self {
+            SortKey::TenorWeight => match (a.weight, b.weight) {
+                (Some(wa), Some(wb)) => wa.lbs().total_cmp(&wb.lbs()),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            SortKey::Bells => a.bells.cmp(&b.bells),
+            SortKey::Place => Ordering::Equal,
+            SortKey::CountyThenPlace => match (&a.county, &b.county) {
+                (Some(ca), Some(cb)) => ca.cmp(cb),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        };
+        primary
+            .then_with(|| a.place.cmp(&b.place))
+            .then_with(|| a.place2.cmp(&b.place2))
+            .then_with(|| a.key().cmp(&b.key()))
+    }
+}
+
+impl Doves {
+    /// Returns references to every [`Ring`], sorted according to a [`SortKey`].  This doesn't
+    /// change the order of this `Doves`; use [`Doves::sort_by`] with [`SortKey::compare`] for
+    /// that.
+    pub fn sorted_by(&self, key: SortKey) -> Vec<&Ring> {
+        let mut rings = self.iter().collect::<Vec<_>>();
+        rings.sort_by(|a, b| key.compare(a, b));
+        rings
+    }
+
+    /// The `n` [`Ring`]s with the heaviest tenors, heaviest first.  `Ring`s with no known weight
+    /// are ignored.
+    pub fn heaviest(&self, n: usize) -> Vec<&Ring> {
+        let weighed = self.iter().filter(|r| r.weight.is_some()).collect();
+        top_n(weighed, n, |a, b| SortKey::TenorWeight.compare(b, a))
+    }
+
+    /// The `n` [`Ring`]s with the lightest tenors, lightest first.  `Ring`s with no known weight
+    /// are ignored.
+    pub fn lightest(&self, n: usize) -> Vec<&Ring> {
+        let weighed = self.iter().filter(|r| r.weight.is_some()).collect();
+        top_n(weighed, n, |a, b| SortKey::TenorWeight.compare(a, b))
+    }
+
+    /// The `n` [`Ring`]s with the most bells, largest first.  Rings with the same number of bells
+    /// are ordered heaviest first.
+    pub fn largest_rings(&self, n: usize) -> Vec<&Ring> {
+        top_n(self.iter().collect(), n, |a, b| {
+            b.bells
+                .cmp(&a.bells)
+                .then_with(|| SortKey::TenorWeight.compare(b, a))
+        })
+    }
+}
+
+/// Returns the first `n` [`Ring`]s according to `compare`, in order.  This only sorts the `n`
+/// selected `Ring`s, rather than all of them.
+fn top_n(
+    mut rings: Vec<&Ring>,
+    n: usize,
+    compare: impl Fn(&Ring, &Ring) -> Ordering,
+) -> Vec<&Ring> {
+    if n == 0 {
+        return Vec::new();
+    }
+    if n < rings.len() {
+        rings.select_nth_unstable_by(n - 1, |a, b| compare(a, b));
+        rings.truncate(n);
+    }
+    rings.sort_by(|a, b| compare(a, b));
+    rings
+}
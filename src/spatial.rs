@@ -0,0 +1,360 @@
+//! A spatial index over the [`Ring`]s in a [`Doves`], for geographic queries which don't scan
+//! every `Ring`.
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+use crate::{Coordinates, Distance, Doves, Ring, RingFilter};
+
+/// A [`Ring`] returned by a geographic query (e.g. [`Doves::nearest`]), along with its
+/// [`Distance`] from the query point
+#[derive(Debug, Clone, Copy)]
+pub struct Nearby<'d> {
+    pub ring: &'d Ring,
+    pub distance: Distance,
+}
+
+/// A k-d tree over the [best coordinates](Ring::best_coordinates) of every [`Ring`] in a
+/// [`Doves`].  Like the other [indices](crate::IndexKind), this is built the first time it's needed
+/// and thrown away whenever the `Doves` is mutated.
+///
+/// Points are stored as 3D unit vectors rather than latitudes and longitudes, so the tree works
+/// the same way everywhere on the globe (including across the antimeridian).  The straight-line
+/// distance between two unit vectors increases with the great-circle distance between the
+/// points, so the nearest points in the tree are also the nearest points on the Earth.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SpatialIndex {
+    /// The points in the tree, with the index of their `Ring` in [`Doves::towers`].  The tree is
+    /// implicit: the root of each sub-slice is its middle element, with the points before it on
+    /// one side of its splitting plane and the points after it on the other.
+    nodes: Vec<([f64; 3], usize)>,
+}
+
+impl SpatialIndex {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "build_spatial_index", level = "debug", skip_all)
+    )]
+    pub(crate) fn new(rings: &[Ring]) -> Self {
+        let mut nodes = rings
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, ring)| Some((unit_vector(ring.best_coordinates()?.0), idx)))
+            .collect::<Vec<_>>();
+        build(&mut nodes, 0);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(points = nodes.len(), "built spatial index");
+        Self { nodes }
+    }
+
+    /// Returns the indices of the [`Ring`]s in this index, in increasing order of their distance
+    /// from `coords`.  Points are only visited as they're needed, so taking the first few items is
+    /// cheap.
+    pub(crate) fn by_distance(&self, coords: Coordinates) -> impl Iterator<Item = usize> + '_ {
+        let query = unit_vector(coords);
+        let mut queue = BinaryHeap::new();
+        if !self.nodes.is_empty() {
+            queue.push(Reverse(Candidate {
+                chord: 0.0,
+                item: Item::SubTree {
+                    start: 0,
+                    end: self.nodes.len(),
+                    depth: 0,
+                },
+            }));
+        }
+        // Best-first search: `queue` always holds a lower bound on the distance of every point
+        // not yet returned, so points are popped in order of distance
+        std::iter::from_fn(move || loop {
+            let Reverse(Candidate { chord, item }) = queue.pop()?;
+            let (start, end, depth) = match item {
+                Item::Point(idx) => return Some(idx),
+                Item::SubTree { start, end, depth } => (start, end, depth),
+            };
+            let mid = (start + end) / 2;
+            let (point, ring_idx) = self.nodes[mid];
+            queue.push(Reverse(Candidate {
+                chord: chord_length(query, point),
+                item: Item::Point(ring_idx),
+            }));
+            // Every point on the far side of the splitting plane is at least as far away as the
+            // plane itself
+            let axis = depth % 3;
+            let plane_dist = (query[axis] - point[axis]).abs();
+            let (near, far) = if query[axis] < point[axis] {
+                ((start, mid), (mid + 1, end))
+            } else {
+                ((mid + 1, end), (start, mid))
+            };
+            for ((start, end), bound) in [(near, chord), (far, chord.max(plane_dist))] {
+                if start < end {
+                    queue.push(Reverse(Candidate {
+                        chord: bound,
+                        item: Item::SubTree {
+                            start,
+                            end,
+                            depth: depth + 1,
+                        },
+                    }));
+                }
+            }
+        })
+    }
+}
+
+/// Recursively arranges `nodes` into an implicit k-d tree, splitting on the given axis first
+fn build(nodes: &mut [([f64; 3], usize)], depth: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    let mid = nodes.len() / 2;
+    nodes.select_nth_unstable_by(mid, |(a, _), (b, _)| a[axis].total_cmp(&b[axis]));
+    let (before, after) = nodes.split_at_mut(mid);
+    build(before, depth + 1);
+    build(&mut after[1..], depth + 1);
+}
+
+/// An entry in the priority queue of [`SpatialIndex::by_distance`]
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    /// The distance to this point, or a lower bound on the distance to every point in this
+    /// sub-tree
+    chord: f64,
+    item: Item,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Item {
+    /// A single point, given by the index of its `Ring`
+    Point(usize),
+    /// The points in `nodes[start..end]`
+    SubTree {
+        start: usize,
+        end: usize,
+        depth: usize,
+    },
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.chord.total_cmp(&other.chord)
+    }
+}
+
+/// Converts [`Coordinates`] into a point on the unit sphere
+pub(crate) fn unit_vector(coords: Coordinates) -> [f64; 3] {
+    let (lat, long) = (coords.lat().to_radians(), coords.long().to_radians());
+    [lat.cos() * long.cos(), lat.cos() * long.sin(), lat.sin()]
+}
+
+/// Converts the direction of a vector into [`Coordinates`], or returns `None` for the zero vector
+pub(crate) fn from_vector([x, y, z]: [f64; 3]) -> Option<Coordinates> {
+    if x == 0.0 && y == 0.0 && z == 0.0 {
+        return None;
+    }
+    let lat = z.atan2(x.hypot(y)).to_degrees();
+    let long = y.atan2(x).to_degrees();
+    Some(Coordinates::new(lat, long).unwrap())
+}
+
+/// The straight-line distance between two points on the unit sphere
+fn chord_length(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a.iter()
+        .zip(&b)
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f64>()
+        .sqrt()
+}
+
+impl Doves {
+    /// Finds the `n` [`Ring`]s nearest to some [`Coordinates`] which pass a [`RingFilter`],
+    /// nearest first.  `Ring`s without coordinates are never returned.  This uses a spatial index
+    /// (built on the first geographic query), so only visits the `Ring`s close to `coords`.
+    pub fn nearest(
+        &self,
+        coords: Coordinates,
+        n: usize,
+        filter: impl RingFilter,
+    ) -> Vec<Nearby<'_>> {
+        self.spatial_index()
+            .by_distance(coords)
+            .map(|idx| &self.towers[idx])
+            .filter(|ring| filter.matches(ring))
+            .take(n)
+            .map(|ring| Nearby {
+                ring,
+                distance: ring.distance_to(coords).unwrap(),
+            })
+            .collect()
+    }
+
+    /// Finds every [`Ring`] within a given [`Distance`] of some [`Coordinates`] which passes a
+    /// [`RingFilter`], nearest first.  `Ring`s without coordinates are never returned.  Like
+    /// [`Doves::nearest`], this only visits the `Ring`s close to `coords`.
+    pub fn within_radius(
+        &self,
+        coords: Coordinates,
+        radius: Distance,
+        filter: impl RingFilter,
+    ) -> Vec<Nearby<'_>> {
+        self.spatial_index()
+            .by_distance(coords)
+            .map(|idx| {
+                let ring = &self.towers[idx];
+                Nearby {
+                    ring,
+                    distance: ring.distance_to(coords).unwrap(),
+                }
+            })
+            .take_while(|nearby| nearby.distance <= radius)
+            .filter(|nearby| filter.matches(nearby.ring))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Coordinates, Distance, Doves, Ring, RingBuilder, TowerId};
+
+    /// A deterministic pseudo-random number in `0.0..1.0`, from a linear congruential generator
+    fn random(state: &mut u64) -> f64 {
+        *state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (*state >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// `Ring`s scattered over the globe, with a dense cluster in England, a cluster across the
+    /// antimeridian, and some without coordinates
+    fn doves() -> Doves {
+        let mut state = 42;
+        let mut rings = Vec::new();
+        for id in 1..=600u32 {
+            let r = (random(&mut state), random(&mut state));
+            let (lat, long) = match id % 4 {
+                0 => (r.0 * 180.0 - 90.0, r.1 * 360.0 - 180.0),
+                1 | 2 => (50.0 + r.0 * 5.0, -5.0 + r.1 * 6.0),
+                _ => {
+                    let long = 175.0 + r.1 * 10.0;
+                    let long = if long > 180.0 { long - 360.0 } else { long };
+                    (-20.0 + r.0 * 10.0, long)
+                }
+            };
+            let mut builder = RingBuilder::new(TowerId::new(id), format!("Place {}", id), 6);
+            if id % 50 != 0 {
+                builder = builder.coordinates(Coordinates::new(lat, long).unwrap());
+            }
+            rings.push(builder.build().unwrap());
+        }
+        Doves::from(rings)
+    }
+
+    /// Every `Ring` with coordinates, sorted by distance from `coords` by checking every one
+    fn brute_force(doves: &Doves, coords: Coordinates) -> Vec<(f64, &Ring)> {
+        let mut rings = doves
+            .iter()
+            .filter_map(|ring| Some((ring.distance_to(coords)?.km(), ring)))
+            .collect::<Vec<_>>();
+        rings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        rings
+    }
+
+    fn queries() -> Vec<Coordinates> {
+        let mut state = 7;
+        let mut queries = (0..40)
+            .map(|_| {
+                let lat = random(&mut state) * 180.0 - 90.0;
+                let long = random(&mut state) * 360.0 - 180.0;
+                Coordinates::new(lat, long).unwrap()
+            })
+            .collect::<Vec<_>>();
+        for &(lat, long) in &[(52.5, -1.5), (-15.0, 180.0), (-15.0, -179.9), (90.0, 0.0)] {
+            queries.push(Coordinates::new(lat, long).unwrap());
+        }
+        queries
+    }
+
+    fn assert_same_distances(tree: &[f64], brute: &[f64]) {
+        assert_eq!(tree.len(), brute.len());
+        for (a, b) in tree.iter().zip(brute) {
+            assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn nearest_matches_brute_force() {
+        let doves = doves();
+        for coords in queries() {
+            let brute = brute_force(&doves, coords);
+            for &n in &[0, 1, 5, 50, 1000] {
+                let nearest = doves.nearest(coords, n, |_: &Ring| true);
+                let tree = nearest.iter().map(|r| r.distance.km()).collect::<Vec<_>>();
+                let expected = brute.iter().take(n).map(|r| r.0).collect::<Vec<_>>();
+                assert_same_distances(&tree, &expected);
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_with_filter() {
+        let doves = doves();
+        let is_odd = |ring: &Ring| ring.id.get() % 2 == 1;
+        for coords in queries() {
+            let nearest = doves.nearest(coords, 20, is_odd);
+            assert!(nearest.iter().all(|r| is_odd(r.ring)));
+            let tree = nearest.iter().map(|r| r.distance.km()).collect::<Vec<_>>();
+            let brute = brute_force(&doves, coords);
+            let expected = brute
+                .iter()
+                .filter(|r| is_odd(r.1))
+                .take(20)
+                .map(|r| r.0)
+                .collect::<Vec<_>>();
+            assert_same_distances(&tree, &expected);
+        }
+    }
+
+    #[test]
+    fn within_radius_matches_brute_force() {
+        let doves = doves();
+        for coords in queries() {
+            for &km in &[0.0, 10.0, 300.0, 5000.0] {
+                let within = doves.within_radius(coords, Distance::from_km(km), |_: &Ring| true);
+                let tree = within.iter().map(|r| r.distance.km()).collect::<Vec<_>>();
+                let brute = brute_force(&doves, coords);
+                let expected = brute
+                    .iter()
+                    .map(|r| r.0)
+                    .filter(|&d| d <= km)
+                    .collect::<Vec<_>>();
+                assert_same_distances(&tree, &expected);
+            }
+        }
+    }
+
+    #[test]
+    fn rings_without_coordinates_are_skipped() {
+        let doves = doves();
+        let coords = Coordinates::new(52.5, -1.5).unwrap();
+        let all = doves.nearest(coords, usize::MAX, |_: &Ring| true);
+        assert_eq!(all.len(), 600 - 12);
+        assert!(all.iter().all(|r| r.ring.coordinates.is_some()));
+    }
+}
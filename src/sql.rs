@@ -0,0 +1,59 @@
+//! Exporting rings as SQL statements, for loading Dove's Guide into SQLite.
+
+use std::fmt::Write;
+
+use crate::{
+    bundle::{Encoding, DOVE_COORDINATE_DECIMALS},
+    Column, Doves, RingFilter,
+};
+
+/// The name of the table created by [`Doves::to_sql`]
+const TABLE: &str = "rings";
+
+impl Doves {
+    /// Renders every [`Ring`](crate::Ring) which passes a [`RingFilter`] as SQL statements which
+    /// create and fill a table called `rings`, with one column per [`Column`] (named by its
+    /// header in Dove's CSV file).  The output is in SQLite's dialect, so can be loaded with e.g.
+    /// `sqlite3 dove.db < dove.sql`.  Numbers are stored as numbers, flags (e.g. `GF`) as `1`,
+    /// and empty values as `NULL`.
+    pub fn to_sql(&self, filter: impl RingFilter) -> String {
+        let mut sql = String::from("BEGIN TRANSACTION;\n");
+        let column_defs = Column::all()
+            .map(|column| {
+                let ty = match column.encoding() {
+                    Encoding::Number | Encoding::Coordinate => "NUMERIC",
+                    Encoding::Flag => "INTEGER",
+                    Encoding::Text => "TEXT",
+                };
+                format!("{} {}", sql_identifier(column.dove_header()), ty)
+            })
+            .collect::<Vec<_>>();
+        writeln!(sql, "CREATE TABLE {} ({});", TABLE, column_defs.join(", ")).unwrap();
+        for ring in self.filter(filter) {
+            let values = Column::all()
+                .map(
+                    |column| match column.json_value(ring, DOVE_COORDINATE_DECIMALS) {
+                        None => "NULL".to_owned(),
+                        Some(_) if column.encoding() == Encoding::Text => {
+                            sql_string(&column.value(ring))
+                        }
+                        Some(value) => value,
+                    },
+                )
+                .collect::<Vec<_>>();
+            writeln!(sql, "INSERT INTO {} VALUES ({});", TABLE, values.join(", ")).unwrap();
+        }
+        sql.push_str("COMMIT;\n");
+        sql
+    }
+}
+
+/// Quotes a string as an SQL identifier
+fn sql_identifier(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+/// Quotes a string as an SQL string literal
+fn sql_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
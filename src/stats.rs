@@ -0,0 +1,119 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{AccessStatus, Affiliation, Country, Doves, Ring, Symbol, Weight};
+
+/// Summary statistics over a set of [`Ring`]s, created with [`Doves::stats`] or (for a filtered
+/// subset) [`Stats::from_rings`].
+#[derive(Debug, Clone)]
+pub struct Stats<'d> {
+    /// The total number of `Ring`s
+    pub num_rings: usize,
+    /// The number of `Ring`s with each number of bells
+    pub by_bells: BTreeMap<u8, usize>,
+    /// The number of `Ring`s in each county.  `Ring`s with no county aren't counted.
+    pub by_county: BTreeMap<Symbol, usize>,
+    /// The number of `Ring`s in each [`Country`].  `Ring`s with no country aren't counted.
+    pub by_country: HashMap<Country, usize>,
+    /// The number of `Ring`s with each [`Affiliation`]
+    pub by_affiliation: HashMap<Affiliation, usize>,
+    /// The `Ring` with the heaviest tenor, if any `Ring`s have a known weight
+    pub heaviest: Option<&'d Ring>,
+    /// The `Ring` with the lightest tenor, if any `Ring`s have a known weight
+    pub lightest: Option<&'d Ring>,
+    /// The median tenor weight, over every `Ring` with a known weight
+    pub median_tenor_weight: Option<Weight>,
+    /// The percentage (from 0 to 100) of `Ring`s which are rung from the ground floor
+    pub ground_floor_percent: f64,
+    /// The percentage (from 0 to 100) of `Ring`s which are [`AccessStatus::Unringable`]
+    pub unringable_percent: f64,
+    /// The number of `Ring`s which can currently be rung (see [`Ring::is_ringable`])
+    pub num_ringable: usize,
+    /// The total number of bells in every `Ring`
+    pub total_bells: usize,
+    /// The total number of [ringable bells](Ring::ringable_bells) in every `Ring`
+    pub total_ringable_bells: usize,
+}
+
+impl<'d> Stats<'d> {
+    /// Computes the `Stats` of any set of [`Ring`]s (e.g. the results of a
+    /// [`Query`](crate::Query))
+    pub fn from_rings(rings: impl IntoIterator<Item = &'d Ring>) -> Self {
+        let mut by_bells = BTreeMap::new();
+        let mut by_county = BTreeMap::new();
+        let mut by_country = HashMap::new();
+        let mut by_affiliation = HashMap::new();
+        let mut weighed = Vec::<&Ring>::new();
+        let mut num_rings = 0;
+        let mut num_ground_floor = 0;
+        let mut num_unringable = 0;
+        let mut num_ringable = 0;
+        let mut total_bells = 0;
+        let mut total_ringable_bells = 0;
+
+        for ring in rings {
+            num_rings += 1;
+            *by_bells.entry(ring.bells).or_insert(0) += 1;
+            if let Some(county) = &ring.county {
+                *by_county.entry(county.clone()).or_insert(0) += 1;
+            }
+            if let Some(country) = &ring.country {
+                *by_country.entry(country.clone()).or_insert(0) += 1;
+            }
+            for affiliation in &ring.affiliations {
+                *by_affiliation.entry(affiliation.clone()).or_insert(0) += 1;
+            }
+            if ring.weight.is_some() {
+                weighed.push(ring);
+            }
+            num_ground_floor += usize::from(ring.ground_floor());
+            num_unringable += usize::from(ring.access == AccessStatus::Unringable);
+            num_ringable += usize::from(ring.is_ringable());
+            total_bells += usize::from(ring.bells);
+            total_ringable_bells += usize::from(ring.ringable_bells());
+        }
+
+        let tenor_lbs = |r: &Ring| r.weight.map_or(0.0, Weight::lbs);
+        weighed.sort_by(|a, b| tenor_lbs(a).total_cmp(&tenor_lbs(b)));
+        let median_tenor_weight = match weighed.len() {
+            0 => None,
+            n if n % 2 == 1 => weighed[n / 2].weight,
+            n => Some(Weight::from_lbs(
+                (tenor_lbs(weighed[n / 2 - 1]) + tenor_lbs(weighed[n / 2])) / 2.0,
+            )),
+        };
+        let percent = |count: usize| match num_rings {
+            0 => 0.0,
+            _ => count as f64 * 100.0 / num_rings as f64,
+        };
+
+        Self {
+            num_rings,
+            by_bells,
+            by_county,
+            by_country,
+            by_affiliation,
+            heaviest: weighed.last().copied(),
+            lightest: weighed.first().copied(),
+            median_tenor_weight,
+            ground_floor_percent: percent(num_ground_floor),
+            unringable_percent: percent(num_unringable),
+            num_ringable,
+            total_bells,
+            total_ringable_bells,
+        }
+    }
+}
+
+impl Doves {
+    /// Computes the [`Stats`] of every [`Ring`] in this `Doves`
+    pub fn stats(&self) -> Stats<'_> {
+        Stats::from_rings(self)
+    }
+
+    /// Computes the [`Stats`] of only the [`Ring`]s which can currently be rung (see
+    /// [`Ring::is_ringable`]), so that derelict or silenced rings don't skew figures such as the
+    /// number of rings of each size
+    pub fn ringable_stats(&self) -> Stats<'_> {
+        Stats::from_rings(self.iter().filter(|ring| ring.is_ringable()))
+    }
+}
@@ -0,0 +1,117 @@
+//! The smaller rings which can be rung using some of the bells of a [`Ring`] (e.g. the back 8 of
+//! a 12, or a light 6 using a sharp 2nd).
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+};
+
+use crate::{semitones::pitch, Accidental, Ring, SemitoneBell};
+
+/// The fewest bells which are counted as a [`SubRing`]
+const MIN_STAGE: usize = 4;
+
+/// A set of bells in a [`Ring`] which form a major scale, so can be rung on their own.  Returned
+/// by [`Ring::available_back_rings`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SubRing {
+    /// The bells, from the treble down to the tenor
+    pub bells: Vec<BellPosition>,
+}
+
+/// Which bell of a [`Ring`] is used in a [`SubRing`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum BellPosition {
+    /// An extra treble, above the ring's treble
+    ExtraTreble,
+    /// One of the [`Ring::bells`] of the diatonic ring, where the treble is `1`
+    Diatonic(u8),
+    /// A semitone bell
+    Semitone(SemitoneBell),
+}
+
+impl SubRing {
+    /// The number of bells in this `SubRing`
+    pub fn stage(&self) -> usize {
+        self.bells.len()
+    }
+
+    /// The heaviest bell in this `SubRing`
+    pub fn tenor(&self) -> BellPosition {
+        *self.bells.last().expect("`SubRing`s are never empty")
+    }
+
+    /// `true` if this `SubRing` uses any semitone bells (or the extra treble)
+    pub fn uses_extra_bells(&self) -> bool {
+        self.bells
+            .iter()
+            .any(|b| !matches!(b, BellPosition::Diatonic(_)))
+    }
+}
+
+impl Ring {
+    /// Every [`SubRing`] of at least 4 bells which can be rung from this `Ring`, including the
+    /// whole ring itself.  These are found by treating every bell as a possible tenor and looking
+    /// for the major scale above it, so a plain 12 gives its back 4 to back 12 as well as (for
+    /// example) the 6 bells in the key of the 8th.  [Semitone bells](Ring::semitone_bells) and
+    /// an extra treble give more `SubRing`s, such as the light 8 using the sharp 2nd.
+    ///
+    /// `SubRing`s are sorted by decreasing stage, then from the heaviest tenor to the lightest.
+    pub fn available_back_rings(&self) -> Vec<SubRing> {
+        let bells = usize::from(self.bells);
+        let semitones = self.semitone_bells().unwrap_or_default();
+        let diatonic_pitch = |bell: u8| pitch(bells - usize::from(bell));
+
+        // Every bell, keyed by the number of semitones above the tenor
+        let mut pitches = BTreeMap::new();
+        for bell in 1..=self.bells {
+            pitches.insert(diatonic_pitch(bell), BellPosition::Diatonic(bell));
+        }
+        if semitones.extra_treble && bells > 0 {
+            pitches.insert(pitch(bells), BellPosition::ExtraTreble);
+        }
+        for &bell in semitones.bells.iter().filter(|b| b.bell <= self.bells) {
+            let pitch = match bell.accidental {
+                Accidental::Sharp => diatonic_pitch(bell.bell) + 1,
+                Accidental::Flat => match diatonic_pitch(bell.bell).checked_sub(1) {
+                    Some(pitch) => pitch,
+                    None => continue, // A flat tenor can't be placed in the ring
+                },
+                Accidental::Natural => continue,
+            };
+            pitches.entry(pitch).or_insert(BellPosition::Semitone(bell));
+        }
+
+        let mut sub_rings = Vec::new();
+        for &tenor in pitches.keys() {
+            let mut scale = Vec::new();
+            while let Some(&bell) = pitches.get(&(tenor + pitch(scale.len()))) {
+                scale.push(bell);
+                if scale.len() >= MIN_STAGE {
+                    let bells = scale.iter().rev().copied().collect();
+                    sub_rings.push(SubRing { bells });
+                }
+            }
+        }
+        sub_rings.sort_by_key(|sub_ring| std::cmp::Reverse(sub_ring.stage()));
+        sub_rings
+    }
+}
+
+impl Display for BellPosition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BellPosition::ExtraTreble => write!(f, "extra treble"),
+            BellPosition::Diatonic(bell) => write!(f, "{}", bell),
+            BellPosition::Semitone(bell) => write!(f, "{}", bell),
+        }
+    }
+}
+
+/// `SubRing`s are displayed as their bells, treble first (e.g. `1, 2#, 3, 4, 5, 6, 7, 8`)
+impl Display for SubRing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bells = self.bells.iter().map(ToString::to_string);
+        write!(f, "{}", bells.collect::<Vec<_>>().join(", "))
+    }
+}
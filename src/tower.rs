@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use crate::{Coordinates, Country, Dedication, Diocese, Doves, Ring, SmolStr, Symbol, TowerId};
+
+/// A tower, containing one or more [`Ring`]s.  Location fields are shared by every `Ring` in a
+/// tower, so they are exposed once here (taken from the tower's first `Ring`).
+#[derive(Debug, Clone)]
+pub struct Tower<'d> {
+    id: TowerId,
+    rings: Vec<&'d Ring>,
+}
+
+impl<'d> Tower<'d> {
+    /// The [`TowerId`] shared by every [`Ring`] in this `Tower`
+    pub fn id(&self) -> TowerId {
+        self.id
+    }
+
+    /// The [`Ring`]s in this `Tower`, in the order they appear in Dove's Guide.  This is never
+    /// empty.
+    pub fn rings(&self) -> &[&'d Ring] {
+        &self.rings
+    }
+
+    /// The first [`Ring`] listed for this `Tower`, which is usually the main ring
+    pub fn main_ring(&self) -> &'d Ring {
+        self.rings[0]
+    }
+
+    /// The name of the place containing this `Tower`
+    pub fn place(&self) -> &'d SmolStr {
+        &self.main_ring().place
+    }
+
+    /// The dedication of the church containing this `Tower`
+    pub fn dedication(&self) -> &'d Dedication {
+        &self.main_ring().dedication
+    }
+
+    /// The county containing this `Tower`
+    pub fn county(&self) -> Option<&'d Symbol> {
+        self.main_ring().county.as_ref()
+    }
+
+    /// The [`Country`] containing this `Tower`
+    pub fn country(&self) -> Option<&'d Country> {
+        self.main_ring().country.as_ref()
+    }
+
+    /// The [`Diocese`] containing this `Tower`
+    pub fn diocese(&self) -> Option<&'d Diocese> {
+        self.main_ring().diocese.as_ref()
+    }
+
+    /// The [`Coordinates`] of this `Tower`
+    pub fn coordinates(&self) -> Option<Coordinates> {
+        self.main_ring().coordinates
+    }
+
+    /// The total number of bells in every [`Ring`] in this `Tower`
+    pub fn total_bells(&self) -> usize {
+        self.rings.iter().map(|r| usize::from(r.bells)).sum()
+    }
+
+    /// The total number of [ringable bells](Ring::ringable_bells) in every [`Ring`] in this `Tower`
+    pub fn total_ringable_bells(&self) -> usize {
+        self.rings
+            .iter()
+            .map(|r| usize::from(r.ringable_bells()))
+            .sum()
+    }
+}
+
+impl Doves {
+    /// Groups the [`Ring`]s into [`Tower`]s by their [`TowerId`].  `Tower`s are returned in the
+    /// order that their first `Ring` appears.
+    pub fn towers_grouped(&self) -> Vec<Tower<'_>> {
+        let mut towers = Vec::<Tower>::new();
+        let mut tower_idxs = HashMap::<TowerId, usize>::new();
+        for ring in &self.towers {
+            match tower_idxs.get(&ring.id) {
+                Some(&idx) => towers[idx].rings.push(ring),
+                None => {
+                    tower_idxs.insert(ring.id, towers.len());
+                    towers.push(Tower {
+                        id: ring.id,
+                        rings: vec![ring],
+                    });
+                }
+            }
+        }
+        towers
+    }
+}
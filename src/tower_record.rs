@@ -0,0 +1,318 @@
+//! Dove's `towers.csv` file, which has one row per tower (rather than per ring) giving only the
+//! fields which describe the building, and reconciling it with the ring-level data.
+
+use std::{collections::HashMap, ptr};
+#[cfg(feature = "serde")]
+use std::{convert::TryFrom, fs::File, io::Read, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+#[cfg(feature = "serde")]
+use crate::record::{
+    combine_coordinates, deser_from_str, deser_not_empty, deser_option_from_str,
+    deser_option_not_none, RecordError,
+};
+use crate::{
+    ChurchCareId, Column, Coordinates, Country, CountryCode, Dedication, Diocese, Doves, Postcode,
+    Ring, SmolStr, TowerId,
+};
+
+/// A single row of Dove's `towers.csv` file.  Each field has the same meaning (and type) as the
+/// field of the same name in [`Ring`], and is shared by every `Ring` in the tower.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(try_from = "TowerRow"))]
+pub struct TowerRecord {
+    pub id: TowerId,
+    pub place: SmolStr,
+    pub place2: Option<Box<str>>,
+    pub place_county_list: Option<Box<str>>,
+    pub dedication: Dedication,
+    pub alt_name: Option<Box<str>>,
+    pub county: Option<SmolStr>,
+    pub country: Option<Country>,
+    pub iso_3166_code: Option<CountryCode>,
+    pub diocese: Option<Diocese>,
+    pub os_grid_ref: Option<SmolStr>,
+    pub postcode: Option<Postcode>,
+    pub coordinates: Option<Coordinates>,
+    pub satnav_coordinates: Option<Coordinates>,
+    /// `true` if the church has a toilet
+    pub toilet: bool,
+    pub url: Option<String>,
+    pub building_id: Option<SmolStr>,
+    pub building_grade: Option<SmolStr>,
+    pub church_care: Option<ChurchCareId>,
+    /// The tower's ID in TowerBase, or `0` if it isn't in TowerBase
+    pub towerbase_id: u32,
+}
+
+/// The [`Column`]s of Dove's CSV file which describe the building rather than the bells, and so
+/// are given in `towers.csv`
+static TOWER_COLUMNS: [Column; 20] = [
+    Column::Place,
+    Column::Place2,
+    Column::PlaceCountyList,
+    Column::Dedication,
+    Column::AltName,
+    Column::County,
+    Column::Country,
+    Column::Iso3166Code,
+    Column::Diocese,
+    Column::Lat,
+    Column::Long,
+    Column::SatnavLat,
+    Column::SatnavLong,
+    Column::Toilet,
+    Column::WebPage,
+    Column::OsGridRef,
+    Column::Postcode,
+    Column::BuildingGrade,
+    Column::BuildingId,
+    Column::ChurchCare,
+];
+
+impl TowerRecord {
+    /// Reads every `TowerRecord` from the contents of Dove's `towers.csv` file.  Columns which
+    /// aren't building-level fields are ignored, and missing columns are treated as empty.
+    #[cfg(feature = "serde")]
+    pub fn from_reader(reader: impl Read) -> Result<Vec<Self>, csv::Error> {
+        csv::Reader::from_reader(reader).deserialize().collect()
+    }
+
+    /// Reads every `TowerRecord` from Dove's `towers.csv` file (see [`TowerRecord::from_reader`])
+    #[cfg(feature = "serde")]
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Vec<Self>, csv::Error> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// The value of a building-level [`Column`] for this tower, formatted in the same way as
+    /// [`Column::value`].  Returns `None` for columns which describe the bells (e.g.
+    /// [`Column::Bells`]), since they aren't in `towers.csv`.
+    pub fn value(&self, column: Column) -> Option<String> {
+        fn opt(value: Option<impl ToString>) -> String {
+            value.map_or_else(String::new, |v| v.to_string())
+        }
+        let coord = |value: Option<f64>| opt(value.map(|x| format!("{:.5}", x)));
+
+        let coords = self.coordinates;
+        let satnav = self.satnav_coordinates;
+        Some(match column {
+            Column::TowerId => self.id.to_string(),
+            Column::Place => self.place.to_string(),
+            Column::Place2 => opt(self.place2.as_ref()),
+            Column::PlaceCountyList => opt(self.place_county_list.as_ref()),
+            Column::Dedication => self.dedication.to_string(),
+            Column::AltName => opt(self.alt_name.as_ref()),
+            Column::County => opt(self.county.as_ref()),
+            Column::Country => opt(self.country.as_ref()),
+            Column::Iso3166Code => opt(self.iso_3166_code.as_ref()),
+            Column::Diocese => opt(self.diocese.as_ref().map(|d| d.dove_code())),
+            Column::Lat => coord(coords.map(|c| c.lat())),
+            Column::Long => coord(coords.map(|c| c.long())),
+            Column::SatnavLat => coord(satnav.map(|c| c.lat())),
+            Column::SatnavLong => coord(satnav.map(|c| c.long())),
+            Column::Toilet => if self.toilet { "T" } else { "" }.to_owned(),
+            Column::WebPage => opt(self.url.as_ref()),
+            Column::OsGridRef => opt(self.os_grid_ref.as_ref()),
+            Column::Postcode => opt(self.postcode.as_ref()),
+            Column::BuildingGrade => opt(self.building_grade.as_ref()),
+            Column::BuildingId => opt(self.building_id.as_ref()),
+            Column::ChurchCare => opt(self.church_care.map(|id| id.get())),
+            Column::TowerBase => format!("{:04}", self.towerbase_id),
+            _ => return None,
+        })
+    }
+}
+
+/// A disagreement between Dove's `towers.csv` and the [`Ring`]s in a [`Doves`], found by
+/// [`Doves::reconcile_towers`]
+#[derive(Debug, Clone)]
+pub enum TowerMismatch<'d> {
+    /// A tower in `towers.csv` has no `Ring`s
+    NoRings(&'d TowerRecord),
+    /// A tower with `Ring`s (the first of which is given) isn't in `towers.csv`
+    NoTowerRecord(&'d Ring),
+    /// A building-level field of a `Ring` differs from its tower's value
+    Field {
+        ring: &'d Ring,
+        column: Column,
+        tower_value: String,
+        ring_value: String,
+    },
+}
+
+impl TowerMismatch<'_> {
+    /// The [`TowerId`] of the tower which doesn't match
+    pub fn id(&self) -> TowerId {
+        match self {
+            TowerMismatch::NoRings(tower) => tower.id,
+            TowerMismatch::NoTowerRecord(ring) | TowerMismatch::Field { ring, .. } => ring.id,
+        }
+    }
+}
+
+impl Doves {
+    /// Matches the [`Ring`]s in this `Doves` with the towers of Dove's `towers.csv` by
+    /// [`TowerId`], returning every disagreement between them.  Building-level fields are compared
+    /// for every `Ring` in each tower, using the formatting of Dove's CSV file (see
+    /// [`TowerRecord::value`]).  Fields which are empty for every tower are assumed to be missing
+    /// from `towers.csv`, so aren't compared.  Mismatches are given in the order of Dove's Guide,
+    /// followed by any towers without `Ring`s.
+    pub fn reconcile_towers<'d>(&'d self, towers: &'d [TowerRecord]) -> Vec<TowerMismatch<'d>> {
+        let mut by_id = HashMap::<TowerId, &TowerRecord>::new();
+        for tower in towers {
+            by_id.entry(tower.id).or_insert(tower);
+        }
+
+        // Columns which are empty for every tower are probably missing from the file, so
+        // comparing them would flag every `Ring`
+        let columns = TOWER_COLUMNS
+            .iter()
+            .copied()
+            .filter(|&column| {
+                towers
+                    .iter()
+                    .any(|t| t.value(column) != Some(String::new()))
+            })
+            .collect::<Vec<_>>();
+
+        let mut mismatches = Vec::new();
+        for ring in self {
+            let tower = match by_id.get(&ring.id) {
+                Some(tower) => tower,
+                None => {
+                    // Only report each tower once, for its first `Ring`
+                    if self
+                        .get_exact(ring.id)
+                        .is_some_and(|first| ptr::eq(first, ring))
+                    {
+                        mismatches.push(TowerMismatch::NoTowerRecord(ring));
+                    }
+                    continue;
+                }
+            };
+            for &column in &columns {
+                let tower_value = tower.value(column).unwrap();
+                let ring_value = column.value(ring);
+                if tower_value != ring_value {
+                    mismatches.push(TowerMismatch::Field {
+                        ring,
+                        column,
+                        tower_value,
+                        ring_value,
+                    });
+                }
+            }
+        }
+        mismatches.extend(
+            towers
+                .iter()
+                .filter(|tower| !self.contains_id(tower.id))
+                .map(TowerMismatch::NoRings),
+        );
+        mismatches
+    }
+}
+
+/// A single row of Dove's `towers.csv` file, before any fields are combined
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Deserialize)]
+struct TowerRow {
+    #[serde(rename = "TowerID")]
+    id: TowerId,
+    #[serde(rename = "Place")]
+    place: SmolStr,
+    #[serde(rename = "Place2", default)]
+    place2: Option<Box<str>>,
+    #[serde(rename = "PlaceCL", default)]
+    place_county_list: Option<Box<str>>,
+    #[serde(rename = "Dedicn", deserialize_with = "deser_from_str")]
+    dedication: Dedication,
+    #[serde(rename = "AltName", default)]
+    alt_name: Option<Box<str>>,
+    #[serde(rename = "County", default)]
+    county: Option<SmolStr>,
+    #[serde(
+        rename = "Country",
+        default,
+        deserialize_with = "deser_option_from_str"
+    )]
+    country: Option<Country>,
+    #[serde(
+        rename = "ISO3166code",
+        default,
+        deserialize_with = "deser_option_from_str"
+    )]
+    iso_3166_code: Option<CountryCode>,
+    #[serde(
+        rename = "Diocese",
+        default,
+        deserialize_with = "deser_option_from_str"
+    )]
+    diocese: Option<Diocese>,
+    #[serde(rename = "NG", default)]
+    os_grid_ref: Option<SmolStr>,
+    #[serde(
+        rename = "Postcode",
+        default,
+        deserialize_with = "deser_option_from_str"
+    )]
+    postcode: Option<Postcode>,
+    #[serde(rename = "Lat", default)]
+    lat: Option<f64>,
+    #[serde(rename = "Long", default)]
+    long: Option<f64>,
+    #[serde(rename = "SNLat", default)]
+    satnav_lat: Option<f64>,
+    #[serde(rename = "SNLong", default)]
+    satnav_long: Option<f64>,
+    #[serde(rename = "Toilet", default, deserialize_with = "deser_not_empty")]
+    toilet: bool,
+    #[serde(rename = "WebPage", default)]
+    url: Option<String>,
+    #[serde(rename = "BldgID", default, deserialize_with = "deser_option_not_none")]
+    building_id: Option<SmolStr>,
+    #[serde(rename = "LGrade", default, deserialize_with = "deser_option_not_none")]
+    building_grade: Option<SmolStr>,
+    #[serde(rename = "ChurchCare", default)]
+    church_care: Option<u32>,
+    #[serde(rename = "TowerBase", default)]
+    towerbase_id: u32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<TowerRow> for TowerRecord {
+    type Error = RecordError;
+
+    fn try_from(r: TowerRow) -> Result<Self, Self::Error> {
+        let coordinates = combine_coordinates(r.lat, r.long, "Lat", "Long")?;
+        let satnav_coordinates =
+            combine_coordinates(r.satnav_lat, r.satnav_long, "SNLat", "SNLong")?;
+        let church_care = r
+            .church_care
+            .map(|id| ChurchCareId::from_dove(id, r.diocese.as_ref(), r.country.as_ref()));
+        Ok(TowerRecord {
+            id: r.id,
+            place: r.place,
+            place2: r.place2,
+            place_county_list: r.place_county_list,
+            dedication: r.dedication,
+            alt_name: r.alt_name,
+            county: r.county,
+            country: r.country,
+            iso_3166_code: r.iso_3166_code,
+            diocese: r.diocese,
+            os_grid_ref: r.os_grid_ref,
+            postcode: r.postcode,
+            coordinates,
+            satnav_coordinates,
+            toilet: r.toilet,
+            url: r.url,
+            building_id: r.building_id,
+            building_grade: r.building_grade,
+            church_care,
+            towerbase_id: r.towerbase_id,
+        })
+    }
+}
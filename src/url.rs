@@ -0,0 +1,122 @@
+//! Links to other websites with information about [`Ring`]s.
+
+use std::fmt::Write;
+
+use crate::{lookup::NO_TOWERBASE_ID, Dedication, Ring};
+
+/// The online maps which [`Ring::map_url`] can link to
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MapProvider {
+    Google,
+    OpenStreetMap,
+    Bing,
+}
+
+impl Ring {
+    /// A link to this `Ring` on an online map, suitable for a 'navigate to this tower' button.
+    /// This uses the [best coordinates](Ring::best_coordinates) if there are any, otherwise it
+    /// searches the map for the postcode or (failing that) the OS grid reference.  Returns `None`
+    /// if Dove's doesn't know where this `Ring` is (e.g. for most mobile rings).
+    pub fn map_url(&self, provider: MapProvider) -> Option<String> {
+        if let Some((coords, _)) = self.best_coordinates() {
+            let (lat, long) = (coords.lat(), coords.long());
+            return Some(match provider {
+                MapProvider::Google => format!(
+                    "https://www.google.com/maps/search/?api=1&query={:.5},{:.5}",
+                    lat, long
+                ),
+                MapProvider::OpenStreetMap => format!(
+                    "https://www.openstreetmap.org/?mlat={lat:.5}&mlon={long:.5}#map=17/{lat:.5}/{long:.5}",
+                    lat = lat,
+                    long = long
+                ),
+                MapProvider::Bing => format!(
+                    "https://www.bing.com/maps?cp={lat:.5}~{long:.5}&lvl=17&sp=point.{lat:.5}_{long:.5}",
+                    lat = lat,
+                    long = long
+                ),
+            });
+        }
+
+        let search = match (&self.postcode, &self.os_grid_ref) {
+            (Some(postcode), _) => postcode.as_str(),
+            (None, Some(grid_ref)) => grid_ref.as_str(),
+            (None, None) => return None,
+        };
+        let search = encode_query(search);
+        Some(match provider {
+            MapProvider::Google => {
+                format!("https://www.google.com/maps/search/?api=1&query={}", search)
+            }
+            MapProvider::OpenStreetMap => {
+                format!("https://www.openstreetmap.org/search?query={}", search)
+            }
+            MapProvider::Bing => format!("https://www.bing.com/maps?q={}", search),
+        })
+    }
+
+    /// A link to this `Ring`'s page on [Dove's Guide](https://dove.cccbr.org.uk), so that sites
+    /// can always link back to the source record.  Dove's gives each tower a single page, so
+    /// every `Ring` in the same tower has the same URL.
+    pub fn doves_url(&self) -> String {
+        format!("https://dove.cccbr.org.uk/tower/{}", self.id.get())
+    }
+
+    /// A link to this tower's record in [TowerBase](https://towerbase.cccbr.org.uk), or `None`
+    /// if Dove's doesn't give a TowerBase ID for this `Ring`
+    pub fn towerbase_url(&self) -> Option<String> {
+        if self.towerbase_id == NO_TOWERBASE_ID {
+            return None;
+        }
+        Some(format!(
+            "https://towerbase.cccbr.org.uk/tower/{}",
+            self.towerbase_id
+        ))
+    }
+
+    /// A link to a search on [BellBoard](https://bb.ringingworld.co.uk) for performances at this
+    /// `Ring`, e.g. to link from a tower's page to its recent peals and quarters.  The search is
+    /// by place, county and dedication (as in [`NameStyle::BellBoard`](crate::NameStyle)), so
+    /// may also find performances at other towers with very similar names.
+    pub fn bellboard_search_url(&self) -> String {
+        let mut place = self.place.to_string();
+        if let Some(place2) = &self.place2 {
+            place.push_str(", ");
+            place.push_str(place2);
+        }
+        let mut url = format!(
+            "https://bb.ringingworld.co.uk/search.php?place={}",
+            encode_query(&place)
+        );
+        if self.is_mobile() {
+            return url;
+        }
+        if let Some(county) = &self.county {
+            write!(url, "&county={}", encode_query(county)).unwrap();
+        }
+        if self.dedication != Dedication::Unknown {
+            write!(
+                url,
+                "&dedication={}",
+                encode_query(self.dedication.as_str())
+            )
+            .unwrap();
+        }
+        url
+    }
+}
+
+/// Percent-encodes a string for use as a value in the query string of a URL
+pub(crate) fn encode_query(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b' ' => encoded.push('+'),
+            _ => write!(encoded, "%{:02X}", byte).unwrap(),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,362 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Ring, RingFilter};
+
+const LBS_PER_QR: u32 = 28;
+const LBS_PER_CWT: u32 = 112;
+const KG_PER_LB: f64 = 0.453_592_37;
+/// The heaviest weight (in pounds) which can be split into `cwt-qr-lb` without overflowing
+const MAX_LBS: f64 = u32::MAX as f64;
+
+/// The `Weight` of the heaviest bell in a [`Ring`](crate::Ring).
+///
+/// `Weight`s are displayed in the hundredweight-quarter-pound notation used by ringers (e.g.
+/// `12-3-4` for 12 cwt, 3 quarters and 4 lbs).  Approximate weights are prefixed with `~` (e.g.
+/// `~12-3-4`).
+#[derive(Debug, Clone, Copy)]
+pub struct Weight {
+    lbs: f64,
+    approximate: bool,
+}
+
+impl Weight {
+    /// Creates an exact `Weight` from a number of pounds
+    pub fn from_lbs(lbs: f64) -> Self {
+        Self {
+            lbs,
+            approximate: false,
+        }
+    }
+
+    /// Returns a copy of this `Weight`, marked as approximate or exact
+    pub fn with_approximate(self, approximate: bool) -> Self {
+        Self {
+            approximate,
+            ..self
+        }
+    }
+
+    /// `true` if this `Weight` is only an estimate (e.g. because the bell has never been
+    /// weighed).
+    ///
+    /// **CSV Header**: `App`; `""` for `false`, `"app"` for `true`
+    pub fn is_approximate(self) -> bool {
+        self.approximate
+    }
+
+    /// Creates a `Weight` from a number of kilograms
+    pub fn from_kg(kg: f64) -> Self {
+        Self::from_lbs(kg / KG_PER_LB)
+    }
+
+    /// Creates a `Weight` from a number of hundredweight, quarters and pounds (e.g. `12-3-4` would
+    /// be `Weight::from_cwt_qr_lb(12, 3, 4)`).
+    pub fn from_cwt_qr_lb(cwt: u32, qr: u32, lb: u32) -> Self {
+        // Sum as `f64`s, since the total number of pounds can overflow a `u32`
+        let lbs = f64::from(cwt) * f64::from(LBS_PER_CWT)
+            + f64::from(qr) * f64::from(LBS_PER_QR)
+            + f64::from(lb);
+        Self::from_lbs(lbs)
+    }
+
+    /// The weight in pounds
+    pub fn lbs(self) -> f64 {
+        self.lbs
+    }
+
+    /// The weight in kilograms
+    pub fn kilograms(self) -> f64 {
+        self.lbs * KG_PER_LB
+    }
+
+    /// The weight in hundredweight (cwt)
+    pub fn cwt(self) -> f64 {
+        self.lbs / LBS_PER_CWT as f64
+    }
+
+    /// Splits this weight into a whole number of hundredweight, quarters and pounds, rounded to
+    /// the nearest pound.
+    pub fn to_cwt_qr_lb(self) -> (u32, u32, u32) {
+        let total_lbs = self.lbs.max(0.0).round() as u32;
+        let cwt = total_lbs / LBS_PER_CWT;
+        let qr = (total_lbs % LBS_PER_CWT) / LBS_PER_QR;
+        let lb = total_lbs % LBS_PER_QR;
+        (cwt, qr, lb)
+    }
+
+    /// The [`WeightClass`] containing this `Weight`
+    pub fn class(self) -> WeightClass {
+        let cwt = self.cwt();
+        WeightClass::ALL
+            .iter()
+            .copied()
+            .rev()
+            .find(|class| cwt >= class.min_cwt() as f64)
+            .unwrap_or(WeightClass::MiniRing)
+    }
+}
+
+/// A rough classification of tenor [`Weight`]s, as used by ringers when choosing towers (e.g.
+/// "a light eight").  Each `WeightClass` is also a [`RingFilter`], matching the [`Ring`]s whose
+/// tenors are in that class (so `Ring`s with no known weight never match).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WeightClass {
+    /// Tenors lighter than 2 cwt, which are almost always mini-rings
+    MiniRing,
+    /// Tenors from 2 cwt up to (but not including) 10 cwt
+    Light,
+    /// Tenors from 10 cwt up to (but not including) 20 cwt
+    Medium,
+    /// Tenors from 20 cwt up to (but not including) 30 cwt
+    Heavy,
+    /// Tenors of 30 cwt or more
+    VeryHeavy,
+}
+
+impl WeightClass {
+    /// Every `WeightClass`, from lightest to heaviest
+    pub const ALL: [WeightClass; 5] = [
+        WeightClass::MiniRing,
+        WeightClass::Light,
+        WeightClass::Medium,
+        WeightClass::Heavy,
+        WeightClass::VeryHeavy,
+    ];
+
+    /// The lightest tenor in this class, in whole hundredweight
+    pub fn min_cwt(self) -> u32 {
+        match self {
+            WeightClass::MiniRing => 0,
+            WeightClass::Light => 2,
+            WeightClass::Medium => 10,
+            WeightClass::Heavy => 20,
+            WeightClass::VeryHeavy => 30,
+        }
+    }
+
+    /// The weight (in whole hundredweight) of the lightest tenor which is too heavy for this
+    /// class, or `None` for [`WeightClass::VeryHeavy`]
+    pub fn max_cwt(self) -> Option<u32> {
+        let next = WeightClass::ALL.get(self as usize + 1)?;
+        Some(next.min_cwt())
+    }
+}
+
+impl Ring {
+    /// The [`WeightClass`] of this `Ring`'s tenor, or `None` if its weight isn't known
+    pub fn weight_class(&self) -> Option<WeightClass> {
+        self.weight.map(Weight::class)
+    }
+}
+
+impl RingFilter for WeightClass {
+    fn matches(&self, ring: &Ring) -> bool {
+        ring.weight_class() == Some(*self)
+    }
+}
+
+impl Display for WeightClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WeightClass::MiniRing => "mini-ring",
+            WeightClass::Light => "light",
+            WeightClass::Medium => "medium",
+            WeightClass::Heavy => "heavy",
+            WeightClass::VeryHeavy => "very heavy",
+        })
+    }
+}
+
+impl PartialEq for Weight {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Weight {}
+
+impl PartialOrd for Weight {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// `Weight`s are ordered by their number of pounds.  Equal weights are ordered with exact weights
+/// before approximate ones.
+impl Ord for Weight {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.lbs
+            .total_cmp(&other.lbs)
+            .then(self.approximate.cmp(&other.approximate))
+    }
+}
+
+impl Display for Weight {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (cwt, qr, lb) = self.to_cwt_qr_lb();
+        if self.approximate {
+            write!(f, "~")?;
+        }
+        write!(f, "{}-{}-{}", cwt, qr, lb)
+    }
+}
+
+impl FromStr for Weight {
+    type Err = ParseWeightError;
+
+    /// Parses a `Weight` in the `cwt-qr-lb` format (e.g. `"12-3-4"`), or as a decimal number of
+    /// hundredweight or kilograms (e.g. `"12.5 cwt"` or `"650 kg"`).  A leading `~` marks the
+    /// weight as approximate.  Weights too heavy to be split back into `cwt-qr-lb` are rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseWeightError(s.to_owned());
+        let trimmed = s.trim();
+        let (approximate, trimmed) = match trimmed.strip_prefix('~') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, trimmed),
+        };
+        // Decimal hundredweight or kilograms
+        let decimal =
+            |number: &str, to_weight: fn(f64) -> Self| -> Result<Self, ParseWeightError> {
+                let weight = to_weight(number.trim().parse().map_err(|_| err())?);
+                // This also rejects `NaN`, which isn't contained in any range
+                if (0.0..=MAX_LBS).contains(&weight.lbs.round()) {
+                    Ok(weight.with_approximate(approximate))
+                } else {
+                    Err(err())
+                }
+            };
+        if let Some(cwt) = trimmed.strip_suffix("cwt") {
+            return decimal(cwt, |cwt| Self::from_lbs(cwt * f64::from(LBS_PER_CWT)));
+        }
+        if let Some(kg) = trimmed.strip_suffix("kg") {
+            return decimal(kg, Self::from_kg);
+        }
+
+        let mut parts = trimmed.split('-');
+        let mut next_part = || -> Result<u32, ParseWeightError> {
+            parts
+                .next()
+                .ok_or_else(err)?
+                .trim()
+                .parse()
+                .map_err(|_| err())
+        };
+        let cwt = next_part()?;
+        let qr = next_part()?;
+        let lb = next_part()?;
+        if parts.next().is_some() || qr >= 4 || lb >= LBS_PER_QR {
+            return Err(err());
+        }
+        let weight = Self::from_cwt_qr_lb(cwt, qr, lb);
+        if weight.lbs > MAX_LBS {
+            return Err(err());
+        }
+        Ok(weight.with_approximate(approximate))
+    }
+}
+
+/// The error generated when parsing an invalid [`Weight`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseWeightError(String);
+
+impl Display for ParseWeightError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} is not a weight in the form `cwt-qr-lb`, `<cwt> cwt` or `<kg> kg`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseWeightError {}
+
+#[cfg(test)]
+mod tests {
+    use super::Weight;
+
+    fn lbs(s: &str) -> Option<f64> {
+        s.parse::<Weight>().ok().map(Weight::lbs)
+    }
+
+    #[test]
+    fn parse_cwt_qr_lb() {
+        assert_eq!(lbs("12-3-4"), Some(1432.0));
+        assert_eq!(lbs(" 0-0-0 "), Some(0.0));
+        assert_eq!(lbs("4-1-27"), Some(503.0));
+        let approx = "~12-3-4".parse::<Weight>().unwrap();
+        assert!(approx.is_approximate());
+        assert_eq!(approx.to_string(), "~12-3-4");
+        assert_eq!(
+            "61-2-2".parse::<Weight>().unwrap().to_cwt_qr_lb(),
+            (61, 2, 2)
+        );
+    }
+
+    #[test]
+    fn parse_decimal_cwt() {
+        assert_eq!(lbs("12.5 cwt"), Some(1400.0));
+        assert_eq!(lbs("10cwt"), Some(1120.0));
+        assert!("~7 cwt".parse::<Weight>().unwrap().is_approximate());
+    }
+
+    #[test]
+    fn parse_kg() {
+        let weight = "650 kg".parse::<Weight>().unwrap();
+        assert!((weight.kilograms() - 650.0).abs() < 1e-9);
+        assert_eq!(weight.to_cwt_qr_lb(), (12, 3, 5));
+        assert!("~1000kg".parse::<Weight>().unwrap().is_approximate());
+    }
+
+    #[test]
+    fn parse_heaviest() {
+        let weight = "38347922-1-3".parse::<Weight>().unwrap();
+        assert_eq!(weight.lbs(), f64::from(u32::MAX));
+        assert_eq!(weight.to_string(), "38347922-1-3");
+        let weight = "38347922 cwt".parse::<Weight>().unwrap();
+        assert_eq!(weight.to_string(), "38347922-0-0");
+    }
+
+    #[test]
+    fn parse_malformed() {
+        for s in [
+            "",
+            "12",
+            "12-3",
+            "12-3-4-5",
+            "12-4-0",
+            "12-0-28",
+            "-1-0-0",
+            "a-b-c",
+            "12.5-0-0",
+            "cwt",
+            "-3 cwt",
+            "NaN kg",
+            "inf kg",
+            "12 lbs",
+            "40000000-0-0",
+            "4294967295-3-27",
+            "38347922-1-4",
+            "1e308 cwt",
+            "1e300 cwt",
+            "38347923 cwt",
+            "5e9 kg",
+        ] {
+            assert_eq!(lbs(s), None, "{:?} should fail to parse", s);
+        }
+    }
+
+    #[test]
+    fn from_cwt_qr_lb_doesnt_overflow() {
+        let weight = Weight::from_cwt_qr_lb(u32::MAX, 3, 27);
+        assert_eq!(weight.lbs(), f64::from(u32::MAX) * 112.0 + 111.0);
+    }
+}